@@ -18,6 +18,8 @@ pub struct Argument {
     pub name: Ident,
     pub ty: Box<Ast>,
     pub default: Option<Ast>,
+    /// Whether it's declared with `.=`, which makes it mutable
+    pub is_mut: bool,
 }
 
 impl GetSpan for Argument {
@@ -65,9 +67,10 @@ impl Argument {
 impl Reconstruct for Argument {
     fn reconstruct(&self) -> String {
         format!(
-            "{}: {}: {}",
+            "{}: {}{}{}",
             self.name.reconstruct(),
             self.ty.reconstruct(),
+            if self.is_mut { " .= " } else { ": " },
             self.default
                 .as_ref()
                 .map_or(String::new(), Reconstruct::reconstruct)
@@ -49,7 +49,7 @@ impl AstData for BinaryOpr {
                     Arc::clone(ty2).into()
                 }
             }),
-            _ => unreachable!(),
+            opr => Err(ZError::t026(opr).with_span(&self.opr_span)),
         }
     }
 
@@ -95,7 +95,13 @@ impl AstData for BinaryOpr {
                             OprType::Gt => "_gt",
                             OprType::Ge => "_ge",
                             OprType::Concat => "_concat",
-                            _ => unimplemented!("{:#?}", self.ty),
+                            OprType::BitAnd => "_and",
+                            OprType::BitOr => "_or",
+                            OprType::BitXor => "_xor",
+                            OprType::Lsh => "_lsh",
+                            OprType::Rsh => "_rsh",
+                            OprType::Zrsh => "_zrsh",
+                            opr => return Err(ZError::t026(opr).with_span(&self.opr_span)),
                         }
                         .into(),
                         name_span: None,
@@ -116,47 +122,26 @@ impl AstData for BinaryOpr {
     fn interpret_expr(&self, val_symt: &mut InterpretSymTable) -> ZResult<Value> {
         let operand1 = self.operand1.interpret_expr(val_symt)?;
         let operand2 = self.operand2.interpret_expr(val_symt)?;
+        // both operands have been cast to `bool`, but a `_typecast` can give anything
+        let as_bool = |v: Value| match v {
+            Value::Bool(b) => Ok(b),
+            v => Err(ZError::t011(&BOOL_T, &v.ty()).with_span(self)),
+        };
         match self.ty {
-            OprType::And => {
-                if let Value::Bool(b) = operand1 {
-                    if b {
-                        if let Value::Bool(b) = operand2 {
-                            Ok(Value::Bool(b))
-                        } else {
-                            panic!()
-                        }
-                    } else {
-                        Ok(Value::Bool(false))
-                    }
-                } else {
-                    panic!()
-                }
-            }
-            OprType::Or => {
-                if let Value::Bool(b) = operand1 {
-                    if b {
-                        Ok(Value::Bool(true))
-                    } else if let Value::Bool(b) = operand2 {
-                        Ok(Value::Bool(b))
-                    } else {
-                        panic!()
-                    }
-                } else {
-                    panic!()
-                }
-            }
+            OprType::And => Ok(Value::Bool(as_bool(operand1)? && as_bool(operand2)?)),
+            OprType::Or => Ok(Value::Bool(as_bool(operand1)? || as_bool(operand2)?)),
             OprType::TypeCast => {
                 let opr1_ty = Arc::clone(&operand1.value_ty());
                 let namespace = opr1_ty.namespace();
                 let Some(f) = namespace.get("_typecast") else {
-                    return Err(ZError::t005(&opr1_ty.to_type(), "_typecast"));
+                    return Err(ZError::t005(&opr1_ty.to_type(), "_typecast").with_span(self));
                 };
                 let Value::Proc(proc) = f else {
-                    return Err(ZError::t011(&PROC_T, &f.ty()))
+                    return Err(ZError::t011(&PROC_T, &f.ty()).with_span(self));
                 };
                 proc.call(vec![operand1, operand2], val_symt)
             }
-            _opr => panic!("{_opr:?}"),
+            opr => Err(ZError::t026(opr).with_span(&self.opr_span)),
         }
     }
 }
@@ -65,12 +65,15 @@ impl Block {
         for ele in &mut self.content {
             last = ele.type_check(ty_symt)?;
         }
-        ty_symt.set_block_return(
-            Arc::clone(&last),
-            self.content.last().and_then(GetSpan::span),
-        )?;
+        // only a proc's or file's own block gives its last value as a return; a nested block like
+        // an `if`'s has its own frame and just evaluates to it, so it mustn't set the return type
         if add_set {
             ty_symt.pop_frame()?;
+        } else {
+            ty_symt.set_block_return(
+                Arc::clone(&last),
+                self.content.last().and_then(GetSpan::span),
+            )?;
         }
         Ok(last)
     }
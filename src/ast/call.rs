@@ -1,21 +1,45 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use itertools::{Either, Itertools};
 use smol_str::SmolStr;
 use tracing::debug;
 
 use crate::{
-    ast::{Ast, AstData, BinaryOpr, Ident, Member, Reconstruct},
+    ast::{Ast, AstData, BinaryOpr, Ident, Literal, Member, Reconstruct},
     errors::{ToZResult, ZError},
-    primitives::{ANY_T, PROC_T, UNIT_T},
+    primitives::{
+        adapt_int_literal, common_numeric_type, is_numeric, proc_signature,
+        utils::{spec_is_valid, FLOAT_SPEC_T, INT_SPEC_T},
+        widen, widens_to, ANY_T, F64_T, NUMBER_T, PROC_T, STR_T, VARGS_T,
+    },
     types::{
         position::{GetSpan, Span},
-        r#type::TypeCheckType,
+        r#type::{TypeCheckType, ValueType},
+        sym_table::InterpretFrameType,
         token::{AccessType, OprType},
+        value::Proc,
     },
     InterpretSymTable, Type, TypeCheckSymTable, Value, ZResult,
 };
 
+/// Methods that binary operators desugar to, whose operands are promoted to a common numeric type
+/// (shifts aren't, since they keep the left operand's type and take an `i64` amount)
+const PROMOTING_METHODS: [&str; 14] = [
+    "_add", "_sub", "_mul", "_div", "_rem", "_eq", "_ne", "_lt", "_le", "_gt", "_ge", "_and",
+    "_or", "_xor",
+];
+
+/// Methods of other binary operators, which fall back to `_r`-prefixed methods of the right operand
+const BINARY_METHODS: [&str; 12] = [
+    "_add", "_sub", "_mul", "_div", "_rem", "_concat", "_and", "_or", "_xor", "_lsh", "_rsh",
+    "_zrsh",
+];
+/// Comparison methods, which can be derived from `_cmp` if a type doesn't define them
+const COMPARISON_METHODS: [&str; 6] = ["_eq", "_ne", "_lt", "_le", "_gt", "_ge"];
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct Call {
     pub called: Box<Ast>,
@@ -40,50 +64,30 @@ impl AstData for Call {
     }
     fn type_check(&mut self, ty_symt: &mut TypeCheckSymTable) -> ZResult<TypeCheckType> {
         debug!(span = ?self.span(), "Type-checking function call");
-        if let Ast::Member(Member { name, parent, .. }) = &*self.called {
-            if let Ast::Ident(Ident {
-                name: parent_name, ..
-            }) = &**parent
-            {
-                if &**name == "out" && &**parent_name == "ter" {
-                    self.args
-                        .iter_mut()
-                        .map(|a| a.type_check(ty_symt))
-                        .collect::<ZResult<Vec<_>>>()?;
-                    return Ok(Arc::clone(&UNIT_T).into());
-                }
+        let mut operand_tys = self.operand_types(ty_symt)?;
+        if let Some(operand_tys) = &mut operand_tys {
+            self.promote_operands(operand_tys)?;
+        }
+        if let Some(tys) = &operand_tys {
+            if self.resolve_operator(tys) {
+                operand_tys = None;
             }
         }
         let called_type = self.called.type_check(ty_symt)?;
-        let arg_tys = self
-            .args
-            .iter_mut()
-            .map(|a| a.type_check(ty_symt))
-            .collect::<ZResult<Vec<_>>>()?;
-        let extract_proc = |ty: &Type| {
-            if let Type::Generic { type_args, base } = ty {
-                if !Arc::ptr_eq(base, &PROC_T) {
-                    None
-                } else if let Some((_, sig_arg_tys)) = type_args.iter().find(|(k, _)| *k == "A") {
-                    if let Some((_, ret_ty)) = type_args.iter().find(|(k, _)| *k == "R") {
-                        let Either::Right(Either::Left(sig_arg_tys)) = sig_arg_tys else {
-                        unreachable!()
-                    };
-                        let Either::Right(Either::Right(ret_ty)) = ret_ty else {
-                        unreachable!()
-                    };
-                        Some((sig_arg_tys.to_owned(), Arc::clone(ret_ty)))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            } else {
-                None
+        if let TypeCheckType::Const(ty) = &called_type {
+            if let Some(params) = ty.params() {
+                return self.type_check_construction(ty, &params.to_owned(), ty_symt);
             }
+        }
+        let arg_tys = if let Some(operand_tys) = operand_tys {
+            operand_tys
+        } else {
+            self.args
+                .iter_mut()
+                .map(|a| Ok(Arc::clone(&*a.type_check(ty_symt)?)))
+                .collect::<ZResult<Vec<_>>>()?
         };
-        let (sig_arg_tys, ret_ty) = if let Some(res) = extract_proc(&called_type) {
+        let (mut sig_arg_tys, ret_ty) = if let Some(res) = proc_signature(&called_type) {
             res
         } else {
             let mut ty = called_type;
@@ -100,7 +104,7 @@ impl AstData for Call {
                     })
                     .desugared()?,
                 );
-                if let Some(res) = extract_proc(&f) {
+                if let Some(res) = proc_signature(&f) {
                     out = Some(res);
                     break;
                 }
@@ -112,14 +116,22 @@ impl AstData for Call {
                 return Err(ZError::t005(&ty, "_call"));
             }
         };
+        // a variadic proc takes any number of `_any`s in place of its last argument
+        if sig_arg_tys
+            .last()
+            .is_some_and(|ty| Arc::ptr_eq(ty, &VARGS_T))
+            && arg_tys.len() >= sig_arg_tys.len() - 1
+        {
+            sig_arg_tys.truncate(sig_arg_tys.len() - 1);
+            sig_arg_tys.resize(arg_tys.len(), Arc::clone(&ANY_T));
+        }
         if arg_tys.len() != sig_arg_tys.len() {
             return Err(ZError::t015(sig_arg_tys.len(), arg_tys.len()).with_span(&*self));
         }
-        for (arg_ty, sig_arg_ty) in arg_tys.iter().zip(&sig_arg_tys) {
-            if !Arc::ptr_eq(arg_ty, sig_arg_ty) && !Arc::ptr_eq(sig_arg_ty, &ANY_T) {
-                return Err(ZError::t004(sig_arg_ty, arg_ty).with_span(&*self));
-            }
+        for ((arg, arg_ty), sig_arg_ty) in self.args.iter_mut().zip(&arg_tys).zip(&sig_arg_tys) {
+            Self::coerce_arg(arg, arg_ty, sig_arg_ty)?;
         }
+        self.check_format_spec(&arg_tys, &sig_arg_tys)?;
         Ok(ret_ty.into())
     }
 
@@ -170,34 +182,349 @@ impl AstData for Call {
     }
 
     fn interpret_expr(&self, val_symt: &mut InterpretSymTable) -> ZResult<Value> {
-        if let Ast::Member(Member { name, parent, .. }) = &*self.called {
-            if let Ast::Ident(Ident {
-                name: parent_name, ..
-            }) = &**parent
-            {
-                if &**name == "out" && &**parent_name == "ter" {
-                    let s = self
-                        .args
-                        .iter()
-                        .map(|arg| arg.interpret_expr(val_symt))
-                        .collect::<Result<Vec<_>, _>>()?
-                        .into_iter()
-                        .map(|v| v.to_string())
-                        .collect::<Vec<String>>()
-                        .join(" ");
-                    println!("{s}");
-                    return Ok(Value::Unit);
+        // methods are called with the type they're from as `$ty`
+        let (called, self_ty) = if let Ast::Member(Member {
+            ty: AccessType::Namespace,
+            name,
+            parent,
+            ..
+        }) = &*self.called
+        {
+            let parent = parent.interpret_expr(val_symt)?;
+            let ty = parent.as_type().z()?;
+            (
+                ty.namespace().get(name).z()?.to_owned(),
+                Some(Value::Type(Arc::clone(ty))),
+            )
+        } else {
+            (self.called.interpret_expr(val_symt)?, None)
+        };
+        if let Value::Type(ty) = &called {
+            if let ValueType::Type(def) = &**ty {
+                if let Some(params) = &def.params {
+                    return self.construct(ty, params, val_symt);
                 }
             }
         }
-        let proc = self.called.interpret_expr(val_symt)?.into_proc().z()?;
-        proc.call(
-            self.args
+        let proc = called.into_proc().z()?;
+        let args = self
+            .args
+            .iter()
+            .map(|a| a.interpret_expr(val_symt))
+            .collect::<ZResult<Vec<_>>>()?;
+        let res = if let (Proc::Defined { .. }, Some(self_ty)) = (&proc, self_ty) {
+            val_symt.add_frame(InterpretFrameType::Normal);
+            val_symt.declare_val("$ty", self_ty);
+            let res = proc.call(args, val_symt);
+            val_symt.pop_frame()?;
+            res
+        } else {
+            proc.call(args, val_symt)
+        };
+        res.map_err(|e| {
+            if e.pos.is_empty() {
+                e.with_span(self)
+            } else {
+                e
+            }
+        })
+    }
+}
+
+impl Call {
+    /// Widens `arg` to the signature's type if needed, erroring if it can't be passed as one
+    fn coerce_arg(arg: &mut Ast, arg_ty: &Arc<Type>, sig_arg_ty: &Arc<Type>) -> ZResult<()> {
+        if Arc::ptr_eq(arg_ty, sig_arg_ty) || Arc::ptr_eq(sig_arg_ty, &ANY_T) {
+            Ok(())
+        } else if Arc::ptr_eq(sig_arg_ty, &NUMBER_T) {
+            if is_numeric(arg_ty) {
+                Ok(())
+            } else {
+                Err(ZError::t004(&F64_T, arg_ty).with_span(&*arg))
+            }
+        } else if Arc::ptr_eq(sig_arg_ty, &INT_SPEC_T) || Arc::ptr_eq(sig_arg_ty, &FLOAT_SPEC_T) {
+            if Arc::ptr_eq(arg_ty, &STR_T) {
+                Ok(())
+            } else {
+                Err(ZError::t004(&STR_T, arg_ty).with_span(&*arg))
+            }
+        } else if widens_to(arg_ty, sig_arg_ty) {
+            *arg = widen(arg.to_owned(), sig_arg_ty);
+            Ok(())
+        } else if is_numeric(arg_ty) && is_numeric(sig_arg_ty) {
+            Err(ZError::t020(arg_ty, sig_arg_ty).with_span(&*arg))
+        } else if let Some((sig_params, sig_ret)) = proc_signature(sig_arg_ty) {
+            Self::coerce_callback(arg, arg_ty, sig_arg_ty, &sig_params, &sig_ret)
+        } else {
+            Err(ZError::t004(sig_arg_ty, arg_ty).with_span(&*arg))
+        }
+    }
+    /// Checks a literal format spec, like an f-string's, against the type of the value it formats
+    fn check_format_spec(&self, arg_tys: &[Arc<Type>], sig_arg_tys: &[Arc<Type>]) -> ZResult<()> {
+        let (
+            Some(this_ty),
+            Some(spec_ty),
+            Some(Ast::Literal(Literal {
+                content: Value::Str(spec),
+                ..
+            })),
+        ) = (arg_tys.first(), sig_arg_tys.get(1), self.args.get(1))
+        else {
+            return Ok(());
+        };
+        let float = Arc::ptr_eq(spec_ty, &FLOAT_SPEC_T);
+        if (float || Arc::ptr_eq(spec_ty, &INT_SPEC_T)) && !spec_is_valid(spec, float) {
+            return Err(ZError::t031(spec, this_ty).with_span(self));
+        }
+        Ok(())
+    }
+    /// Checks a proc passed where a builtin like `collections::map` takes a callback, whose
+    /// arguments are only known when it's called but whose arity and return type aren't
+    fn coerce_callback(
+        arg: &Ast,
+        arg_ty: &Arc<Type>,
+        sig_arg_ty: &Arc<Type>,
+        sig_params: &[Arc<Type>],
+        sig_ret: &Arc<Type>,
+    ) -> ZResult<()> {
+        // a `proc` with no signature can only be checked when it's called
+        if Arc::ptr_eq(arg_ty, &PROC_T) {
+            return Ok(());
+        }
+        let Some((params, ret)) = proc_signature(arg_ty) else {
+            return Err(ZError::t004(sig_arg_ty, arg_ty).with_span(arg));
+        };
+        if params.len() != sig_params.len() {
+            return Err(ZError::t015(sig_params.len(), params.len()).with_span(arg));
+        }
+        let accepts = |a: &Arc<Type>, b: &Arc<Type>| {
+            Arc::ptr_eq(a, b) || Arc::ptr_eq(a, &ANY_T) || Arc::ptr_eq(b, &ANY_T)
+        };
+        if !sig_params.iter().zip(&params).all(|(a, b)| accepts(a, b)) {
+            return Err(ZError::t004(sig_arg_ty, arg_ty).with_span(arg));
+        }
+        if !accepts(sig_ret, &ret) {
+            return Err(ZError::t011(sig_ret, &ret).with_span(arg));
+        }
+        Ok(())
+    }
+    /// Checks the positional and named arguments of a struct construction against its fields
+    fn type_check_construction(
+        &mut self,
+        ty: &Arc<Type>,
+        params: &[(SmolStr, bool)],
+        ty_symt: &mut TypeCheckSymTable,
+    ) -> ZResult<TypeCheckType> {
+        if self.args.len() > params.len() {
+            return Err(ZError::t015(params.len(), self.args.len()).with_span(&*self));
+        }
+        let fields = ty.fields();
+        let mut given = HashSet::new();
+        let positional = self
+            .args
+            .iter_mut()
+            .zip(params)
+            .map(|(arg, (name, _))| (name, arg));
+        for (name, arg) in positional.chain(self.kwargs.iter_mut()) {
+            let field_ty = params
                 .iter()
-                .map(|a| a.interpret_expr(val_symt))
-                .collect::<ZResult<Vec<_>>>()?,
-            val_symt,
-        )
+                .any(|(param, _)| param == name)
+                .then(|| fields.get(name))
+                .flatten();
+            let Some(field_ty) = field_ty else {
+                return Err(ZError::t005(ty, name).with_span(&*arg));
+            };
+            if !given.insert(name.to_owned()) {
+                return Err(ZError::t022(name).with_span(&*arg));
+            }
+            let arg_ty = arg.type_check(ty_symt)?;
+            Self::coerce_arg(arg, &arg_ty, field_ty)?;
+        }
+        if let Some((name, _)) = params
+            .iter()
+            .find(|(name, has_default)| !has_default && !given.contains(name))
+        {
+            return Err(ZError::t023(name).with_span(&*self));
+        }
+        Ok(Arc::clone(ty).into())
+    }
+    /// Creates an instance of a struct, filling fields that aren't given with their defaults
+    fn construct(
+        &self,
+        ty: &Arc<ValueType>,
+        params: &[(SmolStr, Option<Value>)],
+        val_symt: &mut InterpretSymTable,
+    ) -> ZResult<Value> {
+        let attrs = params
+            .iter()
+            .enumerate()
+            .map(|(i, (name, default))| {
+                let value = match self.args.get(i).or_else(|| self.kwargs.get(name)) {
+                    Some(arg) => arg.interpret_expr(val_symt)?,
+                    None => default.to_owned().z()?,
+                };
+                Ok((name.to_string(), value))
+            })
+            .collect::<ZResult<HashMap<_, _>>>()?;
+        Ok(Value::ClassInstance {
+            ty: Arc::clone(ty),
+            attrs,
+        })
+    }
+    /// The desugared form of calling the operator method `name` on `lhs` and `rhs`
+    fn operator_call(name: &str, lhs: Ast, rhs: Ast) -> Self {
+        Self {
+            called: Box::new(Ast::Member(Member {
+                ty: AccessType::Namespace,
+                name: name.into(),
+                parent: Box::new(Ast::BinaryOpr(BinaryOpr {
+                    ty: OprType::TypeCast,
+                    opr_span: None,
+                    operand1: Box::new(lhs.to_owned()),
+                    operand2: Box::new(Ast::Ident(Ident::new("type"))),
+                })),
+                name_span: None,
+                dot_span: None,
+            })),
+            paren_spans: None,
+            args: vec![lhs, rhs],
+            kwargs: HashMap::default(),
+        }
+    }
+    /// Whether `ty` has a method `name` that takes a value of type `other` as its second argument
+    fn has_operator(ty: &Type, name: &str, other: &Arc<Type>) -> bool {
+        let Some(method) = ty.namespace().get(name).map(|m| Arc::clone(m)) else {
+            return false;
+        };
+        let Type::Generic { type_args, .. } = &*method else {
+            return false;
+        };
+        type_args.iter().any(|(k, v)| {
+            k == "A"
+                && matches!(v, Either::Right(Either::Left(sig_arg_tys))
+                if sig_arg_tys.get(1).is_some_and(|sig_arg_ty| {
+                    Arc::ptr_eq(sig_arg_ty, other)
+                        || Arc::ptr_eq(sig_arg_ty, &ANY_T)
+                        || widens_to(other, sig_arg_ty)
+                }))
+        })
+    }
+    /// The method that a binary operator's method falls back to on its right operand
+    fn reflected(name: &str) -> SmolStr {
+        match name {
+            "_lt" => "_gt".into(),
+            "_le" => "_ge".into(),
+            "_gt" => "_lt".into(),
+            "_ge" => "_le".into(),
+            "_eq" | "_ne" => name.into(),
+            _ => format!("_r{}", name.strip_prefix('_').unwrap_or(name)).into(),
+        }
+    }
+    /// If this is a desugared binary operator, type-checks its operands
+    fn operand_types(
+        &mut self,
+        ty_symt: &mut TypeCheckSymTable,
+    ) -> ZResult<Option<Vec<Arc<Type>>>> {
+        let Ast::Member(Member {
+            ty: AccessType::Namespace,
+            parent,
+            ..
+        }) = &*self.called else {
+            return Ok(None);
+        };
+        if self.args.len() != 2
+            || !matches!(
+                &**parent,
+                Ast::BinaryOpr(BinaryOpr {
+                    ty: OprType::TypeCast,
+                    ..
+                })
+            )
+        {
+            return Ok(None);
+        }
+        self.args
+            .iter_mut()
+            .map(|a| Ok(Arc::clone(&*a.type_check(ty_symt)?)))
+            .collect::<ZResult<Vec<_>>>()
+            .map(Some)
+    }
+    /// If the left operand of a desugared binary operator can't handle the right, falls back to
+    /// its `_cmp`, then to the reflected method of the right operand, then to the right's `_cmp`.
+    /// Returns whether the call was rewritten.
+    fn resolve_operator(&mut self, operand_tys: &[Arc<Type>]) -> bool {
+        let Ast::Member(Member { name, .. }) = &*self.called else {
+            return false;
+        };
+        if !BINARY_METHODS.contains(&&**name) && !COMPARISON_METHODS.contains(&&**name) {
+            return false;
+        }
+        let name = name.to_owned();
+        let (lhs, rhs) = (self.args[0].to_owned(), self.args[1].to_owned());
+        let (lhs_ty, rhs_ty) = (&operand_tys[0], &operand_tys[1]);
+        let is_comparison = COMPARISON_METHODS.contains(&&*name);
+        let reflected = Self::reflected(&name);
+        // `a < b` becomes `a:._cmp(b) < 0`
+        let from_cmp = |lhs: Ast, rhs: Ast, name: &str| {
+            Self::operator_call(
+                name,
+                Self::operator_call("_cmp", lhs, rhs).as_variant(),
+                Value::I32(0).as_ast(),
+            )
+        };
+        *self = if Self::has_operator(lhs_ty, &name, rhs_ty) {
+            return false;
+        } else if is_comparison && Self::has_operator(lhs_ty, "_cmp", rhs_ty) {
+            from_cmp(lhs, rhs, &name)
+        } else if Self::has_operator(rhs_ty, &reflected, lhs_ty) {
+            Self::operator_call(&reflected, rhs, lhs)
+        } else if is_comparison && Self::has_operator(rhs_ty, "_cmp", lhs_ty) {
+            from_cmp(rhs, lhs, &reflected)
+        } else {
+            return false;
+        };
+        debug!(method = ?self.called.reconstruct(), "Resolved operator");
+        true
+    }
+    /// Widens the operands of a desugared binary operator to their common numeric type
+    fn promote_operands(&mut self, operand_tys: &mut [Arc<Type>]) -> ZResult<()> {
+        let Self { called, args, .. } = self;
+        let Ast::Member(Member { name, parent, .. }) = &mut **called else {
+            return Ok(());
+        };
+        let Ast::BinaryOpr(BinaryOpr { operand1, .. }) = &mut **parent else {
+            return Ok(());
+        };
+        if !PROMOTING_METHODS.contains(&&**name) {
+            return Ok(());
+        }
+        let (ty1, ty2) = (&operand_tys[0], &operand_tys[1]);
+        if Arc::ptr_eq(ty1, ty2) || !is_numeric(ty1) || !is_numeric(ty2) {
+            return Ok(());
+        }
+        // an integer literal takes the other operand's type if it fits, so `x += 1` keeps `x`'s type
+        for (i, other) in [(0, 1), (1, 0)] {
+            if let Some(literal) = adapt_int_literal(&args[i], &operand_tys[other]) {
+                debug!(ty = %operand_tys[other], "Adapting integer literal");
+                args[i] = literal;
+                operand_tys[i] = Arc::clone(&operand_tys[other]);
+                **operand1 = args[0].to_owned();
+                return Ok(());
+            }
+        }
+        let (ty1, ty2) = (&operand_tys[0], &operand_tys[1]);
+        let Some(target) = common_numeric_type(ty1, ty2) else {
+            return Err(ZError::t021(ty1, ty2).with_span(args.as_slice()));
+        };
+        debug!(%target, "Promoting operands");
+        for (arg, ty) in args.iter_mut().zip(operand_tys.iter_mut()) {
+            if !Arc::ptr_eq(ty, &target) {
+                *arg = widen(arg.to_owned(), &target);
+                *ty = Arc::clone(&target);
+            }
+        }
+        **operand1 = args[0].to_owned();
+        Ok(())
     }
 }
 
@@ -206,7 +533,15 @@ impl Reconstruct for Call {
         format!(
             "{} ( {} )",
             self.called.reconstruct(),
-            self.args.iter().map(Reconstruct::reconstruct).join(" , ")
+            self.args
+                .iter()
+                .map(Reconstruct::reconstruct)
+                .chain(
+                    self.kwargs
+                        .iter()
+                        .map(|(name, arg)| format!("{name} : {}", arg.reconstruct()))
+                )
+                .join(" , ")
         )
     }
 }
@@ -1,16 +1,21 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use itertools::Itertools;
+use once_cell::sync::OnceCell;
 use smol_str::SmolStr;
 use tracing::debug;
 
 use crate::{
-    ast::{argument::Argument, Ast, AstData, Block, Reconstruct},
+    ast::{argument::Argument, Ast, AstData, Block, Ident, Reconstruct},
     errors::{ToZResult, ZError},
+    primitives::instance_namespace,
     types::{
         position::{GetSpan, Span},
-        r#type::{TypeCheckType, ValueType},
-        sym_table::TypeCheckFrameType,
+        r#type::{LazyType, TypeCheckType, TypeDef, ValueType, ValueTypeDef},
+        sym_table::{InterpretFrameType, TypeCheckFrameType},
         token::Flag,
     },
     InterpretSymTable, Type, TypeCheckSymTable, Value, ZResult,
@@ -23,13 +28,18 @@ pub enum Class {
         kwd_span: Option<Span>,
         content: Option<Block>,
         args: Option<Vec<Argument>>,
+        /// The name the class is declared under, if any
+        name: Option<Ident>,
     },
     TypeChecked {
         is_struct: bool,
         span: Option<Span>,
         reconstruction: String,
-        namespace: HashMap<SmolStr, Ast>,
+        namespace: Vec<(SmolStr, Ast)>,
         fields: HashMap<SmolStr, Arc<Type>>,
+        mutable_fields: HashSet<SmolStr>,
+        args: Option<Vec<Argument>>,
+        name: Option<Ident>,
     },
 }
 
@@ -54,37 +64,47 @@ impl AstData for Class {
 
     fn type_check(&mut self, ty_symt: &mut TypeCheckSymTable) -> ZResult<TypeCheckType> {
         debug!(span = ?self.span(), "Type-checking class declaration");
-        let (is_struct, content, args) = match self {
+        let (is_struct, content, args, name) = match self {
             Self::Raw {
                 is_struct,
                 content,
                 args,
+                name,
                 ..
-            } => (is_struct, content, args),
+            } => (is_struct, content, args, name),
             Self::TypeChecked {
-                namespace, fields, ..
+                is_struct,
+                namespace,
+                fields,
+                mutable_fields,
+                args,
+                name,
+                ..
             } => {
                 let mut ty_symt = ty_symt.to_owned();
-                return Ok(Arc::new(Type::Type {
-                    name: None,
-                    namespace: namespace
-                        .iter_mut()
-                        .map(|(k, v)| {
-                            Ok((
-                                k.to_owned(),
-                                Arc::clone(&*v.type_check(&mut ty_symt)?).into(),
-                            ))
-                        })
-                        .collect::<ZResult<HashMap<_, _>>>()?,
-                    fields: fields.to_owned(),
-                    type_args: vec![],
-                })
-                .into());
+                let (ty, cells) = Self::class_type(
+                    name.to_owned(),
+                    namespace.iter().map(|(k, _)| k),
+                    fields.to_owned(),
+                    mutable_fields.to_owned(),
+                    Self::params(*is_struct, args.as_deref()),
+                );
+                ty_symt.add_frame(TypeCheckFrameType::Function(None));
+                ty_symt.declare_val(
+                    "$ty",
+                    TypeCheckType::Const(Arc::clone(&ty)),
+                    false,
+                    None::<Span>,
+                )?;
+                for (k, v) in namespace {
+                    let _ = cells[&*k].set(Arc::clone(&*v.type_check(&mut ty_symt)?));
+                }
+                return Ok(TypeCheckType::Const(ty));
             }
         };
-        let mut namespace_ast = HashMap::new();
-        let mut namespace_ty = HashMap::new();
+        let mut namespace_ast = vec![];
         let mut fields = HashMap::new();
+        let mut mutable_fields = HashSet::new();
         let mut new_span = None;
 
         ty_symt.add_frame(TypeCheckFrameType::Function(None));
@@ -95,12 +115,12 @@ impl AstData for Class {
         } else {
             &mut empty
         };
-        for statement in statements {
-            let ty = statement.type_check(ty_symt)?;
+        let mut namespace_names = vec![];
+        for statement in statements.iter() {
             let Ast::Declare(dec) = statement else {
-                return Err(ZError::t013().with_span(&*statement))
+                return Err(ZError::t013().with_span(statement))
             };
-            let Ast::Ident(ident) = *dec.variable.to_owned() else {
+            let Ast::Ident(ident) = &*dec.variable else {
                 return Err(ZError::t008().with_span(&dec.variable))
             };
             if ident.name == "_new" {
@@ -109,11 +129,8 @@ impl AstData for Class {
                 }
                 new_span = Some(ident.span());
             }
-            if dec.flags.iter().any(|(k, _)| *k == Flag::Inst) {
-                fields.insert(ident.name, Arc::clone(&*ty));
-            } else {
-                namespace_ty.insert(ident.name.to_owned(), Arc::clone(&*ty).into());
-                namespace_ast.insert(ident.name, *dec.content.to_owned());
+            if !dec.flags.iter().any(|(k, _)| *k == Flag::Inst) {
+                namespace_names.push(ident.name.to_owned());
             }
         }
 
@@ -126,46 +143,78 @@ impl AstData for Class {
         } else {
             &mut empty2
         };
-        let _arg_tys = args
-            .iter_mut()
-            .map(|arg| {
-                let arg_ty = arg.type_check(ty_symt)?;
-                fields.insert(arg.name.name.to_owned(), Arc::clone(&arg_ty));
-                Ok(arg_ty)
-            })
-            .collect::<ZResult<Vec<_>>>()?;
+        // the fields come first, so that the type is complete when the namespace is checked
+        for arg in args.iter_mut() {
+            let arg_ty = arg.type_check(ty_symt)?;
+            fields.insert(arg.name.name.to_owned(), arg_ty);
+            if arg.is_mut {
+                mutable_fields.insert(arg.name.name.to_owned());
+            }
+        }
+        let is_inst = |statement: &Ast| {
+            let Ast::Declare(dec) = statement else {
+                return false;
+            };
+            dec.flags.iter().any(|(k, _)| *k == Flag::Inst)
+        };
+        for statement in statements.iter_mut().filter(|s| is_inst(s)) {
+            // a struct is only constructed from its parameters, which is where its fields go
+            if *is_struct {
+                let Ast::Declare(dec) = &*statement else {
+                    unreachable!()
+                };
+                let name = &dec.variable.as_ident().z()?.name;
+                return Err(ZError::t030(name).with_span(&*statement));
+            }
+            let ty = statement.type_check(ty_symt)?;
+            let Ast::Declare(dec) = statement else {
+                unreachable!()
+            };
+            let name = dec.variable.as_ident().z()?.name.to_owned();
+            if dec.is_mut {
+                mutable_fields.insert(name.to_owned());
+            }
+            fields.insert(name, Arc::clone(&*ty));
+        }
 
-        let ty = Arc::new(Type::Type {
-            name: None,
-            namespace: namespace_ty,
-            fields: fields.to_owned(),
-            type_args: vec![],
-        });
-
-        if new_span.is_none() { // todo when `$` is added
-             /*let Some(Type::Type { namespace, ..}) = Arc::get_mut(&mut ty) else {
-                 unreachable!()
-             };
-             namespace.insert("_new".into(), generic_proc(arg_tys, Arc::clone(&ty)).into());
-             namespace_ast.insert("_new".into(), Value::Proc(Proc::Defined {
-                 is_fn: false,
-                 content: Block { brace_spans: None, content: vec![
-
-                 ] },
-                 args: vec![],
-             }).as_ast().into());*/
+        let name = name.to_owned();
+        let (ty, cells) = Self::class_type(
+            name.to_owned(),
+            namespace_names.iter(),
+            fields.to_owned(),
+            mutable_fields.to_owned(),
+            Self::params(*is_struct, Some(args)),
+        );
+        ty_symt.declare_val(
+            "$ty",
+            TypeCheckType::Const(Arc::clone(&ty)),
+            false,
+            None::<Span>,
+        )?;
+        for statement in statements.iter_mut().filter(|s| !is_inst(s)) {
+            let statement_ty = statement.type_check(ty_symt)?;
+            let Ast::Declare(dec) = statement else {
+                unreachable!()
+            };
+            let name = dec.variable.as_ident().z()?.name.to_owned();
+            let _ = cells[&name].set(Arc::clone(&*statement_ty));
+            namespace_ast.push((name, *dec.content.to_owned()));
         }
 
+        let args = is_struct.then(|| args.to_owned());
         *self = Self::TypeChecked {
             is_struct: *is_struct,
             reconstruction: self.reconstruct(),
             span: self.span(),
             namespace: namespace_ast,
             fields,
+            mutable_fields,
+            args,
+            name,
         };
 
         ty_symt.pop_frame()?;
-        Ok(ty.into())
+        Ok(TypeCheckType::Const(ty))
     }
 
     fn desugared(&self) -> ZResult<Ast> {
@@ -182,29 +231,120 @@ impl AstData for Class {
                     }
                 }
             }
-            Self::TypeChecked { namespace, .. } => {
-                for ast in namespace.values_mut() {
+            Self::TypeChecked {
+                namespace, args, ..
+            } => {
+                for (_, ast) in namespace {
                     ast.desugar()?;
                 }
+                for arg in args.iter_mut().flatten() {
+                    arg.desugar()?;
+                }
             }
         }
         Ok(new_self.as_variant())
     }
 
     fn interpret_expr(&self, val_symt: &mut InterpretSymTable) -> ZResult<Value> {
-        let Self::TypeChecked { namespace, fields, .. } = self else {
+        let Self::TypeChecked { namespace, fields, mutable_fields, args, name, .. } = self else {
             unreachable!()
         };
+        let params = args
+            .as_ref()
+            .map(|args| {
+                args.iter()
+                    .map(|arg| {
+                        let default = arg
+                            .default
+                            .as_ref()
+                            .map(|default| default.interpret_expr(val_symt))
+                            .transpose()?;
+                        Ok((arg.name.name.to_owned(), default))
+                    })
+                    .collect::<ZResult<Vec<_>>>()
+            })
+            .transpose()?;
+        let has_cmp = namespace.iter().any(|(k, _)| k == "_cmp");
+        let value_type = |namespace| {
+            ValueType::Type(Box::new(ValueTypeDef {
+                name: name.to_owned(),
+                namespace,
+                fields: fields.to_owned(),
+                mutable_fields: mutable_fields.to_owned(),
+                params: params.to_owned(),
+                type_args: vec![],
+            }))
+        };
+        // while the namespace is evaluated, `$ty` only has the builtins
+        val_symt.add_frame(InterpretFrameType::Normal);
+        val_symt.declare_val(
+            "$ty",
+            Value::Type(Arc::new(value_type(
+                Self::instance_builtins(has_cmp).collect(),
+            ))),
+        );
         let namespace = namespace
             .iter()
             .map(|(k, v)| Ok((k.to_owned(), v.interpret_expr(val_symt)?)))
-            .collect::<ZResult<HashMap<_, _>>>()?;
-        Ok(Value::Type(Arc::new(ValueType::Type {
-            name: None,
+            .collect::<ZResult<HashMap<_, _>>>();
+        val_symt.pop_frame()?;
+        let mut namespace = namespace?;
+        for (k, v) in Self::instance_builtins(has_cmp) {
+            namespace.entry(k).or_insert(v);
+        }
+        Ok(Value::Type(Arc::new(value_type(namespace))))
+    }
+}
+
+impl Class {
+    pub fn set_name(&mut self, new_name: &Ident) {
+        let (Self::Raw { name, .. } | Self::TypeChecked { name, .. }) = self;
+        *name = Some(new_name.to_owned());
+    }
+    /// Creates the type of a class before its namespace is type-checked,
+    /// along with the cells that each item's type is put in once it's known
+    fn class_type<'a>(
+        name: Option<Ident>,
+        names: impl Iterator<Item = &'a SmolStr>,
+        fields: HashMap<SmolStr, Arc<Type>>,
+        mutable_fields: HashSet<SmolStr>,
+        params: Option<Vec<(SmolStr, bool)>>,
+    ) -> (Arc<Type>, HashMap<SmolStr, Arc<OnceCell<Arc<Type>>>>) {
+        let cells = names
+            .map(|k| (k.to_owned(), Arc::new(OnceCell::new())))
+            .collect::<HashMap<_, _>>();
+        let mut namespace = cells
+            .iter()
+            .map(|(k, cell)| (k.to_owned(), LazyType::new_deferred(Arc::clone(cell))))
+            .collect::<HashMap<_, _>>();
+        for (k, v) in Self::instance_builtins(cells.contains_key("_cmp")) {
+            namespace
+                .entry(k)
+                .or_insert_with(|| LazyType::new_lazy(v, Value::ty));
+        }
+        let ty = Arc::new(Type::Type(Box::new(TypeDef {
+            name,
             namespace,
-            fields: fields.to_owned(),
+            fields,
+            mutable_fields,
+            params,
             type_args: vec![],
-        })))
+        })));
+        (ty, cells)
+    }
+    /// The builtins in a class's namespace, leaving `_eq` and `_ne` to `_cmp` if it's defined
+    fn instance_builtins(has_cmp: bool) -> impl Iterator<Item = (SmolStr, Value)> {
+        instance_namespace()
+            .into_iter()
+            .filter(move |(k, _)| !has_cmp || !["_eq", "_ne"].contains(&&**k))
+    }
+    fn params(is_struct: bool, args: Option<&[Argument]>) -> Option<Vec<(SmolStr, bool)>> {
+        is_struct.then(|| {
+            args.unwrap_or_default()
+                .iter()
+                .map(|arg| (arg.name.name.to_owned(), arg.default.is_some()))
+                .collect()
+        })
     }
 }
 
@@ -1,12 +1,15 @@
+use std::sync::Arc;
+
 use tracing::debug;
 
 use crate::{
-    ast::{Ast, AstData, BinaryOpr, Reconstruct},
+    ast::{Ast, AstData, Reconstruct},
     errors::ToZResult,
+    primitives::{adapt_int_literal, is_numeric, widen, widens_to},
     types::{
         position::{GetSpan, Span},
         r#type::TypeCheckType,
-        token::{Flag, OprType},
+        token::Flag,
     },
     InterpretSymTable, TypeCheckSymTable, Value, ZError, ZResult,
 };
@@ -17,6 +20,7 @@ pub struct Declare {
     pub content: Box<Ast>,
     pub flags: Vec<(Flag, Span)>,
     pub ty: Option<Box<Ast>>,
+    pub is_mut: bool,
     pub eq_span: Option<Span>,
 }
 impl GetSpan for Declare {
@@ -39,6 +43,9 @@ impl AstData for Declare {
         if !self.variable.is_pattern() {
             return Err(ZError::t006().with_span(&self.variable));
         }
+        if let (Ast::Class(class), Ast::Ident(ident)) = (&mut *self.content, &*self.variable) {
+            class.set_name(ident);
+        }
         let mut content_type = self.content.type_check(ty_symt)?;
         let ty = self
             .ty
@@ -56,28 +63,25 @@ impl AstData for Declare {
             return Err(ZError::t008().with_span(&self.variable));
         };
         if let Some(ty) = ty {
-            if *content_type != ty {
-                let mut new_content = BinaryOpr {
-                    ty: OprType::TypeCast,
-                    opr_span: None,
-                    operand1: self.content.to_owned(),
-                    operand2: self.ty.to_owned().unwrap_or_else(|| unreachable!()),
+            // like an argument, the value only converts to the annotated type if it can't lose
+            // anything, or if it's an integer literal that fits
+            if !Arc::ptr_eq(&content_type, &ty) {
+                if let Some(literal) = adapt_int_literal(&self.content, &ty) {
+                    *self.content = literal;
+                } else if widens_to(&content_type, &ty) {
+                    *self.content = widen(*self.content.to_owned(), &ty);
+                } else if is_numeric(&content_type) && is_numeric(&ty) {
+                    return Err(ZError::t020(&content_type, &ty).with_span(&self.content));
+                } else {
+                    return Err(ZError::t011(&ty, &content_type).with_span(&self.content));
                 }
-                .as_variant();
-                new_content.type_check(ty_symt)?;
-                *self = Self {
-                    ty: self.ty.to_owned(),
-                    content: new_content.into(),
-                    variable: self.variable.to_owned(),
-                    flags: self.flags.to_owned(),
-                    eq_span: self.eq_span.to_owned(),
-                };
             }
+            content_type = ty.into();
         }
         if let Ok(ty) = content_type.as_const_mut() {
             ty.update_name(self.variable.as_ident().z()?.to_owned())?;
         }
-        ty_symt.declare_val(&name, content_type.to_owned())?;
+        ty_symt.declare_val(&name, content_type.to_owned(), self.is_mut, &self.variable)?;
         Ok(content_type)
     }
 
@@ -101,23 +105,27 @@ impl AstData for Declare {
         } else {
             unreachable!()
         };
-        let var = self.content.interpret_expr(val_symt)?;
+        let mut var = self.content.interpret_expr(val_symt)?;
+        if let Value::Type(ty) = &mut var {
+            ty.update_name(self.variable.as_ident().z()?.to_owned())?;
+        }
         val_symt.declare_val(name, var.to_owned());
         Ok(var)
     }
 }
 impl Reconstruct for Declare {
     fn reconstruct(&self) -> String {
+        let opr = if self.is_mut { ".=" } else { ":=" };
         if let Some(ty) = &self.ty {
             format!(
-                "{}: {} := {}",
+                "{}: {} {opr} {}",
                 self.variable.reconstruct(),
                 ty.reconstruct(),
                 self.content.reconstruct()
             )
         } else {
             format!(
-                "{} := {}",
+                "{} {opr} {}",
                 self.variable.reconstruct(),
                 self.content.reconstruct()
             )
@@ -0,0 +1,89 @@
+use std::{collections::HashMap, sync::Arc};
+
+use itertools::{Either, Itertools};
+use smol_str::SmolStr;
+use tracing::debug;
+
+use crate::{
+    ast::{Ast, AstData, BinaryOpr, Call, Member, Reconstruct},
+    primitives::STR_T_VAL,
+    types::{
+        position::{GetSpan, Span},
+        token::{AccessType, OprType},
+        value::Value,
+    },
+    ZResult,
+};
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct FString {
+    pub span: Option<Span>,
+    pub parts: Vec<Either<String, (Box<Ast>, Option<SmolStr>)>>,
+}
+impl GetSpan for FString {
+    fn span(&self) -> Option<Span> {
+        self.span.span()
+    }
+}
+
+impl AstData for FString {
+    fn as_variant(&self) -> Ast {
+        Ast::FString(self.to_owned())
+    }
+
+    fn desugared(&self) -> ZResult<Ast> {
+        debug!(span = ?self.span(), "Desugaring formatted string");
+        let mut out = Value::Str(String::new()).as_ast();
+        for part in &self.parts {
+            let part = match part {
+                Either::Left(text) => Value::Str(text.to_owned()).as_ast(),
+                Either::Right((expr, None)) => BinaryOpr {
+                    ty: OprType::TypeCast,
+                    opr_span: None,
+                    operand1: expr.to_owned(),
+                    operand2: Box::new(Value::Type(Arc::clone(&STR_T_VAL)).as_ast()),
+                }
+                .as_variant(),
+                Either::Right((expr, Some(spec))) => Call {
+                    called: Member {
+                        ty: AccessType::Method,
+                        name: "_fmt".into(),
+                        name_span: None,
+                        dot_span: None,
+                        parent: expr.to_owned(),
+                    }
+                    .as_variant()
+                    .into(),
+                    paren_spans: None,
+                    args: vec![Value::Str(spec.to_string()).as_ast()],
+                    kwargs: HashMap::default(),
+                }
+                .as_variant(),
+            };
+            out = BinaryOpr {
+                ty: OprType::Concat,
+                opr_span: None,
+                operand1: out.into(),
+                operand2: part.into(),
+            }
+            .as_variant();
+        }
+        out.desugared()
+    }
+}
+
+impl Reconstruct for FString {
+    fn reconstruct(&self) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                Either::Left(text) => format!("{text:?}"),
+                Either::Right((expr, spec)) => format!(
+                    "({}){}",
+                    expr.reconstruct(),
+                    spec.as_ref().map(|s| format!(":{s}")).unwrap_or_default()
+                ),
+            })
+            .join(" ~ ")
+    }
+}
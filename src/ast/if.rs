@@ -5,6 +5,7 @@ use tracing::debug;
 use crate::{
     ast::{Ast, AstData, Condition, Reconstruct},
     errors::ZError,
+    primitives::BOOL_T,
     types::{
         position::{GetSpan, Span},
         r#type::TypeCheckType,
@@ -34,6 +35,12 @@ impl AstData for If {
         debug!(span = ?self.span(), "Type-checking if expression");
         let mut first_ty: Option<TypeCheckType> = None;
         for ty in &mut self.conditions {
+            if let Some(cond) = &mut ty.condition {
+                let cond_ty = cond.type_check(ty_symt)?;
+                if !Arc::ptr_eq(&cond_ty, &BOOL_T) {
+                    return Err(ZError::t011(&BOOL_T, &cond_ty).with_span(&*cond));
+                }
+            }
             let ty = ty.if_true.block_type(ty_symt, true)?;
             if let Some(first_ty) = &first_ty {
                 if !Arc::ptr_eq(first_ty, &ty) {
@@ -5,10 +5,11 @@ use tracing::debug;
 
 use crate::{
     ast::{Ast, AstData, Reconstruct},
-    errors::{ToZResult, ZError},
+    errors::ZError,
+    file_importer::is_package_private,
     types::{
         position::{GetSpan, Span},
-        r#type::TypeCheckType,
+        r#type::{Type, TypeCheckType},
         token::AccessType,
     },
     InterpretSymTable, TypeCheckSymTable, Value, ZResult,
@@ -30,6 +31,19 @@ impl GetSpan for Member {
     }
 }
 
+impl Member {
+    fn missing_attr(&self, parent_type: &Type) -> ZError {
+        // operators desugar to calls of methods without a name span, so say which one is missing
+        if self.name_span.is_none() && self.name.starts_with('_') {
+            ZError::t028(parent_type, &self.name).with_span(self)
+        } else if is_package_private(parent_type, &self.name) {
+            ZError::t029(&self.name).with_span(self)
+        } else {
+            ZError::t005(parent_type, &self.name).with_span(self)
+        }
+    }
+}
+
 impl AstData for Member {
     fn as_variant(&self) -> Ast {
         Ast::Member(self.to_owned())
@@ -43,15 +57,25 @@ impl AstData for Member {
         debug!(span = ?self.span(), "Type-checking member access");
         let parent_type = self.parent.type_check(ty_symt)?;
         let res = match self.ty {
-            AccessType::Method => unreachable!(),
-            AccessType::Namespace => parent_type
-                .as_const()?
+            AccessType::Method => parent_type
                 .namespace()
                 .get(&self.name)
-                .ok_or_else(|| {
-                    ZError::t005(parent_type.as_const().unwrap(), &self.name).with_span(&*self)
-                })
+                .ok_or_else(|| self.missing_attr(&parent_type))
                 .map(|a| Arc::clone(a))?,
+            AccessType::Namespace => {
+                let parent_type = parent_type
+                    .as_const()
+                    .map_err(|e| e.with_span(&*self.parent))?;
+                let item = parent_type
+                    .namespace()
+                    .get(&self.name)
+                    .cloned()
+                    .ok_or_else(|| self.missing_attr(parent_type))?;
+                if let Some(ty) = item.const_ty {
+                    return Ok(TypeCheckType::Const(ty));
+                }
+                Arc::clone(&item)
+            }
             AccessType::Field => parent_type
                 .fields()
                 .get(&self.name)
@@ -71,15 +95,29 @@ impl AstData for Member {
     fn interpret_expr(&self, val_symt: &mut InterpretSymTable) -> ZResult<Value> {
         let parent = self.parent.interpret_expr(val_symt)?;
         match self.ty {
-            AccessType::Method => unreachable!(),
-            AccessType::Field => Ok(todo!()),
-            AccessType::Namespace => Ok(parent
-                .as_type()
-                .z()?
+            AccessType::Method => parent
+                .value_ty()
                 .namespace()
                 .get(&self.name)
-                .z()?
-                .to_owned()),
+                .cloned()
+                .ok_or_else(|| ZError::t005(&parent.ty(), &self.name).with_span(self)),
+            AccessType::Field => {
+                if let Value::ClassInstance { attrs, .. } = &parent {
+                    if let Some(value) = attrs.get(&*self.name) {
+                        return Ok(value.to_owned());
+                    }
+                }
+                Err(ZError::i006(&parent, &self.name).with_span(self))
+            }
+            AccessType::Namespace => {
+                let Value::Type(ty) = &parent else {
+                    return Err(ZError::t016().with_span(&*self.parent));
+                };
+                ty.namespace()
+                    .get(&self.name)
+                    .cloned()
+                    .ok_or_else(|| ZError::t005(&ty.to_type(), &self.name).with_span(self))
+            }
         }
     }
 }
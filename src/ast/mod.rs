@@ -8,6 +8,7 @@ mod condition;
 mod declare;
 mod defer;
 mod delete;
+mod fstring;
 mod ident;
 mod r#if;
 mod literal;
@@ -17,6 +18,7 @@ mod procedure;
 mod r#return;
 mod set;
 mod unary_opr;
+mod r#use;
 
 use std::{fmt::Debug, sync::Arc};
 
@@ -31,6 +33,7 @@ pub use declare::Declare;
 pub use defer::Defer;
 pub use delete::Delete;
 use enum_as_inner::EnumAsInner;
+pub use fstring::FString;
 pub use ident::Ident;
 use itertools::Itertools;
 pub use literal::Literal;
@@ -38,6 +41,7 @@ pub use preprocess::Preprocess;
 pub use procedure::Procedure;
 pub use r#if::If;
 pub use r#return::Return;
+pub use r#use::Use;
 pub use set::Set;
 pub use unary_opr::UnaryOpr;
 
@@ -78,6 +82,7 @@ macro_rules! for_all_variants {
             Ast::Declare(v) => v.$f($($args,)*),
             Ast::Set(v) => v.$f($($args,)*),
             Ast::Literal(v) => v.$f($($args,)*),
+            Ast::FString(v) => v.$f($($args,)*),
             Ast::Ident(v) => v.$f($($args,)*),
             Ast::If(v) => v.$f($($args,)*),
             Ast::Block(v) => v.$f($($args,)*),
@@ -88,6 +93,7 @@ macro_rules! for_all_variants {
             Ast::Defer(v) => v.$f($($args,)*),
             Ast::Class(v) => v.$f($($args,)*),
             Ast::Member(v) => v.$f($($args,)*),
+            Ast::Use(v) => v.$f($($args,)*),
         }
     }
 }
@@ -100,6 +106,7 @@ pub enum Ast {
     Declare(Declare),
     Set(Set),
     Literal(Literal),
+    FString(FString),
     Ident(Ident),
     If(If),
     Block(Block),
@@ -110,6 +117,7 @@ pub enum Ast {
     Defer(Defer),
     Class(Class),
     Member(Member),
+    Use(Use),
 }
 impl GetSpan for Ast {
     fn span(&self) -> Option<Span> {
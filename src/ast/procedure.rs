@@ -6,7 +6,8 @@ use tracing::debug;
 use crate::{
     ast::{argument::Argument, Ast, AstData, Block, Reconstruct},
     errors::{ToZResult, ZError},
-    primitives::generic_proc,
+    file_importer::module_env,
+    primitives::{generic_proc, ANY_T_VAL},
     types::{
         position::{GetSpan, Span},
         r#type::TypeCheckType,
@@ -55,7 +56,12 @@ impl AstData for Procedure {
             .iter_mut()
             .map(|arg| {
                 let ty = arg.type_check(ty_symt)?;
-                ty_symt.declare_val(&arg.name.name, Arc::clone(&ty).into())?;
+                ty_symt.declare_val(
+                    &arg.name.name,
+                    Arc::clone(&ty).into(),
+                    arg.is_mut,
+                    &arg.name,
+                )?;
                 Ok(ty)
             })
             .collect::<ZResult<Vec<_>>>()?;
@@ -91,11 +97,22 @@ impl AstData for Procedure {
         Ok(new_self.as_variant())
     }
 
-    fn interpret_expr(&self, _val_symt: &mut InterpretSymTable) -> ZResult<Value> {
+    fn interpret_expr(&self, val_symt: &mut InterpretSymTable) -> ZResult<Value> {
+        // a type that can't be resolved yet, like the struct a method is in, accepts anything
+        let arg_tys = self
+            .args
+            .iter()
+            .map(|a| match a.ty.interpret_expr(val_symt) {
+                Ok(Value::Type(ty)) => ty,
+                _ => Arc::clone(&ANY_T_VAL),
+            })
+            .collect();
         Ok(Value::Proc(Proc::Defined {
             is_fn: self.is_fn,
             content: self.content.to_owned(),
             args: self.args.iter().map(|a| a.name.name.to_owned()).collect(),
+            arg_tys,
+            module: module_env(),
         }))
     }
 }
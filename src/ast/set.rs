@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use tracing::debug;
 
 use crate::{
@@ -5,6 +7,7 @@ use crate::{
     types::{
         position::{GetSpan, Span},
         r#type::TypeCheckType,
+        token::AccessType,
     },
     InterpretSymTable, TypeCheckSymTable, Value, ZError, ZResult,
 };
@@ -34,15 +37,26 @@ impl AstData for Set {
             return Err(ZError::t006().with_span(&*self.variable));
         }
         let content_type = self.content.type_check(ty_symt)?;
-        let name = if let Ast::Ident(ident) = &*self.variable {
-            &ident.name
-        } else {
-            return Err(ZError::t008().with_span(&self.variable));
-        };
-        let var_type = ty_symt.get_val(name, self.variable.span())?;
-        ty_symt.set_val(name, content_type, self.variable.span())?;
-
-        Ok(var_type)
+        match &mut *self.variable {
+            Ast::Ident(ident) => {
+                let var_type = ty_symt.get_val(&ident.name, ident.span())?;
+                ty_symt.set_val(&ident.name, content_type, ident.span())?;
+                Ok(var_type)
+            }
+            Ast::Member(member) if member.ty == AccessType::Field => {
+                Self::check_mutable(&member.parent, ty_symt)?;
+                let parent_type = member.parent.type_check(ty_symt)?;
+                if !parent_type.mutable_fields().contains(&member.name) {
+                    return Err(ZError::t019(&parent_type, &member.name).with_span(&*member));
+                }
+                let var_type = member.type_check(ty_symt)?;
+                if !Arc::ptr_eq(&var_type, &content_type) {
+                    return Err(ZError::t011(&var_type, &content_type).with_span(&self.content));
+                }
+                Ok(var_type)
+            }
+            _ => Err(ZError::t008().with_span(&self.variable)),
+        }
     }
 
     fn desugared(&self) -> ZResult<Ast> {
@@ -55,16 +69,53 @@ impl AstData for Set {
 
     fn interpret_expr(&self, val_symt: &mut InterpretSymTable) -> ZResult<Value> {
         let var = self.content.interpret_expr(val_symt)?;
-        let name = if let Ast::Ident(ident) = &*self.variable {
-            &ident.name
-        } else {
-            return Err(ZError::t008().with_span(&self.variable));
-        };
-        val_symt.set_val(name, var.to_owned(), self)?;
+        Self::set_pattern(&self.variable, var.to_owned(), val_symt)?;
         Ok(var)
     }
 }
 
+impl Set {
+    /// Stores `value` into a variable or a field path such as `a.b.c`,
+    /// writing each modified parent back into its own parent
+    fn set_pattern(ast: &Ast, value: Value, val_symt: &mut InterpretSymTable) -> ZResult<()> {
+        match ast {
+            Ast::Ident(ident) => val_symt.set_val(&ident.name, value, ident),
+            Ast::Member(member) if member.ty == AccessType::Field => {
+                let mut parent = member.parent.interpret_expr(val_symt)?;
+                let Value::ClassInstance { attrs, .. } = &mut parent else {
+                    return Err(ZError::i006(&parent, &member.name).with_span(member));
+                };
+                let Some(attr) = attrs.get_mut(&*member.name) else {
+                    return Err(ZError::i006(&parent, &member.name).with_span(member));
+                };
+                *attr = value;
+                Self::set_pattern(&member.parent, parent, val_symt)
+            }
+            _ => Err(ZError::t008().with_span(ast)),
+        }
+    }
+    /// Checks that a field path such as `a.b.c` starts with a mutable variable
+    fn check_mutable(ast: &Ast, ty_symt: &TypeCheckSymTable) -> ZResult<()> {
+        match ast {
+            Ast::Ident(ident) => {
+                let entry = ty_symt.get_entry(&ident.name, ident)?;
+                if entry.mutable {
+                    Ok(())
+                } else {
+                    Err(ZError::t018(&ident.name)
+                        .with_span(ident)
+                        .add_span(&entry.decl_span))
+                }
+            }
+            Ast::Member(member) if member.ty == AccessType::Field => {
+                Self::check_mutable(&member.parent, ty_symt)
+            }
+            // a field of a temporary like `Cell().a` can't be stored anywhere
+            _ => Err(ZError::t008().with_span(ast)),
+        }
+    }
+}
+
 impl Reconstruct for Set {
     fn reconstruct(&self) -> String {
         format!(
@@ -4,6 +4,7 @@ use tracing::debug;
 
 use crate::{
     ast::{Ast, AstData, Call, Member, Reconstruct},
+    errors::ZError,
     types::{
         position::{GetSpan, Span},
         token::{AccessType, OprType},
@@ -30,14 +31,15 @@ impl AstData for UnaryOpr {
 
     fn desugared(&self) -> ZResult<Ast> {
         debug!(span = ?self.span(), "Desugaring unary operator");
-        Ok(Call {
+        Call {
             called: Member {
                 ty: AccessType::Method,
                 name: match self.ty {
                     OprType::Not => "_not",
-                    OprType::UnPlus => "_un_plus",
-                    OprType::UnMinus => "_un_minus",
-                    _ => panic!(),
+                    OprType::UnPlus => "_un_add",
+                    OprType::UnMinus => "_un_sub",
+                    OprType::BitCompl => "_compl",
+                    opr => return Err(ZError::t026(opr).with_span(&self.opr_span)),
                 }
                 .into(),
                 name_span: None,
@@ -50,7 +52,7 @@ impl AstData for UnaryOpr {
             args: vec![],
             kwargs: HashMap::default(),
         }
-        .as_variant())
+        .desugared()
     }
 }
 
@@ -0,0 +1,156 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use smol_str::SmolStr;
+use tracing::debug;
+
+use crate::{
+    ast::{Ast, AstData, Member, Reconstruct},
+    errors::{ToZResult, ZError},
+    file_importer::{find_module, import_module, module_name, Module},
+    package::Package,
+    primitives::UNIT_T,
+    stdlib::builtin_module,
+    types::{
+        position::{GetSpan, Span},
+        r#type::TypeCheckType,
+        token::AccessType,
+        value::Value,
+    },
+    InterpretSymTable, TypeCheckSymTable, ZResult,
+};
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Use {
+    pub kwd_span: Option<Span>,
+    /// Either a path in a string, or a module name like `math` or `math::trig`
+    pub path: Box<Ast>,
+    /// The module, once it's found and compiled
+    pub module: Option<Arc<Module>>,
+}
+impl GetSpan for Use {
+    fn span(&self) -> Option<Span> {
+        self.kwd_span.merge_span(&self.path)
+    }
+}
+
+impl AstData for Use {
+    fn as_variant(&self) -> Ast {
+        Ast::Use(self.to_owned())
+    }
+
+    fn type_check(&mut self, ty_symt: &mut TypeCheckSymTable) -> ZResult<TypeCheckType> {
+        debug!(span = ?self.span(), "Type-checking use statement");
+        if let Some(module) = self.segments().as_deref().and_then(builtin_module) {
+            ty_symt.declare_val(
+                &self.name().z()?,
+                TypeCheckType::Const(Arc::clone(&module.pub_ty)),
+                false,
+                &self.path,
+            )?;
+            self.module = Some(module);
+            return Ok(Arc::clone(&UNIT_T).into());
+        }
+        let path = self.module_path().z()?;
+        // relative paths start from the directory of the file with the `use` in it
+        let from = self
+            .kwd_span
+            .as_ref()
+            .and_then(|span| span.start_pos.filename.as_ref())
+            .and_then(|filename| Path::new(filename.as_str()).parent().map(Path::to_path_buf))
+            .unwrap_or_default();
+        let package = Package::find(&from)?;
+        let dependency_file = if let (Some(package), Some(segments)) = (&package, self.segments()) {
+            package.find_dependency_module(&segments)?
+        } else {
+            None
+        };
+        let Some(file) = dependency_file.or_else(|| find_module(&path, &from, package.as_ref()))
+        else {
+            return Err(ZError::t024(path.display()).with_span(&self.path));
+        };
+        let module = import_module(&file).map_err(|e| {
+            if e.pos.is_empty() {
+                e.with_span(&*self)
+            } else {
+                e
+            }
+        })?;
+        // `pubp` items are only visible within the package
+        let ty = if module.package == package.map(|package| package.root) {
+            &module.ty
+        } else {
+            &module.pub_ty
+        };
+        ty_symt.declare_val(
+            &self.name().z()?,
+            TypeCheckType::Const(Arc::clone(ty)),
+            false,
+            &self.path,
+        )?;
+        self.module = Some(module);
+        Ok(Arc::clone(&UNIT_T).into())
+    }
+
+    fn interpret_expr(&self, val_symt: &mut InterpretSymTable) -> ZResult<Value> {
+        let module = self.module.as_ref().z()?;
+        val_symt.declare_val(&self.name().z()?, module.value()?);
+        Ok(Value::Unit)
+    }
+}
+
+impl Use {
+    /// The segments of a module name like `math::trig`, or `None` for a path in a string
+    #[must_use]
+    pub fn segments(&self) -> Option<Vec<&str>> {
+        fn segments(ast: &Ast) -> Option<Vec<&str>> {
+            match ast {
+                Ast::Ident(ident) => Some(vec![&ident.name]),
+                Ast::Member(Member {
+                    ty: AccessType::Namespace,
+                    name,
+                    parent,
+                    ..
+                }) => {
+                    let mut segments = segments(parent)?;
+                    segments.push(name);
+                    Some(segments)
+                }
+                _ => None,
+            }
+        }
+        segments(&self.path)
+    }
+    /// The name the module is declared under, which is the last segment of its name
+    #[must_use]
+    pub fn name(&self) -> Option<SmolStr> {
+        if let Some(segments) = self.segments() {
+            segments.last().map(|name| (*name).into())
+        } else {
+            module_name(&self.module_path()?)
+        }
+    }
+    /// The path of the module's file relative to where it's looked for
+    #[must_use]
+    pub fn module_path(&self) -> Option<PathBuf> {
+        if let Ast::Literal(literal) = &*self.path {
+            if let Value::Str(path) = &literal.content {
+                return Some(path.into());
+            }
+        }
+        Some(
+            self.segments()?
+                .into_iter()
+                .collect::<PathBuf>()
+                .with_extension("xt"),
+        )
+    }
+}
+
+impl Reconstruct for Use {
+    fn reconstruct(&self) -> String {
+        format!("use {}", self.path.reconstruct())
+    }
+}
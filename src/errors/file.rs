@@ -0,0 +1,38 @@
+use std::{fmt::Display, io, path::Path};
+
+use crate::errors::ZError;
+
+impl ZError {
+    #[must_use]
+    pub fn f001(path: impl Display) -> Self {
+        Self::new("F001", format!("File `{path}` does not exist"))
+    }
+    #[must_use]
+    pub fn f002(path: impl Display) -> Self {
+        Self::new("F002", format!("Permission denied to read `{path}`"))
+    }
+    #[must_use]
+    pub fn f003(path: impl Display) -> Self {
+        Self::new("F003", format!("File `{path}` is not valid UTF-8"))
+    }
+    #[must_use]
+    pub fn f004(path: impl Display) -> Self {
+        Self::new("F004", format!("`{path}` is a directory, not a file"))
+    }
+    #[must_use]
+    pub fn f005(path: impl Display, reason: impl Display) -> Self {
+        Self::new("F005", format!("Could not read `{path}`: {reason}"))
+    }
+    /// The file error for `err`, which happened while reading `path`
+    #[must_use]
+    pub fn from_io(path: &Path, err: &io::Error) -> Self {
+        let path = path.display();
+        match err.kind() {
+            io::ErrorKind::NotFound => Self::f001(path),
+            io::ErrorKind::PermissionDenied => Self::f002(path),
+            io::ErrorKind::InvalidData => Self::f003(path),
+            io::ErrorKind::IsADirectory => Self::f004(path),
+            _ => Self::f005(path, err),
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 use itertools::Itertools;
 
 use crate::{errors::ZError, types::value::Value};
@@ -13,4 +15,99 @@ impl ZError {
             ),
         )
     }
+    #[must_use]
+    pub fn i002(opr: &str, args: &[Value]) -> Self {
+        Self::new(
+            "I002",
+            format!(
+                "Overflow in `{opr}` (Arguments: {})",
+                args.iter().map(ToString::to_string).join(", ")
+            ),
+        )
+    }
+    #[must_use]
+    pub fn i003(args: &[Value]) -> Self {
+        Self::new(
+            "I003",
+            format!(
+                "Division by zero (Arguments: {})",
+                args.iter().map(ToString::to_string).join(", ")
+            ),
+        )
+    }
+    #[must_use]
+    pub fn i004() -> Self {
+        Self::new("I004", "Cannot unwrap an empty `opt`".to_owned())
+    }
+    #[must_use]
+    pub fn i005(spec: &str, value: &Value) -> Self {
+        Self::new(
+            "I005",
+            format!("Format spec `{spec}` cannot be applied to `{value}`"),
+        )
+    }
+    #[must_use]
+    pub fn i006(value: &Value, name: &str) -> Self {
+        Self::new(
+            "I006",
+            format!(
+                "Value `{value}` of type `{}` has no field `{name}`",
+                value.ty()
+            ),
+        )
+    }
+    #[must_use]
+    pub fn i007(opr: &str, args: &[Value]) -> Self {
+        Self::new(
+            "I007",
+            format!(
+                "`{opr}` is undefined for its arguments (Arguments: {})",
+                args.iter().map(ToString::to_string).join(", ")
+            ),
+        )
+    }
+    #[must_use]
+    pub fn i008(reason: impl Display) -> Self {
+        Self::new("I008", format!("I/O error: {reason}"))
+    }
+    /// Not a failure, but how `sys::exit` unwinds to the top level
+    #[must_use]
+    pub fn i009(code: i32) -> Self {
+        Self::new("I009", format!("Exited with code {code}"))
+    }
+    #[must_use]
+    pub fn i010(input: &str, ty: &str, reason: impl Display) -> Self {
+        Self::new(
+            "I010",
+            format!("Cannot parse `{input}` as `{ty}`: {reason}"),
+        )
+    }
+    #[must_use]
+    pub fn i011(pattern: &str, reason: impl Display) -> Self {
+        Self::new("I011", format!("Invalid regex `{pattern}`: {reason}"))
+    }
+    #[must_use]
+    pub fn i012(line: usize, column: usize, reason: impl Display) -> Self {
+        Self::new(
+            "I012",
+            format!("Invalid JSON at line {line}, column {column}: {reason}"),
+        )
+    }
+    #[must_use]
+    pub fn i013(value: &Value) -> Self {
+        Self::new(
+            "I013",
+            format!(
+                "Value `{value}` of type `{}` cannot be converted to JSON",
+                value.ty()
+            ),
+        )
+    }
+    #[must_use]
+    pub fn i014(method: &str, amount: i64) -> Self {
+        Self::new(
+            "I014",
+            format!("Invalid shift amount {amount} in `{method}`"),
+        )
+    }
 }
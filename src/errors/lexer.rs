@@ -12,4 +12,44 @@ impl ZError {
     pub fn l002() -> Self {
         Self::new("L002", "Unexpected end of comment".to_owned())
     }
+    #[must_use]
+    pub fn l003(raw: &str) -> Self {
+        Self::new("L003", format!("Malformed number literal `{raw}`"))
+    }
+    #[must_use]
+    pub fn l004(raw: &str, ty: &str) -> Self {
+        Self::new(
+            "L004",
+            format!("Number literal `{raw}` is out of range for `{ty}`"),
+        )
+    }
+    #[must_use]
+    pub fn l005(escape: &str) -> Self {
+        Self::new("L005", format!("Invalid escape `{escape}`"))
+    }
+    #[must_use]
+    pub fn l006() -> Self {
+        Self::new("L006", "Unterminated text literal".to_owned())
+    }
+    #[must_use]
+    pub fn l007(raw: &str) -> Self {
+        Self::new(
+            "L007",
+            format!("Char literal `{raw}` must be one character or its hex code"),
+        )
+    }
+    #[must_use]
+    pub fn l008() -> Self {
+        Self::new(
+            "L008",
+            "Unmatched `}` in formatted string literal".to_owned(),
+        )
+    }
+    #[must_use]
+    pub fn l009() -> Self {
+        Self::new(
+            "L009",
+            "Empty expression in formatted string literal".to_owned(),
+        )
+    }
 }
@@ -1,9 +1,11 @@
+mod file;
 mod interpreter;
 mod lexer;
+mod package;
 mod parser;
 mod type_check;
 
-use std::fmt::Debug;
+use std::{cell::Cell, fmt::Debug};
 
 use backtrace::Backtrace;
 use color_eyre::{eyre::eyre, Report, Result};
@@ -19,6 +21,18 @@ use crate::{
 
 pub type ZResult<T> = Result<T, ZError>;
 
+/// How many errors compiling a file reports at most, unless it's set with [`set_error_limit`]
+pub const DEFAULT_ERROR_LIMIT: usize = 20;
+
+thread_local! {
+    static ERROR_LIMIT: Cell<usize> = const { Cell::new(DEFAULT_ERROR_LIMIT) };
+}
+
+/// Sets how many errors compiling a file reports at most, which is at least one
+pub fn set_error_limit(limit: usize) {
+    ERROR_LIMIT.with(|l| l.set(limit.max(1)));
+}
+
 #[derive(Clone, Debug)]
 pub struct ZError {
     pub pos: Vec<Span>,
@@ -111,7 +125,10 @@ impl ZError {
     pub fn print(&self) -> Result<()> {
         debug!("Span trace:\n{}", self.span_trace);
         debug!("Back trace:\n{:#?}", self.back_trace);
-        println!("{}", self.get_surrounding_text()?);
+        // errors about a whole file, like it not existing, have no span to show
+        if !self.pos.is_empty() {
+            println!("{}", self.get_surrounding_text()?);
+        }
         println!(
             " Error {}{} ",
             self.code.black().on_yellow(),
@@ -122,6 +139,19 @@ impl ZError {
         };
         Ok(())
     }
+    /// The code that the process exits with if this error stops it, so that scripts can tell file
+    /// errors apart from errors in the code
+    #[must_use]
+    pub fn exit_code(&self) -> i32 {
+        match self.code {
+            "F001" => 3,
+            "F002" => 4,
+            "F003" => 5,
+            "F004" => 6,
+            "F005" => 7,
+            _ => 1,
+        }
+    }
     #[must_use]
     pub fn with_span(mut self, span: impl GetSpan) -> Self {
         self.pos = if let Some(span) = span.span() {
@@ -131,8 +161,58 @@ impl ZError {
         };
         self
     }
+    #[must_use]
+    pub fn add_span(mut self, span: impl GetSpan) -> Self {
+        if let Some(span) = span.span() {
+            self.pos.push(span);
+        }
+        self
+    }
 }
 
+/// The errors found while compiling a file, which is never empty
+#[derive(Clone, Debug)]
+pub struct ZErrors(pub Vec<ZError>);
+
+impl ZErrors {
+    /// Puts `errors` in the order they are in the source and keeps as many as the error limit
+    /// allows. Errors from using a declaration that failed are left out if there are others, since
+    /// those explain them.
+    #[must_use]
+    pub fn new(mut errors: Vec<ZError>) -> Self {
+        if errors.iter().any(|e| e.code != "T027") {
+            errors.retain(|e| e.code != "T027");
+        }
+        errors.sort_by_key(|e| {
+            e.pos
+                .first()
+                .map(|span| (span.start_pos.line, span.start_pos.column))
+        });
+        errors.truncate(ERROR_LIMIT.with(Cell::get));
+        Self(errors)
+    }
+    #[must_use]
+    pub fn from_error(error: ZError) -> Self {
+        Self(vec![error])
+    }
+    /// The first error, for where only one can be reported, like a module that an import failed to
+    /// compile
+    #[must_use]
+    pub fn into_first(self) -> ZError {
+        self.0.into_iter().next().unwrap_or_else(|| unreachable!())
+    }
+    pub fn print(&self) -> Result<()> {
+        for error in &self.0 {
+            error.print()?;
+        }
+        Ok(())
+    }
+    /// The code that the process exits with if these errors stop it, which is the first's
+    #[must_use]
+    pub fn exit_code(&self) -> i32 {
+        self.0.first().map_or(1, ZError::exit_code)
+    }
+}
 pub trait ToZResult<T> {
     fn z(self) -> ZResult<T>;
 }
@@ -0,0 +1,35 @@
+use std::fmt::Display;
+
+use crate::errors::ZError;
+
+impl ZError {
+    #[must_use]
+    pub fn m001(path: impl Display, reason: impl Display) -> Self {
+        Self::new("M001", format!("Invalid manifest `{path}`: {reason}"))
+    }
+    #[must_use]
+    pub fn m002(dir: impl Display) -> Self {
+        Self::new(
+            "M002",
+            format!("No `zyxt.toml` found in `{dir}` or any of its parents"),
+        )
+    }
+    #[must_use]
+    pub fn m003(name: &str, path: impl Display) -> Self {
+        Self::new(
+            "M003",
+            format!("Dependency `{name}` has no `zyxt.toml` at `{path}`"),
+        )
+    }
+    #[must_use]
+    pub fn m004(path: impl Display) -> Self {
+        Self::new("M004", format!("`{path}` already exists"))
+    }
+    #[must_use]
+    pub fn m005(name: &str) -> Self {
+        Self::new(
+            "M005",
+            format!("Package name `{name}` is not a valid ident"),
+        )
+    }
+}
@@ -121,4 +121,15 @@ impl ZError {
     pub fn p024() -> Self {
         Self::new("P024", "Stray `(`".to_owned())
     }
+    #[must_use]
+    pub fn p025() -> Self {
+        Self::new(
+            "P025",
+            "Expected a module name or path after `use`".to_owned(),
+        )
+    }
+    #[must_use]
+    pub fn p026() -> Self {
+        Self::new("P026", "Expected a name after `.`".to_owned())
+    }
 }
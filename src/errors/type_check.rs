@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use itertools::Itertools;
+
 use crate::{errors::ZError, types::r#type::Type};
 
 impl ZError {
@@ -91,4 +93,100 @@ impl ZError {
     pub fn t017() -> Self {
         Self::new("T017", "Unable to return anything here".to_owned())
     }
+    #[must_use]
+    pub fn t018(sym: &str) -> Self {
+        Self::new(
+            "T018",
+            format!("Symbol `{sym}` is immutable (declare it with `.=` to make it mutable)"),
+        )
+    }
+    #[must_use]
+    pub fn t019(ty: &Type, attr: impl Display) -> Self {
+        Self::new(
+            "T019",
+            format!("Attribute `{attr}` of type `{ty}` is immutable"),
+        )
+    }
+    #[must_use]
+    pub fn t020(from: &Type, to: &Type) -> Self {
+        Self::new(
+            "T020",
+            format!(
+                "Implicit conversion from `{from}` to `{to}` may lose precision (use `@` to convert explicitly)"
+            ),
+        )
+    }
+    #[must_use]
+    pub fn t021(ty1: &Type, ty2: &Type) -> Self {
+        Self::new(
+            "T021",
+            format!(
+                "`{ty1}` and `{ty2}` have no common type that both convert to losslessly (use `@` to convert explicitly)"
+            ),
+        )
+    }
+    #[must_use]
+    pub fn t022(name: &str) -> Self {
+        Self::new("T022", format!("Field `{name}` is given more than once"))
+    }
+    #[must_use]
+    pub fn t023(name: &str) -> Self {
+        Self::new(
+            "T023",
+            format!("Field `{name}` is not given and has no default"),
+        )
+    }
+    #[must_use]
+    pub fn t024(path: impl Display) -> Self {
+        Self::new("T024", format!("Module `{path}` not found"))
+    }
+    #[must_use]
+    pub fn t025(cycle: &[String]) -> Self {
+        Self::new(
+            "T025",
+            format!(
+                "Import cycle: {}",
+                cycle.iter().map(|p| format!("`{p}`")).join(" -> ")
+            ),
+        )
+    }
+    #[must_use]
+    pub fn t026(opr: impl Display) -> Self {
+        Self::new("T026", format!("Operator `{opr}` is not supported"))
+    }
+    #[must_use]
+    pub fn t027(name: &str) -> Self {
+        Self::new(
+            "T027",
+            format!("`{name}` can't be used as its declaration has an error"),
+        )
+    }
+    #[must_use]
+    pub fn t028(ty: &Type, method: impl Display) -> Self {
+        Self::new(
+            "T028",
+            format!("Type `{ty}` doesn't define the operator method `{method}`"),
+        )
+    }
+    #[must_use]
+    pub fn t029(name: &str) -> Self {
+        Self::new(
+            "T029",
+            format!("`{name}` is `pubp`, so it can only be used within its package"),
+        )
+    }
+    #[must_use]
+    pub fn t030(name: &str) -> Self {
+        Self::new(
+            "T030",
+            format!("Struct field `{name}` must be declared in the struct's parameters, not with `inst`"),
+        )
+    }
+    #[must_use]
+    pub fn t031(spec: &str, ty: &Type) -> Self {
+        Self::new(
+            "T031",
+            format!("Format spec `{spec}` cannot be applied to `{ty}`"),
+        )
+    }
 }
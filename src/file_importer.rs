@@ -1,24 +1,42 @@
 use std::{
-    collections::HashMap,
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    iter,
     path::{Path, PathBuf},
     sync::{Arc, Mutex},
 };
 
 use color_eyre::{eyre::eyre, Result};
-use once_cell::sync::Lazy;
+use itertools::Either;
+use once_cell::sync::{Lazy, OnceCell};
 use smol_str::SmolStr;
 
+use crate::{
+    ast::{Ast, Block, Ident},
+    compile,
+    errors::{ToZResult, ZError, ZErrors, ZResult},
+    package::Package,
+    types::{
+        r#type::{LazyType, Type, TypeCheckType, TypeDef, ValueType, ValueTypeDef},
+        sym_table::{InterpretSymTable, TypeCheckSymTable},
+        token::Flag,
+        value::Value,
+    },
+};
+
 static FILE_CACHE: Lazy<Mutex<HashMap<SmolStr, Arc<str>>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
 
-pub fn import_file(file: &Path) -> Result<Arc<str>> {
-    Ok(Arc::clone(
-        FILE_CACHE
-            .lock()
-            .map_err(|a| eyre!("{a:?}"))?
-            .entry(file.to_string_lossy().into())
-            .or_insert_with(|| Arc::from(std::fs::read_to_string(file).unwrap())),
-    ))
+/// Reads `file`, or gets it if it's already been read, giving a file error if it can't be read
+pub fn import_file(file: &Path) -> ZResult<Arc<str>> {
+    let mut cache = FILE_CACHE.lock().z()?;
+    let name = SmolStr::from(file.to_string_lossy());
+    if let Some(input) = cache.get(&name) {
+        return Ok(Arc::clone(input));
+    }
+    let input = Arc::from(std::fs::read_to_string(file).map_err(|e| ZError::from_io(file, &e))?);
+    cache.insert(name, Arc::clone(&input));
+    Ok(input)
 }
 
 pub fn register_input(name: &SmolStr, input: &str) -> Result<Arc<str>> {
@@ -37,7 +55,278 @@ pub fn get_input(name: &SmolStr) -> Result<Option<Arc<str>>> {
     } else {
         PathBuf::try_from(name.to_string())
             .ok()
-            .map(|p| import_file(&p))
+            .map(|p| import_file(&p).map_err(|e| eyre!("{}", e.message)))
             .transpose()
     }
 }
+
+/// The top-level items of an imported module, which its procs can see wherever they're called from
+pub type ModuleEnv = Arc<OnceCell<HashMap<SmolStr, Value>>>;
+
+#[derive(Debug)]
+pub struct Module {
+    pub name: SmolStr,
+    pub path: PathBuf,
+    /// The root of the package it's in, if any
+    pub package: Option<PathBuf>,
+    /// The type of the module within its package, whose namespace has its `pub` and `pubp` items
+    pub ty: Arc<Type>,
+    /// The type of the module outside its package, whose namespace only has its `pub` items
+    pub pub_ty: Arc<Type>,
+    content: Vec<Ast>,
+    env: ModuleEnv,
+    value: OnceCell<Value>,
+}
+impl PartialEq for Module {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+    }
+}
+
+static MODULE_CACHE: Lazy<Mutex<HashMap<PathBuf, Arc<Module>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+thread_local! {
+    static SEARCH_PATHS: RefCell<Vec<PathBuf>> = const { RefCell::new(vec![]) };
+    /// The files being compiled, each imported by the one before it
+    static IMPORT_STACK: RefCell<Vec<PathBuf>> = const { RefCell::new(vec![]) };
+    /// The modules whose top level is being interpreted, innermost last
+    static MODULE_ENVS: RefCell<Vec<ModuleEnv>> = const { RefCell::new(vec![]) };
+}
+
+/// Sets the directories that modules are looked for in, after the importing file's directory
+pub fn set_search_paths(paths: Vec<PathBuf>) {
+    SEARCH_PATHS.with(|p| *p.borrow_mut() = paths);
+}
+
+/// The directories set with [`set_search_paths`], followed by those in `ZYXT_PATH`
+#[must_use]
+pub fn search_paths() -> Vec<PathBuf> {
+    let mut paths = SEARCH_PATHS.with(|p| p.borrow().to_owned());
+    if let Some(env_paths) = std::env::var_os("ZYXT_PATH") {
+        paths.extend(std::env::split_paths(&env_paths));
+    }
+    paths
+}
+
+/// Finds the file of a module, looking next to the importing file, then in its package's
+/// source directory, then in the search paths
+#[must_use]
+pub fn find_module(path: &Path, from: &Path, package: Option<&Package>) -> Option<PathBuf> {
+    iter::once(from.to_path_buf())
+        .chain(package.map(Package::src_dir))
+        .chain(search_paths())
+        .map(|dir| dir.join(path))
+        .find(|file| file.is_file())
+}
+
+/// Runs `f` while `file` is being compiled, erroring if that would import it in a cycle
+pub fn with_import<T>(file: &Path, f: impl FnOnce() -> Result<T, ZErrors>) -> Result<T, ZErrors> {
+    let cycle = IMPORT_STACK.with(|stack| {
+        let stack = stack.borrow();
+        stack.iter().position(|p| p == file).map(|i| {
+            stack[i..]
+                .iter()
+                .chain([&file.to_path_buf()])
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+        })
+    });
+    if let Some(cycle) = cycle {
+        return Err(ZErrors::from_error(ZError::t025(&cycle)));
+    }
+    IMPORT_STACK.with(|stack| stack.borrow_mut().push(file.to_path_buf()));
+    let res = f();
+    IMPORT_STACK.with(|stack| stack.borrow_mut().pop());
+    res
+}
+
+/// Compiles the module at `file` with its own symbol table, or gets it if it's already compiled
+pub fn import_module(file: &Path) -> ZResult<Arc<Module>> {
+    let path = file.canonicalize().z()?;
+    let cached = MODULE_CACHE.lock().z()?.get(&path).cloned();
+    if let Some(module) = cached {
+        return Ok(module);
+    }
+    let module = {
+        let mut ty_symt = TypeCheckSymTable::default();
+        let content =
+            compile(&Either::Left(&path), &mut ty_symt, false).map_err(ZErrors::into_first)?;
+        let package = Package::find(path.parent().z()?)?;
+        // a package's entry file is named after the package
+        let name = match &package {
+            Some(package) if package.entry().canonicalize().ok().as_ref() == Some(&path) => {
+                SmolStr::from(package.name())
+            }
+            _ => module_name(&path).z()?,
+        };
+        let mut namespace = HashMap::new();
+        let mut pub_namespace = HashMap::new();
+        for ast in &content {
+            let Ast::Declare(dec) = ast else {
+                continue;
+            };
+            let Some((flag, _)) = dec
+                .flags
+                .iter()
+                .find(|(k, _)| matches!(k, Flag::Pub | Flag::Pubp))
+            else {
+                continue;
+            };
+            let Ast::Ident(ident) = &*dec.variable else {
+                continue;
+            };
+            let item = match ty_symt.get_val(&ident.name, ident)? {
+                TypeCheckType::Const(ty) => LazyType::new_const(ty),
+                TypeCheckType::Type(ty) => ty.into(),
+            };
+            if *flag == Flag::Pub {
+                pub_namespace.insert(ident.name.to_owned(), item.to_owned());
+            }
+            namespace.insert(ident.name.to_owned(), item);
+        }
+        let module_type = |namespace| {
+            Arc::new(Type::Type(Box::new(TypeDef {
+                name: Some(Ident::new(&name)),
+                namespace,
+                fields: HashMap::new(),
+                mutable_fields: HashSet::new(),
+                params: None,
+                type_args: vec![],
+            })))
+        };
+        Module {
+            ty: module_type(namespace),
+            pub_ty: module_type(pub_namespace),
+            package: package.map(|package| package.root),
+            name,
+            path: path.to_owned(),
+            content,
+            env: Arc::default(),
+            value: OnceCell::new(),
+        }
+    };
+    Ok(Arc::clone(
+        MODULE_CACHE
+            .lock()
+            .z()?
+            .entry(path)
+            .or_insert_with(|| Arc::new(module)),
+    ))
+}
+
+/// Whether `ty` is the type of a module outside its package, and `name` is one of its `pubp` items
+#[must_use]
+pub fn is_package_private(ty: &Type, name: &str) -> bool {
+    MODULE_CACHE.lock().is_ok_and(|cache| {
+        cache.values().any(|module| {
+            std::ptr::eq(&*module.pub_ty, ty) && module.ty.namespace().contains_key(name)
+        })
+    })
+}
+
+/// The name a module is declared under if it's imported with a path, which is its file's name
+/// without any extensions
+#[must_use]
+pub fn module_name(path: &Path) -> Option<SmolStr> {
+    Some(
+        path.file_name()?
+            .to_string_lossy()
+            .split('.')
+            .next()?
+            .into(),
+    )
+}
+
+/// The environment of the module whose top level is being interpreted, if any
+#[must_use]
+pub fn module_env() -> Option<ModuleEnv> {
+    MODULE_ENVS.with(|envs| envs.borrow().last().cloned())
+}
+
+impl Module {
+    /// A module that's built into the interpreter, like `std::math`, with the given items and
+    /// the types among them
+    #[must_use]
+    pub fn builtin(
+        name: &str,
+        path: &str,
+        namespace: HashMap<SmolStr, Value>,
+        types: &HashMap<SmolStr, Arc<Type>>,
+    ) -> Self {
+        let ty_namespace = namespace
+            .iter()
+            .map(|(k, v)| {
+                let item = types.get(k).map_or_else(
+                    || LazyType::new_lazy(v.to_owned(), Value::ty),
+                    |ty| LazyType::new_const(Arc::clone(ty)),
+                );
+                (k.to_owned(), item)
+            })
+            .collect();
+        let ty = Arc::new(Type::Type(Box::new(TypeDef {
+            name: Some(Ident::new(name)),
+            namespace: ty_namespace,
+            fields: HashMap::new(),
+            mutable_fields: HashSet::new(),
+            params: None,
+            type_args: vec![],
+        })));
+        let value = Value::Type(Arc::new(ValueType::Type(Box::new(ValueTypeDef {
+            name: Some(Ident::new(name)),
+            namespace,
+            fields: HashMap::new(),
+            mutable_fields: HashSet::new(),
+            params: None,
+            type_args: vec![],
+        }))));
+        Self {
+            name: name.into(),
+            path: path.into(),
+            package: None,
+            pub_ty: Arc::clone(&ty),
+            ty,
+            content: vec![],
+            env: Arc::default(),
+            value: value.into(),
+        }
+    }
+    /// Interprets the module the first time it's needed, giving its `pub` and `pubp` items
+    pub fn value(&self) -> ZResult<Value> {
+        self.value
+            .get_or_try_init(|| {
+                let mut val_symt = InterpretSymTable::default();
+                MODULE_ENVS.with(|envs| envs.borrow_mut().push(Arc::clone(&self.env)));
+                let res = self.interpret_top_level(&mut val_symt);
+                MODULE_ENVS.with(|envs| envs.borrow_mut().pop());
+                res
+            })
+            .cloned()
+    }
+    fn interpret_top_level(&self, val_symt: &mut InterpretSymTable) -> ZResult<Value> {
+        Block {
+            brace_spans: None,
+            content: self.content.to_owned(),
+        }
+        .interpret_block(val_symt, true, false)?;
+        let table = val_symt.front_mut()?.table.to_owned();
+        let namespace = self
+            .ty
+            .namespace()
+            .keys()
+            .filter_map(|k| Some((k.to_owned(), table.get(k)?.to_owned())))
+            .collect();
+        let _ = self.env.set(table);
+        val_symt.pop_frame()?;
+        Ok(Value::Type(Arc::new(ValueType::Type(Box::new(
+            ValueTypeDef {
+                name: Some(Ident::new(&self.name)),
+                namespace,
+                fields: HashMap::new(),
+                mutable_fields: HashSet::new(),
+                params: None,
+                type_args: vec![],
+            },
+        )))))
+    }
+}
@@ -1,6 +1,5 @@
 use crate::{
     ast::{Ast, Block},
-    errors::ToZResult,
     types::{sym_table::InterpretSymTable, value::Value},
     ZResult,
 };
@@ -14,8 +13,6 @@ pub fn interpret_asts(input: &Vec<Ast>, val_symt: &mut InterpretSymTable) -> ZRe
     while let Value::Return(v) = last {
         last = *v;
     }
-    if last == Value::Unit {
-        last = Value::I32(0);
-    }
-    last.as_i32().copied().z()
+    // anything but an `i32`, like the value of a declaration, leaves the exit code at 0
+    Ok(last.as_i32().copied().unwrap_or(0))
 }
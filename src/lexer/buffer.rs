@@ -34,4 +34,9 @@ impl Buffer {
             .get(if self.started { self.cursor + 1 } else { 0 })
             .cloned()
     }
+    pub fn peek_nth(&self, n: usize) -> Option<(char, Position)> {
+        self.content
+            .get(if self.started { self.cursor + 1 } else { 0 } + n)
+            .cloned()
+    }
 }
@@ -9,9 +9,11 @@ mod word;
 use std::sync::Arc;
 
 use lazy_regex::lazy_regex;
+pub use number::parse_number;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use smol_str::SmolStr;
+pub use text_literal::{parse_text_literal, split_fstring, FStringPart};
 use tracing::{debug, trace};
 
 use crate::{
@@ -24,7 +26,10 @@ use crate::{
         whitespace::{clean_whitespaces, lex_whitespace},
         word::lex_word,
     },
-    types::{position::Position, token::Token},
+    types::{
+        position::Position,
+        token::{Token, TokenType},
+    },
 };
 
 static ALPHANUMERIC: Lazy<Regex> = lazy_regex!(r"^[a-zA-Z0-9_]+$");
@@ -33,30 +38,39 @@ static WHITESPACE: Lazy<Regex> = lazy_regex!(r"^\s+$");
 static ALPHABETIC: Lazy<Regex> = lazy_regex!(r"^[a-zA-Z_]+$");
 
 #[tracing::instrument(skip_all)]
-pub fn lex(mut input: String, filename: SmolStr) -> ZResult<Vec<Token>> {
+pub fn lex(input: String, filename: SmolStr) -> ZResult<Vec<Token>> {
+    let pos = Position {
+        filename: Some(Arc::new(filename)),
+        ..Default::default()
+    };
+    lex_at(input, pos)
+}
+
+/// Lexes code that starts at `pos` instead of the start of a file
+fn lex_at(mut input: String, pos: Position) -> ZResult<Vec<Token>> {
     if input.trim().is_empty() {
         return Ok(vec![]);
     };
     input.push('\n');
 
-    let pos = Position {
-        filename: Some(Arc::new(filename)),
-        ..Default::default()
-    };
     let mut iter = Buffer::new(&input, pos);
     let mut tokens = vec![];
     while let Some((char, pos)) = iter.to_owned().peek() {
         trace!(?char, ?pos);
-        if char == '"' {
+        if char == '"'
+            || (matches!(char, 'r' | 'c' | 'f') && matches!(iter.peek_nth(1), Some(('"', _))))
+        {
             debug!(?char, ?pos, "Text literal detected");
             lex_text_literal(&mut iter, &mut tokens)?;
-        } else if ALPHABETIC.is_match(&char.to_string()) {
+        } else if ALPHABETIC.is_match(&char.to_string()) || char == '$' {
             debug!(?char, ?pos, "Word detected");
             lex_word(&mut iter, &mut tokens)?;
         } else if WHITESPACE.is_match(&char.to_string()) {
             debug!(?char, ?pos, "Whitespace detected");
             lex_whitespace(&mut iter, &mut tokens)?;
-        } else if NUMERIC.is_match(&char.to_string()) {
+        } else if NUMERIC.is_match(&char.to_string())
+            || (char == '-' && is_negative_number(&iter, &tokens))
+        {
             debug!(?char, ?pos, "Number detected");
             lex_number(&mut iter, &mut tokens)?;
         } else {
@@ -68,3 +82,29 @@ pub fn lex(mut input: String, filename: SmolStr) -> ZResult<Vec<Token>> {
     tokens = clean_whitespaces(tokens);
     Ok(tokens)
 }
+
+/// Whether the `-` at the start of the buffer is the sign of a number literal rather than subtraction
+fn is_negative_number(iter: &Buffer, tokens: &[Token]) -> bool {
+    let next_is_digit = iter
+        .peek_nth(1)
+        .is_some_and(|(c, _)| NUMERIC.is_match(&c.to_string()));
+    let follows_value = tokens
+        .iter()
+        .rev()
+        .find(|t| t.ty != Some(TokenType::Whitespace))
+        .is_some_and(|t| {
+            matches!(
+                t.ty,
+                Some(
+                    TokenType::Ident
+                        | TokenType::LiteralNumber
+                        | TokenType::LiteralMisc
+                        | TokenType::LiteralString
+                        | TokenType::CloseParen
+                        | TokenType::CloseSquareParen
+                        | TokenType::CloseCurlyParen
+                )
+            )
+        });
+    next_is_digit && !follows_value
+}
@@ -1,38 +1,165 @@
+use half::f16;
+use num::{BigInt, BigUint, Num};
 use tracing::trace;
 
 use crate::{
-    errors::ToZResult,
-    lexer::{buffer::Buffer, NUMERIC},
+    errors::{ToZResult, ZError},
+    lexer::{buffer::Buffer, ALPHANUMERIC, NUMERIC},
     types::{
         position::Span,
         token::{Token, TokenType},
+        value::Value,
     },
     ZResult,
 };
 
+const INT_SUFFIXES: [&str; 14] = [
+    "i8", "i16", "i32", "i64", "i128", "isize", "ibig", "u8", "u16", "u32", "u64", "u128", "usize",
+    "ubig",
+];
+const FLOAT_SUFFIXES: [&str; 3] = ["f16", "f32", "f64"];
+
 #[tracing::instrument(skip_all)]
 pub fn lex_number(iter: &mut Buffer, tokens: &mut Vec<Token>) -> ZResult<()> {
     let mut raw = String::new();
     let init_pos = iter.peek().z()?.1;
+    if iter.peek().z()?.0 == '-' {
+        raw.push('-');
+        iter.next().z()?;
+    }
+    let radix = iter.peek().z()?.0 == '0' && matches!(iter.peek_nth(1), Some(('x' | 'o' | 'b', _)));
     let mut dotted = false;
     while let Some((char, pos)) = iter.peek() {
         trace!(?char, ?pos);
-        if NUMERIC.is_match(&char.to_string()) {
+        let next_is_digit = iter
+            .peek_nth(1)
+            .is_some_and(|(c, _)| NUMERIC.is_match(&c.to_string()));
+        if ALPHANUMERIC.is_match(&char.to_string()) {
             raw.push(char);
             iter.next().z()?;
-        } else if char == '.' && !dotted {
+        } else if char == '.' && !dotted && !radix && next_is_digit {
             dotted = true;
             raw.push(char);
             iter.next().z()?;
+        } else if matches!(char, '+' | '-') && !radix && raw.ends_with(['e', 'E']) && next_is_digit
+        {
+            raw.push(char);
+            iter.next().z()?;
         } else {
-            tokens.push(Token {
-                ty: Some(TokenType::LiteralNumber),
-                value: (&raw).into(),
-                span: Span::new(init_pos, &raw),
-                ..Default::default()
-            });
-            return Ok(());
+            break;
         }
     }
+    let span = Span::new(init_pos, &raw);
+    parse_number(&raw).map_err(|e| e.with_span(&span))?;
+    tokens.push(Token {
+        ty: Some(TokenType::LiteralNumber),
+        value: (&raw).into(),
+        span,
+        ..Default::default()
+    });
     Ok(())
 }
+
+macro_rules! parse_int {
+    ($variant:ident $ty:ty, $digits:expr, $radix:expr, $raw:expr, $suffix:expr) => {
+        Value::$variant(
+            <$ty>::from_str_radix($digits, $radix)
+                .ok()
+                .ok_or_else(|| ZError::l004($raw, $suffix))?,
+        )
+    };
+}
+
+/// Converts the raw text of a number literal into its value
+pub fn parse_number(raw: &str) -> ZResult<Value> {
+    let (sign, unsigned) = raw
+        .strip_prefix('-')
+        .map_or(("", raw), |unsigned| ("-", unsigned));
+    let (radix, body) = [("0x", 16), ("0o", 8), ("0b", 2)]
+        .into_iter()
+        .find_map(|(prefix, radix)| Some((radix, unsigned.strip_prefix(prefix)?)))
+        .unwrap_or((10, unsigned));
+    let suffix = INT_SUFFIXES
+        .iter()
+        .chain(if radix == 10 {
+            &FLOAT_SUFFIXES[..]
+        } else {
+            &[]
+        })
+        .find(|s| body.ends_with(**s))
+        .copied();
+    let digits = suffix
+        .and_then(|s| body.strip_suffix(s))
+        .unwrap_or(body)
+        .replace('_', "");
+    let is_float = radix == 10 && digits.contains(['.', 'e', 'E']);
+
+    let valid = if is_float {
+        lazy_regex::regex_is_match!(r"^[0-9]+(\.[0-9]+)?([eE][+-]?[0-9]+)?$", &digits)
+    } else {
+        !digits.is_empty() && digits.chars().all(|c| c.is_digit(radix))
+    };
+    let digits = format!("{sign}{digits}");
+    if !valid || (is_float && suffix.is_some_and(|s| !s.starts_with('f'))) {
+        return Err(ZError::l003(raw));
+    }
+
+    Ok(match suffix {
+        Some("i8") => parse_int!(I8 i8, &digits, radix, raw, "i8"),
+        Some("i16") => parse_int!(I16 i16, &digits, radix, raw, "i16"),
+        Some("i32") => parse_int!(I32 i32, &digits, radix, raw, "i32"),
+        Some("i64") => parse_int!(I64 i64, &digits, radix, raw, "i64"),
+        Some("i128") => parse_int!(I128 i128, &digits, radix, raw, "i128"),
+        Some("isize") => parse_int!(Isize isize, &digits, radix, raw, "isize"),
+        Some("ibig") => parse_int!(Ibig BigInt, &digits, radix, raw, "ibig"),
+        Some("u8") => parse_int!(U8 u8, &digits, radix, raw, "u8"),
+        Some("u16") => parse_int!(U16 u16, &digits, radix, raw, "u16"),
+        Some("u32") => parse_int!(U32 u32, &digits, radix, raw, "u32"),
+        Some("u64") => parse_int!(U64 u64, &digits, radix, raw, "u64"),
+        Some("u128") => parse_int!(U128 u128, &digits, radix, raw, "u128"),
+        Some("usize") => parse_int!(Usize usize, &digits, radix, raw, "usize"),
+        Some("ubig") => parse_int!(Ubig BigUint, &digits, radix, raw, "ubig"),
+        Some("f16") => {
+            let v = f16::from_f64(digits.parse().ok().ok_or_else(|| ZError::l003(raw))?);
+            if v.is_infinite() {
+                return Err(ZError::l004(raw, "f16"));
+            }
+            Value::F16(v)
+        }
+        Some("f32") => {
+            let v: f32 = digits.parse().ok().ok_or_else(|| ZError::l003(raw))?;
+            if v.is_infinite() {
+                return Err(ZError::l004(raw, "f32"));
+            }
+            Value::F32(v)
+        }
+        Some(_) => Value::F64(parse_f64(&digits, raw)?),
+        None if is_float => Value::F64(parse_f64(&digits, raw)?),
+        None => {
+            // unsuffixed integers take the smallest of these that fits
+            if let Ok(v) = i32::from_str_radix(&digits, radix) {
+                Value::I32(v)
+            } else if let Ok(v) = i64::from_str_radix(&digits, radix) {
+                Value::I64(v)
+            } else if let Ok(v) = i128::from_str_radix(&digits, radix) {
+                Value::I128(v)
+            } else if let Ok(v) = u128::from_str_radix(&digits, radix) {
+                Value::U128(v)
+            } else {
+                Value::Ibig(
+                    BigInt::from_str_radix(&digits, radix)
+                        .ok()
+                        .ok_or_else(|| ZError::l003(raw))?,
+                )
+            }
+        }
+    })
+}
+
+fn parse_f64(digits: &str, raw: &str) -> ZResult<f64> {
+    let v: f64 = digits.parse().ok().ok_or_else(|| ZError::l003(raw))?;
+    if v.is_infinite() {
+        return Err(ZError::l004(raw, "f64"));
+    }
+    Ok(v)
+}
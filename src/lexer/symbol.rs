@@ -155,7 +155,14 @@ pub fn lex_symbol(iter: &mut Buffer, tokens: &mut Vec<Token>) -> ZResult<()> {
                 } // TODO |>
                 _ => TokenType::Bar,
             },
-            '.' => TokenType::DotOpr(AccessType::Field),
+            '.' => match iter.peek() {
+                Some(('=', _)) => {
+                    iter.next().z()?;
+                    char.push('=');
+                    TokenType::MutDeclarationOpr
+                }
+                _ => TokenType::DotOpr(AccessType::Field),
+            },
             ':' => match iter.peek() {
                 Some(('=', _)) => {
                     iter.next().z()?;
@@ -174,6 +181,44 @@ pub fn lex_symbol(iter: &mut Buffer, tokens: &mut Vec<Token>) -> ZResult<()> {
                 }
                 _ => TokenType::Colon,
             },
+            '\\' => {
+                let next = iter.next().map(|(c, _)| *c);
+                if let Some(c) = next {
+                    char.push(c);
+                }
+                let opr = match next {
+                    Some('&') => OprType::BitAnd,
+                    Some('|') => OprType::BitOr,
+                    Some('^') => OprType::BitXor,
+                    Some('~') => OprType::BitCompl,
+                    Some('<') if matches!(iter.peek(), Some(('<', _))) => {
+                        iter.next().z()?;
+                        char.push('<');
+                        OprType::Lsh
+                    }
+                    Some('>') if matches!(iter.peek(), Some(('>', _))) => {
+                        iter.next().z()?;
+                        char.push('>');
+                        if let Some(('>', _)) = iter.peek() {
+                            iter.next().z()?;
+                            char.push('>');
+                            OprType::Zrsh
+                        } else {
+                            OprType::Rsh
+                        }
+                    }
+                    _ => return Err(ZError::l001().with_span(Span::new(pos, &char))),
+                };
+                if opr == OprType::BitCompl {
+                    TokenType::UnaryOpr(opr)
+                } else if let Some(('=', _)) = iter.peek() {
+                    iter.next().z()?;
+                    char.push('=');
+                    TokenType::AssignmentOpr(Some(opr))
+                } else {
+                    TokenType::BinaryOpr(opr)
+                }
+            }
             ';' => TokenType::StatementEnd,
             ',' => TokenType::Comma,
             '(' => TokenType::OpenParen,
@@ -1,32 +1,261 @@
+use itertools::Either;
+use smol_str::SmolStr;
 use tracing::trace;
 
 use crate::{
-    errors::ToZResult,
-    lexer::buffer::Buffer,
+    errors::{ToZResult, ZError},
+    lexer::{buffer::Buffer, lex_at},
     types::{
-        position::Span,
+        position::{Position, Span},
         token::{Token, TokenType},
+        value::Value,
     },
     ZResult,
 };
 
 #[tracing::instrument(skip_all)]
 pub fn lex_text_literal(iter: &mut Buffer, tokens: &mut Vec<Token>) -> ZResult<()> {
-    let (_, init_pos) = iter.next().z()?.to_owned();
-    let mut raw = "\"".to_owned();
+    let (first, init_pos) = iter.next().z()?.to_owned();
+    let mut raw = first.to_string();
+    if first != '"' {
+        raw.push(iter.next().z()?.0);
+    }
+    let is_raw = first == 'r';
+    let is_fmt = first == 'f';
+    let mut positions: Vec<Position> = vec![];
+    // how deep into embedded expressions the cursor is, and whether it's in a string inside one
+    let mut depth = 0usize;
+    let mut in_str = false;
     while let Some((char, pos)) = iter.next() {
         trace!(?char, ?pos);
-        if *char == '"' {
+        let char = *char;
+        if depth > 0 {
+            raw.push(char);
+            match char {
+                '\\' if in_str => raw.push(iter.next().z()?.0),
+                '"' => in_str = !in_str,
+                '{' if !in_str => depth += 1,
+                '}' if !in_str => depth -= 1,
+                _ => (),
+            }
+            continue;
+        }
+        if char == '"' {
             raw.push('"');
+            if is_fmt {
+                split_fstring(&raw, &init_pos)?;
+            } else if !is_raw {
+                if let Err((i, escape)) = unescape(split_literal(&raw).1) {
+                    let pos = positions.get(i).z()?.to_owned();
+                    return Err(ZError::l005(&escape).with_span(Span::new(pos, &escape)));
+                }
+            }
+            let span = Span::new(init_pos, &raw);
+            if !is_fmt {
+                parse_text_literal(&raw).map_err(|e| e.with_span(&span))?;
+            }
             tokens.push(Token {
                 ty: Some(TokenType::LiteralString),
                 value: (&raw).into(),
-                span: Span::new(init_pos, &raw),
+                span,
                 ..Default::default()
             });
             return Ok(());
         }
-        raw.push(*char);
+        raw.push(char);
+        positions.push(pos.to_owned());
+        if char == '{' && is_fmt {
+            if matches!(iter.peek(), Some(('{', _))) {
+                raw.push(iter.next().z()?.0);
+            } else {
+                depth = 1;
+            }
+        } else if char == '\\' && !is_raw {
+            // the escaped character can't end the literal
+            if let Some((char, pos)) = iter.next() {
+                raw.push(*char);
+                positions.push(pos.to_owned());
+            }
+        }
+    }
+    let opening = format!("{}\"", split_literal(&raw).0);
+    Err(ZError::l006().with_span(Span::new(init_pos, &opening)))
+}
+
+/// Processes the escapes in the contents of a string literal.
+/// On failure, returns the char index and text of the invalid escape.
+fn unescape(content: &str) -> Result<String, (usize, String)> {
+    let mut out = String::new();
+    let mut chars = content.chars().enumerate().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        let Some((_, escaped)) = chars.next() else {
+            return Err((i, "\\".to_owned()));
+        };
+        out.push(match escaped {
+            '\\' => '\\',
+            '"' => '"',
+            '\'' => '\'',
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            '0' => '\0',
+            'u' => {
+                let mut escape = "\\u".to_owned();
+                if chars.next_if(|(_, c)| *c == '{').is_none() {
+                    return Err((i, escape));
+                }
+                escape.push('{');
+                let mut hex = String::new();
+                while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_hexdigit()) {
+                    hex.push(c);
+                }
+                escape.push_str(&hex);
+                if chars.next_if(|(_, c)| *c == '}').is_none() {
+                    return Err((i, escape));
+                }
+                escape.push('}');
+                u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .filter(|_| hex.len() <= 6)
+                    .and_then(char::from_u32)
+                    .ok_or((i, escape))?
+            }
+            c => return Err((i, format!("\\{c}"))),
+        });
+    }
+    Ok(out)
+}
+
+/// A part of a formatted string literal,
+/// either text or the tokens and format spec of an embedded expression
+pub type FStringPart = Either<String, (Vec<Token>, Option<SmolStr>)>;
+
+/// Splits the raw text of a formatted string literal starting at `init_pos` into its parts
+pub fn split_fstring(raw: &str, init_pos: &Position) -> ZResult<Vec<FStringPart>> {
+    let mut pos = init_pos.to_owned();
+    pos.next_char('f');
+    pos.next_char('"');
+    let mut iter = Buffer::new(split_literal(raw).1, pos);
+    let mut parts = vec![];
+    let mut text = String::new();
+    let mut positions: Vec<Position> = vec![];
+    while let Some((char, pos)) = iter.next().cloned() {
+        match char {
+            '{' | '}' if iter.peek().is_some_and(|(c, _)| c == char) => {
+                iter.next();
+                text.push(char);
+                positions.push(pos);
+            }
+            '}' => return Err(ZError::l008().with_span(Span::new(pos, "}"))),
+            '{' => {
+                parts.push(Either::Left(unescape_at(&text, &positions)?));
+                text.clear();
+                positions.clear();
+                parts.push(Either::Right(lex_embedded(&mut iter, pos)?));
+            }
+            _ => {
+                text.push(char);
+                positions.push(pos);
+                if char == '\\' {
+                    if let Some((char, pos)) = iter.next().cloned() {
+                        text.push(char);
+                        positions.push(pos);
+                    }
+                }
+            }
+        }
+    }
+    parts.push(Either::Left(unescape_at(&text, &positions)?));
+    Ok(parts)
+}
+
+fn unescape_at(text: &str, positions: &[Position]) -> ZResult<String> {
+    unescape(text).or_else(|(i, escape)| {
+        let pos = positions.get(i).z()?.to_owned();
+        Err(ZError::l005(&escape).with_span(Span::new(pos, &escape)))
+    })
+}
+
+/// Lexes the expression embedded after the `{` at `open_pos`, up to its closing `}`
+fn lex_embedded(iter: &mut Buffer, open_pos: Position) -> ZResult<(Vec<Token>, Option<SmolStr>)> {
+    let mut expr = String::new();
+    let mut depth = 1usize;
+    let mut in_str = false;
+    let mut last_colon = None;
+    let mut start_pos = None;
+    loop {
+        let Some((char, pos)) = iter.next().cloned() else {
+            return Err(ZError::l006().with_span(Span::new(open_pos, "{")));
+        };
+        start_pos.get_or_insert(pos);
+        match char {
+            '\\' if in_str => {
+                expr.push(char);
+                expr.push(iter.next().z()?.0);
+                continue;
+            }
+            '"' => in_str = !in_str,
+            '{' if !in_str => depth += 1,
+            '}' if !in_str => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            ':' if !in_str && depth == 1 => last_colon = Some(expr.len()),
+            _ => (),
+        }
+        expr.push(char);
+    }
+    // a format spec is whatever comes after the last `:` if it looks like one
+    let (expr, spec) = last_colon
+        .map(|i| expr.split_at(i))
+        .and_then(|(before, spec)| {
+            let spec = spec.strip_prefix(':')?;
+            (!before.ends_with(':')
+                && lazy_regex::regex_is_match!(r"^0?[0-9]*(\.[0-9]+)?[xXobe]?$", spec))
+            .then(|| (before, Some(SmolStr::from(spec))))
+        })
+        .unwrap_or((&expr, None));
+    let tokens = lex_at(
+        expr.to_owned(),
+        start_pos.unwrap_or_else(|| open_pos.to_owned()),
+    )?;
+    if tokens.is_empty() {
+        return Err(ZError::l009().with_span(Span::new(open_pos, "{")));
     }
-    Ok(())
+    Ok((tokens, spec))
+}
+
+/// Converts the raw text of a string, raw string or char literal into its value
+pub fn parse_text_literal(raw: &str) -> ZResult<Value> {
+    let (prefix, content) = split_literal(raw);
+    if prefix == "r" {
+        return Ok(Value::Str(content.to_owned()));
+    }
+    let content = unescape(content).map_err(|(_, escape)| ZError::l005(&escape))?;
+    if prefix != "c" {
+        return Ok(Value::Str(content));
+    }
+    let mut chars = content.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(Value::Char(c));
+    }
+    // otherwise it's the hex code of the character
+    u32::from_str_radix(&content, 16)
+        .ok()
+        .filter(|_| content.len() <= 6)
+        .and_then(char::from_u32)
+        .map(Value::Char)
+        .ok_or_else(|| ZError::l007(raw))
+}
+
+/// Splits a text literal into its prefix and its contents between the quotes
+fn split_literal(raw: &str) -> (&str, &str) {
+    let (prefix, rest) = raw.split_once('"').unwrap_or((raw, ""));
+    (prefix, rest.strip_suffix('"').unwrap_or(rest))
 }
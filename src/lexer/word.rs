@@ -15,7 +15,8 @@ pub fn lex_word(iter: &mut Buffer, tokens: &mut Vec<Token>) -> ZResult<()> {
     let init_pos = iter.peek().z()?.1;
     while let Some((char, pos)) = iter.peek() {
         trace!(?char, ?pos);
-        if ALPHANUMERIC.is_match(&char.to_string()) {
+        // `$` can only start a word, as in `$` and `$ty` in classes
+        if ALPHANUMERIC.is_match(&char.to_string()) || (raw.is_empty() && char == '$') {
             raw.push(char);
             iter.next().z()?;
         } else {
@@ -37,9 +38,11 @@ pub fn lex_word(iter: &mut Buffer, tokens: &mut Vec<Token>) -> ZResult<()> {
                     "defer" => TokenType::Keyword(Keyword::Defer),
                     "class" => TokenType::Keyword(Keyword::Class),
                     "struct" => TokenType::Keyword(Keyword::Struct),
+                    "use" => TokenType::Keyword(Keyword::Use),
                     "const" => TokenType::Flag(Flag::Const),
                     "hoi" => TokenType::Flag(Flag::Hoi),
                     "pub" => TokenType::Flag(Flag::Pub),
+                    "pubp" => TokenType::Flag(Flag::Pubp),
                     "inst" => TokenType::Flag(Flag::Inst),
                     "priv" => TokenType::Flag(Flag::Priv),
                     "prot" => TokenType::Flag(Flag::Prot),
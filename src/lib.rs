@@ -1,5 +1,6 @@
 #![feature(box_patterns)]
 #![feature(iterator_try_reduce)]
+#![feature(float_gamma)]
 #![allow(clippy::type_complexity, clippy::too_many_arguments)]
 #![warn(
     clippy::as_underscore,
@@ -148,14 +149,16 @@ pub mod errors;
 pub mod file_importer;
 pub mod interpreter;
 pub mod lexer;
+pub mod package;
 pub mod parser;
 pub mod primitives;
 pub mod repl;
+pub mod stdlib;
 pub mod types;
 
 use std::{path::Path, time::Instant};
 
-use errors::{ZError, ZResult};
+use errors::{ZError, ZErrors, ZResult};
 use itertools::Either;
 use smol_str::SmolStr;
 use tracing::{debug, info, trace};
@@ -163,10 +166,11 @@ use tracing::{debug, info, trace};
 use crate::{
     ast::{Ast, AstData, Reconstruct},
     errors::ToZResult,
-    file_importer::{import_file, register_input},
+    file_importer::{import_file, register_input, with_import},
     interpreter::interpret_asts,
     lexer::lex,
-    parser::parse_token_list,
+    parser::parse_statements,
+    stdlib::take_exit_code,
     types::{
         r#type::Type,
         sym_table::{InterpretSymTable, TypeCheckSymTable},
@@ -174,45 +178,91 @@ use crate::{
     },
 };
 
+/// Compiles a file or some named input, giving every error found in it up to the error limit
 pub fn compile(
     file: &Either<&Path, (SmolStr, String)>,
     ty_symt: &mut TypeCheckSymTable,
     pop_symt: bool,
-) -> ZResult<Vec<Ast>> {
-    let (input, filename) = match &file {
-        Either::Left(p) => (import_file(p).z()?, SmolStr::from(p.to_string_lossy())),
-        Either::Right((name, input)) => (register_input(name, input).z()?, name.to_owned()),
-    };
+) -> Result<Vec<Ast>, ZErrors> {
+    match &file {
+        Either::Left(p) => {
+            let path = p.canonicalize().unwrap_or_else(|_| p.to_path_buf());
+            with_import(&path, || {
+                let input = import_file(p).map_err(ZErrors::from_error)?;
+                compile_input(&input, p.to_string_lossy().into(), ty_symt, pop_symt)
+            })
+        }
+        Either::Right((name, input)) => {
+            let input = register_input(name, input)
+                .z()
+                .map_err(ZErrors::from_error)?;
+            compile_input(&input, name.to_owned(), ty_symt, pop_symt)
+        }
+    }
+}
 
+fn compile_input(
+    input: &str,
+    filename: SmolStr,
+    ty_symt: &mut TypeCheckSymTable,
+    pop_symt: bool,
+) -> Result<Vec<Ast>, ZErrors> {
     info!("Lexing");
     let lex_start = Instant::now();
-    let lexed = lex((*input).to_owned(), filename)?;
+    let lexed = lex(input.to_owned(), filename).map_err(ZErrors::from_error)?;
     let lex_time = lex_start.elapsed().as_micros();
     trace!("{lexed:#?}");
 
     info!("Parsing");
     let parse_start = Instant::now();
-    let mut parsed = parse_token_list(lexed)?;
+    let statements = parse_statements(lexed).map_err(ZErrors::from_error)?;
     let parse_time = parse_start.elapsed().as_micros();
-    trace!("{parsed:#?}");
-    debug!("{}", parsed.reconstruct());
+    trace!("{statements:#?}");
 
+    // statements that failed to parse or desugar are skipped, and the rest are still type checked
+    // so that all their errors are given together, in order
     info!("Desugaring");
     let desugar_start = Instant::now();
-    for ele in &mut parsed {
-        ele.desugar()?;
-    }
+    let statements = statements
+        .into_iter()
+        .map(|(statement, ident)| {
+            (
+                statement.and_then(|mut ele| ele.desugar().map(|()| ele)),
+                ident,
+            )
+        })
+        .collect::<Vec<_>>();
     let desugar_time = desugar_start.elapsed().as_micros();
-    trace!("{parsed:#?}");
-    debug!("{}", parsed.reconstruct());
 
     info!("Typechecking");
     let typecheck_start = Instant::now();
-    for ele in &mut parsed {
-        ele.type_check(ty_symt)?;
+    let mut parsed = vec![];
+    let mut errors = vec![];
+    for (statement, ident) in statements {
+        match statement {
+            Ok(mut ele) => {
+                let depth = ty_symt.0.len();
+                if let Err(e) = ele.type_check(ty_symt) {
+                    errors.push(e);
+                    ty_symt.recover(depth, &ele);
+                }
+                parsed.push(ele);
+            }
+            Err(e) => {
+                // a declaration that failed to parse still declares its variable
+                if let Some(ident) = ident {
+                    ty_symt.declare_error(&ident.name, &ident);
+                }
+                errors.push(e);
+            }
+        }
+    }
+    debug!("{}", parsed.reconstruct());
+    if !errors.is_empty() {
+        return Err(ZErrors::new(errors));
     }
     if pop_symt {
-        ty_symt.pop_frame()?;
+        ty_symt.pop_frame().map_err(ZErrors::from_error)?;
     }
     let typecheck_time = typecheck_start.elapsed().as_micros();
     trace!("{parsed:#?}");
@@ -233,7 +283,20 @@ pub fn compile(
 pub fn interpret(input: &Vec<Ast>, val_symt: &mut InterpretSymTable) -> ZResult<i32> {
     info!("Interpreting");
     let interpret_start = Instant::now();
-    let exit_code = interpret_asts(input, val_symt)?;
+    let depth = val_symt.0.len();
+    let exit_code = match interpret_asts(input, val_symt) {
+        Ok(exit_code) => exit_code,
+        Err(e) => {
+            let Some(exit_code) = take_exit_code() else {
+                return Err(e);
+            };
+            // `sys::exit` leaves the scopes it was called in, so their defers are run here
+            while val_symt.0.len() > depth {
+                val_symt.pop_frame()?;
+            }
+            exit_code
+        }
+    };
     let interpret_time = interpret_start.elapsed().as_micros();
     info!("Exited with code {exit_code}");
     info!("Stats");
@@ -1,12 +1,17 @@
-use std::{path::PathBuf, process::exit};
+use std::{env::current_dir, iter, path::PathBuf, process::exit};
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use color_eyre::{config::HookBuilder, eyre::Result};
 use itertools::Either;
 use tracing_error::ErrorLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 use zyxt::{
+    errors::{set_error_limit, ToZResult, ZError, ZErrors, ZResult, DEFAULT_ERROR_LIMIT},
+    file_importer::set_search_paths,
+    package::Package,
+    primitives::{set_overflow_mode, OverflowMode},
     repl,
+    stdlib::set_args,
     types::sym_table::{InterpretSymTable, TypeCheckSymTable},
 };
 
@@ -18,14 +23,94 @@ struct Args {
 }
 #[derive(Parser)]
 enum Subcmd {
-    /// Runs Zyxt source code
+    /// Runs Zyxt source code, or the package in the current directory if no file is given
     Run(Run),
+    /// Type-checks the package in the current directory without running it
+    Build(Build),
+    /// Creates a new package
+    New(New),
     /// Start a REPL for Zyxt
     Repl,
 }
 #[derive(Parser)]
 struct Run {
-    filename: PathBuf,
+    filename: Option<PathBuf>,
+    /// What integer arithmetic does when the result doesn't fit in its type
+    #[clap(long, value_enum, default_value_t)]
+    overflow: Overflow,
+    /// Directories to look for modules in, after the importing file's directory
+    #[clap(long = "path", short = 'I')]
+    search_paths: Vec<PathBuf>,
+    /// The most errors to report if the code doesn't compile
+    #[clap(long, default_value_t = DEFAULT_ERROR_LIMIT)]
+    error_limit: usize,
+    /// Arguments for the script, after `--`
+    #[clap(last = true)]
+    args: Vec<String>,
+}
+#[derive(Copy, Clone, Default, ValueEnum)]
+enum Overflow {
+    /// Raise an error
+    #[default]
+    Checked,
+    /// Wrap around at the boundary of the type
+    Wrapping,
+    /// Clamp to the minimum or maximum of the type
+    Saturating,
+}
+impl From<Overflow> for OverflowMode {
+    fn from(overflow: Overflow) -> Self {
+        match overflow {
+            Overflow::Checked => Self::Checked,
+            Overflow::Wrapping => Self::Wrapping,
+            Overflow::Saturating => Self::Saturating,
+        }
+    }
+}
+#[derive(Parser)]
+struct Build {
+    /// Directories to look for modules in, after the importing file's directory
+    #[clap(long = "path", short = 'I')]
+    search_paths: Vec<PathBuf>,
+    /// The most errors to report if the code doesn't compile
+    #[clap(long, default_value_t = DEFAULT_ERROR_LIMIT)]
+    error_limit: usize,
+}
+#[derive(Parser)]
+struct New {
+    /// The directory to create the package in
+    path: PathBuf,
+    /// The name of the package, if it's not the name of the directory
+    #[clap(long)]
+    name: Option<String>,
+}
+
+/// Prints the error and exits if there is one
+fn or_exit<T>(res: ZResult<T>) -> Result<T> {
+    match res {
+        Ok(v) => Ok(v),
+        Err(e) => {
+            e.print()?;
+            exit(e.exit_code())
+        }
+    }
+}
+
+/// Prints the errors and exits if there are any
+fn or_exit_all<T>(res: Result<T, ZErrors>) -> Result<T> {
+    match res {
+        Ok(v) => Ok(v),
+        Err(e) => {
+            e.print()?;
+            exit(e.exit_code())
+        }
+    }
+}
+
+/// The package that the current directory is in
+fn current_package() -> ZResult<Package> {
+    let dir = current_dir().z()?;
+    Package::find(&dir)?.ok_or_else(|| ZError::m002(dir.display()))
 }
 
 fn main() -> Result<()> {
@@ -41,24 +126,56 @@ fn main() -> Result<()> {
 
     match args.subcmd {
         Subcmd::Run(sargs) => {
+            set_overflow_mode(sargs.overflow.into());
+            set_search_paths(sargs.search_paths);
+            set_error_limit(sargs.error_limit);
+            let filename = if let Some(filename) = sargs.filename {
+                filename
+            } else {
+                or_exit(current_package())?.entry()
+            };
+            set_args(
+                iter::once(filename.to_string_lossy().into_owned())
+                    .chain(sargs.args)
+                    .collect(),
+            );
             let mut ty_symt = TypeCheckSymTable::default();
             let mut val_symt = InterpretSymTable::default();
-            let compiled = match zyxt::compile(&Either::Left(&sargs.filename), &mut ty_symt, true) {
-                Ok(v) => v,
-                Err(e) => {
-                    e.print()?;
-                    exit(1)
-                }
-            };
-            let exit_code = match zyxt::interpret(&compiled, &mut val_symt) {
-                Ok(v) => v,
-                Err(e) => {
-                    e.print()?;
-                    exit(1)
-                }
-            };
+            let compiled =
+                or_exit_all(zyxt::compile(&Either::Left(&filename), &mut ty_symt, true))?;
+            let exit_code = or_exit(zyxt::interpret(&compiled, &mut val_symt))?;
             exit(exit_code);
         }
+        Subcmd::Build(sargs) => {
+            set_search_paths(sargs.search_paths);
+            set_error_limit(sargs.error_limit);
+            let package = or_exit(current_package())?;
+            or_exit_all(zyxt::compile(
+                &Either::Left(&package.entry()),
+                &mut TypeCheckSymTable::default(),
+                true,
+            ))?;
+            println!(
+                "Built `{}` v{}",
+                package.name(),
+                package.manifest.package.version
+            );
+        }
+        Subcmd::New(sargs) => {
+            let name = sargs.name.unwrap_or_else(|| {
+                sargs
+                    .path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default()
+            });
+            let package = or_exit(Package::create(&sargs.path, &name))?;
+            println!(
+                "Created package `{}` in {}",
+                package.name(),
+                package.root.display()
+            );
+        }
         Subcmd::Repl => repl::repl()?,
     }
     Ok(())
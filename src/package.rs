@@ -0,0 +1,158 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::errors::{ZError, ZResult};
+
+pub const MANIFEST_NAME: &str = "zyxt.toml";
+
+/// The contents of a `zyxt.toml`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub package: PackageInfo,
+    #[serde(default)]
+    pub dependencies: HashMap<String, Dependency>,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageInfo {
+    pub name: String,
+    pub version: String,
+    /// The file that's run, relative to the package's root
+    #[serde(default = "PackageInfo::default_entry")]
+    pub entry: PathBuf,
+}
+impl PackageInfo {
+    fn default_entry() -> PathBuf {
+        ["src", "main.xt"].into_iter().collect()
+    }
+}
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Dependency {
+    /// The dependency's root, relative to the root of the package depending on it
+    pub path: PathBuf,
+}
+
+/// A directory with a `zyxt.toml` in it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Package {
+    pub root: PathBuf,
+    pub manifest: Manifest,
+}
+
+impl Package {
+    /// Reads the manifest in `root`
+    pub fn load(root: &Path) -> ZResult<Self> {
+        let path = root.join(MANIFEST_NAME);
+        let contents =
+            std::fs::read_to_string(&path).map_err(|e| ZError::m001(path.display(), e))?;
+        let manifest = toml::from_str::<Manifest>(&contents)
+            .map_err(|e| ZError::m001(path.display(), e.message()))?;
+        if !is_valid_name(&manifest.package.name) {
+            return Err(ZError::m005(&manifest.package.name));
+        }
+        Ok(Self {
+            root: root.canonicalize().unwrap_or_else(|_| root.to_path_buf()),
+            manifest,
+        })
+    }
+    /// Finds the package that `dir` is in, looking for a manifest in it and then its parents
+    pub fn find(dir: &Path) -> ZResult<Option<Self>> {
+        // an empty path is the current directory, as for files given without a directory
+        let dir = Path::new(".").join(dir);
+        let dir = dir.canonicalize().unwrap_or(dir);
+        dir.ancestors()
+            .find(|dir| dir.join(MANIFEST_NAME).is_file())
+            .map(|root| {
+                debug!(?root, "Found package");
+                Self::load(root)
+            })
+            .transpose()
+    }
+    /// Creates a package called `name` in a new directory `dir`, with a hello world entry file
+    pub fn create(dir: &Path, name: &str) -> ZResult<Self> {
+        if !is_valid_name(name) {
+            return Err(ZError::m005(name));
+        }
+        if dir.exists() {
+            return Err(ZError::m004(dir.display()));
+        }
+        let manifest = Manifest {
+            package: PackageInfo {
+                name: name.to_owned(),
+                version: "0.1.0".to_owned(),
+                entry: PackageInfo::default_entry(),
+            },
+            dependencies: HashMap::new(),
+        };
+        let write = |path: &Path, contents: &str| {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| ZError::m001(path.display(), e))?;
+            }
+            std::fs::write(path, contents).map_err(|e| ZError::m001(path.display(), e))
+        };
+        write(
+            &dir.join(MANIFEST_NAME),
+            &toml::to_string(&manifest).map_err(|e| ZError::m001(MANIFEST_NAME, e))?,
+        )?;
+        write(
+            &dir.join(&manifest.package.entry),
+            "ter.out \"Hello, world!\";\n",
+        )?;
+        Self::load(dir)
+    }
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.manifest.package.name
+    }
+    #[must_use]
+    pub fn entry(&self) -> PathBuf {
+        self.root.join(&self.manifest.package.entry)
+    }
+    /// The directory the entry file is in, which modules of the package are looked for in
+    #[must_use]
+    pub fn src_dir(&self) -> PathBuf {
+        self.entry()
+            .parent()
+            .map_or_else(|| self.root.to_owned(), Path::to_path_buf)
+    }
+    /// The package of the dependency called `name`, if there's one
+    pub fn dependency(&self, name: &str) -> ZResult<Option<Self>> {
+        let Some(dependency) = self.manifest.dependencies.get(name) else {
+            return Ok(None);
+        };
+        let root = self.root.join(&dependency.path);
+        if !root.join(MANIFEST_NAME).is_file() {
+            return Err(ZError::m003(name, root.display()));
+        }
+        Self::load(&root).map(Some)
+    }
+    /// Finds the file of a module in a dependency, as in `dep` for its entry file and `dep::a`
+    /// for `a.xt` next to it
+    pub fn find_dependency_module(&self, segments: &[&str]) -> ZResult<Option<PathBuf>> {
+        let Some((name, rest)) = segments.split_first() else {
+            return Ok(None);
+        };
+        let Some(dependency) = self.dependency(name)? else {
+            return Ok(None);
+        };
+        Ok(Some(if rest.is_empty() {
+            dependency.entry()
+        } else {
+            dependency
+                .src_dir()
+                .join(rest.iter().collect::<PathBuf>())
+                .with_extension("xt")
+        }))
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
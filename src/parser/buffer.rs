@@ -122,7 +122,8 @@ impl Buffer {
             }
         }
         if nest_level != 0 {
-            return Err(ZError::p009(end_token).with_span(&self.content[self.cursor]));
+            // the end was reached without finding it, so the unclosed start is pointed out instead
+            return Err(ZError::p009(end_token).with_span(self.content.get(start)));
         }
         Ok(BufferWindow {
             slice: self.content[start + 1..self.cursor].to_owned(),
@@ -33,29 +33,29 @@ impl Buffer {
             let init_span = selected.span();
             debug!(pos = ?init_span, "Parsing");
             let start = self.cursor;
-            let mut selected = self.next_or_err()?;
-            let args = if let Either::Right(Token {
+            let args = if let Some(Either::Right(Token {
                 ty: Some(TokenType::Bar),
                 ..
-            }) = selected
+            })) = self.peek()
             {
+                let selected = self.next_or_err()?;
                 debug!(pos = ?selected.span(), "Argument list detected");
                 if kwd == Keyword::Class {
                     return Err(ZError::p010().with_span(selected));
                 }
-                let args = self.parse_args()?;
-                selected = self.next_or_err()?;
-                Some(args)
+                Some(self.parse_args()?)
             } else {
                 None
             };
-            let content = if let Either::Left(Ast::Block(block)) = &selected {
-                debug!(pos = ?selected.span(), "Block detected");
-                Some(block.to_owned())
+            let content = if let Some(Either::Left(Ast::Block(block))) = self.peek() {
+                let block = block.to_owned();
+                debug!(pos = ?block.span(), "Block detected");
+                self.next();
+                Some(block)
             } else if kwd == Keyword::Class {
-                return Err(ZError::p011().with_span(selected));
+                return Err(ZError::p011().with_span(self.next_or_err()?));
             } else {
-                self.prev()?;
+                // a struct with only fields
                 None
             };
             let ele = Ast::Class(Class::Raw {
@@ -63,6 +63,7 @@ impl Buffer {
                 is_struct: kwd == Keyword::Struct,
                 content,
                 args,
+                name: None,
             });
             trace!(?ele);
             let buffer_window = BufferWindow {
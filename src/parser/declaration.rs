@@ -12,35 +12,30 @@ use crate::{
 };
 
 impl Buffer {
-    #[allow(unused_assignments)]
     #[tracing::instrument(skip_all)]
     pub fn parse_declaration(&mut self) -> ZResult<()> {
         self.reset_cursor();
         let mut flag_pos = None;
-        let mut start = None;
         while let Some(selected) = self.next() {
-            if matches!(
-                selected,
+            let is_mut = match selected {
                 Either::Right(Token {
                     ty: Some(TokenType::Flag(_)),
                     ..
-                })
-            ) {
-                debug!(pos = ?selected.span(), "Flag detected");
-                flag_pos = Some(self.cursor);
-                start = Some(self.cursor);
-                continue;
-            } else if !matches!(
-                selected,
+                }) => {
+                    debug!(pos = ?selected.span(), "Flag detected");
+                    flag_pos.get_or_insert(self.cursor);
+                    continue;
+                }
                 Either::Right(Token {
                     ty: Some(TokenType::DeclarationOpr),
                     ..
-                })
-            ) {
-                continue;
-            }
-
-            start.get_or_insert(self.cursor - 1);
+                }) => false,
+                Either::Right(Token {
+                    ty: Some(TokenType::MutDeclarationOpr),
+                    ..
+                }) => true,
+                _ => continue,
+            };
             let eq_span = selected.span();
 
             let declared_var = if let Some(Either::Left(d)) = self.peek_prev() {
@@ -50,41 +45,54 @@ impl Buffer {
             } else {
                 return Err(ZError::p008().with_span(selected));
             };
-            debug!(pos = ?declared_var.span(), "Parsing declaration");
-
-            let flags = if let Some(flag_pos) = flag_pos {
-                self.content[flag_pos..self.cursor - 1]
-                    .iter()
-                    .map(|ele| {
-                        if let Either::Right(Token {
-                            ty: Some(TokenType::Flag(flag)),
-                            span,
-                            ..
-                        }) = ele
-                        {
-                            debug!(?flag, "Flag detected");
-                            Ok((flag.to_owned(), span.to_owned()))
-                        } else {
-                            Err(ZError::p013().with_span(ele))
-                        }
-                    })
-                    .collect::<Result<_, _>>()?
+            let (var_pos, declared_var, ty) = if let (
+                Some(Either::Right(Token {
+                    ty: Some(TokenType::Colon),
+                    ..
+                })),
+                Some(Either::Left(var)),
+            ) = (
+                self.cursor.checked_sub(2).and_then(|i| self.content.get(i)),
+                self.cursor.checked_sub(3).and_then(|i| self.content.get(i)),
+            ) {
+                debug!(pos = ?declared_var.span(), "Type annotation detected");
+                (self.cursor - 3, var.to_owned(), Some(declared_var))
             } else {
-                vec![]
+                (self.cursor - 1, declared_var, None)
             };
+            debug!(pos = ?declared_var.span(), is_mut, "Parsing declaration");
+
+            let start = flag_pos.take().unwrap_or(var_pos);
+            let flags = self.content[start..var_pos]
+                .iter()
+                .map(|ele| {
+                    if let Either::Right(Token {
+                        ty: Some(TokenType::Flag(flag)),
+                        span,
+                        ..
+                    }) = ele
+                    {
+                        debug!(?flag, "Flag detected");
+                        Ok((flag.to_owned(), span.to_owned()))
+                    } else {
+                        Err(ZError::p013().with_span(ele))
+                    }
+                })
+                .collect::<Result<_, _>>()?;
             self.next_or_err()?;
             let content = self.rest_incl_curr().with_as_buffer(&Self::parse_as_expr)?;
             let ele = Ast::Declare(Declare {
                 variable: declared_var.to_owned().into(),
                 content: content.into(),
                 flags,
-                ty: None,
+                ty: ty.map(Into::into),
+                is_mut,
                 eq_span,
             });
             trace!(?ele);
             let buffer_window = BufferWindow {
                 slice: vec![Either::Left(ele)],
-                range: start.take().unwrap_or_else(|| unreachable!())..self.content.len(),
+                range: start..self.content.len(),
             };
             self.splice_buffer(buffer_window);
         }
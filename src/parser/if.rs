@@ -18,7 +18,6 @@ impl Buffer {
         while let Some(selected) = self.next() {
             let Either::Right(Token {
                 ty: Some(TokenType::Keyword(kwd)),
-                span: ref kwd_span,
                 ..
             }) = selected else {
                 continue;
@@ -61,6 +60,7 @@ impl Buffer {
                 };
                 debug!(?kwd, pos = ?selected.span(), "Parsing condition");
                 prev_kwd = kwd;
+                let kwd_span = selected.span();
                 selected = self.next_or_err()?;
                 let condition = if kwd == Keyword::Else {
                     None
@@ -91,7 +91,7 @@ impl Buffer {
                     return Err(ZError::p018().with_span(selected));
                 };
                 conditions.push(Condition {
-                    kwd_span: Some(kwd_span.to_owned()),
+                    kwd_span,
                     condition,
                     if_true: block.to_owned(),
                 });
@@ -11,6 +11,7 @@ mod proc_fn;
 mod r#return;
 mod un_opr;
 mod unparen_call;
+mod r#use;
 mod var_literal_call;
 
 use itertools::Either;
@@ -18,9 +19,9 @@ use smol_str::SmolStr;
 use tracing::{debug, info};
 
 use crate::{
-    ast::{Ast, AstData, Block, Comment},
-    errors::{ZError, ZResult},
-    parser::buffer::{Buffer, BufferWindow},
+    ast::{Ast, AstData, Block, Comment, Ident},
+    errors::{ZError, ZErrors, ZResult},
+    parser::buffer::{Buffer, BufferWindow, BufferWindows},
     types::{
         position::{GetSpan, Span},
         token::{Token, TokenType},
@@ -58,6 +59,7 @@ impl Buffer {
         //self.parse_enum()?;
         self.parse_var_literal_call()?;
         self.parse_delete()?;
+        self.parse_use()?;
         self.parse_return()?;
         self.parse_declaration()?;
         self.parse_assignment_opr()?;
@@ -78,8 +80,65 @@ impl Buffer {
     }
 }
 
+/// Parses each top-level statement on its own, so that one with an error is skipped up to the next
+/// `;` or the end of its braces, and the rest are still checked. Only top-level statements are
+/// recovered from, so an error inside a block, like a proc's body, skips the whole statement it's in
 #[tracing::instrument(skip_all)]
-pub fn parse_token_list(mut input: Vec<Token>) -> ZResult<Vec<Ast>> {
+pub fn parse_token_list(input: Vec<Token>) -> Result<Vec<Ast>, ZErrors> {
+    let mut content = vec![];
+    let mut errors = vec![];
+    for (statement, _) in parse_statements(input).map_err(ZErrors::from_error)? {
+        match statement {
+            Ok(ast) => content.push(ast),
+            Err(e) => errors.push(e),
+        }
+    }
+    if errors.is_empty() {
+        Ok(content)
+    } else {
+        Err(ZErrors::new(errors))
+    }
+}
+
+/// Parses each top-level statement on its own like [`parse_token_list`], keeping them in order. A
+/// statement with an error comes with the variable it starts by declaring, if any, so that it can
+/// still be declared
+#[tracing::instrument(skip_all)]
+pub fn parse_statements(input: Vec<Token>) -> ZResult<Vec<(ZResult<Ast>, Option<Ident>)>> {
+    let mut statements = split_statements(input)?;
+    Ok(statements
+        .buffer_windows
+        .iter_mut()
+        .map(
+            |statement| match statement.with_as_buffer(&Buffer::parse_as_expr) {
+                Ok(ast) => (Ok(ast), None),
+                Err(e) => (Err(e), declared_ident(&statement.slice)),
+            },
+        )
+        .collect())
+}
+
+/// The variable a statement declares, if it starts with one and a `:=` or `.=`
+fn declared_ident(slice: &[Either<Ast, Token>]) -> Option<Ident> {
+    match slice {
+        [Either::Right(ident), Either::Right(opr), ..]
+            if ident.ty == Some(TokenType::Ident)
+                && [
+                    Some(TokenType::DeclarationOpr),
+                    Some(TokenType::MutDeclarationOpr),
+                ]
+                .contains(&opr.ty) =>
+        {
+            Some(Ident {
+                name: ident.value.to_owned(),
+                name_span: Some(ident.span.to_owned()),
+            })
+        }
+        _ => None,
+    }
+}
+
+fn split_statements(mut input: Vec<Token>) -> ZResult<BufferWindows> {
     let mut comments: Vec<Comment> = vec![];
 
     info!("Removing comments");
@@ -118,5 +177,9 @@ pub fn parse_token_list(mut input: Vec<Token>) -> ZResult<Vec<Ast>> {
 
     let mut buffer = Buffer::new(input);
     buffer.next_or_err()?;
-    Ok(buffer.parse_as_block()?.content)
+    buffer.get_split_between(
+        TokenType::OpenCurlyParen,
+        TokenType::CloseCurlyParen,
+        TokenType::StatementEnd,
+    )
 }
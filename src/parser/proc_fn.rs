@@ -2,12 +2,12 @@ use itertools::Either;
 use tracing::{debug, trace};
 
 use crate::{
-    ast::{Argument, Ast, Block, Procedure},
+    ast::{Argument, Ast, Block, Ident, Procedure, UnaryOpr},
     errors::{ZError, ZResult},
     parser::buffer::{Buffer, BufferWindow},
     types::{
         position::GetSpan,
-        token::{Keyword, Token, TokenType},
+        token::{Keyword, OprType, Token, TokenType},
     },
 };
 
@@ -18,28 +18,73 @@ impl Buffer {
         let mut windows =
             self.get_split_between(TokenType::Bar, TokenType::Bar, TokenType::Comma)?;
         windows.with_as_buffers(&|buf| {
-            let arg_sections = buf
-                .get_split(TokenType::Colon)?
-                .with_as_buffers(&Self::parse_as_expr)?;
-            let name = if let Some(name) = arg_sections.first() {
-                if let Ast::Ident(ident) = name {
-                    debug!(pos = ?name.span(), "Name detected");
+            // the default comes after either a second `:` or a `:=`, or a `.=` if it's mutable
+            let is_mut = buf.content.iter().any(|ele| {
+                matches!(
+                    ele,
+                    Either::Right(Token {
+                        ty: Some(TokenType::MutDeclarationOpr),
+                        ..
+                    })
+                )
+            });
+            let mut decl_sections = buf.get_split(if is_mut {
+                TokenType::MutDeclarationOpr
+            } else {
+                TokenType::DeclarationOpr
+            })?;
+            let mut decl_sections = decl_sections.buffer_windows.iter_mut();
+            let mut arg_sections = decl_sections
+                .next()
+                .map(|w| w.as_buffer().get_split(TokenType::Colon))
+                .transpose()?
+                .map(|mut w| w.with_as_buffers(&Self::parse_as_expr))
+                .transpose()?
+                .unwrap_or_default();
+            if let Some(default) = decl_sections.next() {
+                arg_sections.push(default.with_as_buffer(&Self::parse_as_expr)?);
+            }
+            let name = match arg_sections.first() {
+                Some(Ast::Ident(ident)) => {
+                    debug!(pos = ?ident.span(), "Name detected");
                     ident.to_owned()
-                } else {
-                    return Err(ZError::p019().with_span(name));
                 }
-            } else {
-                return Err(ZError::p019().with_span(&init_span));
+                // `&$` is the same as `$`
+                Some(Ast::UnaryOpr(UnaryOpr {
+                    ty: OprType::Ref,
+                    operand,
+                    ..
+                })) if matches!(&**operand, Ast::Ident(Ident { name, .. }) if name == "$") => {
+                    debug!(pos = ?operand.span(), "Instance detected");
+                    Ident {
+                        name: "$".into(),
+                        name_span: operand.span(),
+                    }
+                }
+                Some(name) => return Err(ZError::p019().with_span(name)),
+                None => return Err(ZError::p019().with_span(&init_span)),
             };
             let ty = if let Some(ele) = arg_sections.get(1) {
                 debug!(pos = ?ele.span(), "Type detected");
                 ele.to_owned().into()
+            } else if name.name == "$" {
+                // the instance is always of the class's own type
+                Ast::Ident(Ident {
+                    name: "$ty".into(),
+                    name_span: name.span(),
+                })
+                .into()
             } else {
                 return Err(ZError::p020().with_span(&init_span));
             };
             let default = arg_sections.get(2).cloned();
             debug!(pos = ?default.as_ref().map(GetSpan::span), "Default may be detected");
-            Ok(Argument { name, ty, default })
+            Ok(Argument {
+                name,
+                ty,
+                default,
+                is_mut,
+            })
         })
     }
     #[tracing::instrument(skip_all)]
@@ -25,7 +25,7 @@ impl Buffer {
             let opr_span = selected.span();
             debug!(pos = ?opr_span);
             let operand = self
-                .rest_incl_curr()
+                .window(self.cursor + 1..self.content.len())
                 .with_as_buffer(&Self::parse_as_expr)?
                 .into();
             let ele = Ast::UnaryOpr(UnaryOpr {
@@ -36,7 +36,7 @@ impl Buffer {
             trace!(?ele);
             let buffer_window = BufferWindow {
                 slice: vec![Either::Left(ele)],
-                range: self.cursor - 1..self.content.len(),
+                range: self.cursor..self.content.len(),
             };
             self.splice_buffer(buffer_window);
         }
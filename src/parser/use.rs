@@ -0,0 +1,53 @@
+use itertools::Either;
+use tracing::{debug, trace};
+
+use crate::{
+    ast::{Ast, Use},
+    errors::{ZError, ZResult},
+    parser::buffer::{Buffer, BufferWindow},
+    types::{
+        position::GetSpan,
+        token::{Keyword, Token, TokenType},
+    },
+};
+
+impl Buffer {
+    #[tracing::instrument(skip_all)]
+    pub fn parse_use(&mut self) -> ZResult<()> {
+        self.reset_cursor();
+        while let Some(selected) = self.next() {
+            if !matches!(
+                selected,
+                Either::Right(Token {
+                    ty: Some(TokenType::Keyword(Keyword::Use)),
+                    ..
+                })
+            ) {
+                continue;
+            }
+            let kwd_span = selected.span();
+            debug!(pos = ?kwd_span, "Parsing use");
+            let start = self.cursor;
+            if self.next().is_none() {
+                return Err(ZError::p025().with_span(kwd_span));
+            }
+            let path = self.rest_incl_curr().with_as_buffer(&Self::parse_as_expr)?;
+            let ele = Use {
+                kwd_span,
+                path: path.into(),
+                module: None,
+            };
+            if ele.module_path().is_none() {
+                return Err(ZError::p025().with_span(&ele.path));
+            }
+            let ele = Ast::Use(ele);
+            trace!(?ele);
+            let buffer_window = BufferWindow {
+                slice: vec![Either::Left(ele)],
+                range: start..self.content.len(),
+            };
+            self.splice_buffer(buffer_window);
+        }
+        Ok(())
+    }
+}
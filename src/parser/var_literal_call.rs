@@ -1,12 +1,12 @@
 use std::collections::HashMap;
 
-use itertools::Either;
-use num::BigInt;
+use itertools::{Either, Itertools};
 use tracing::{debug, trace};
 
 use crate::{
-    ast::{Ast, AstData, Call, Ident, Literal, Member},
-    errors::{ToZResult, ZError, ZResult},
+    ast::{Ast, AstData, Call, FString, Ident, Literal, Member},
+    errors::{ZError, ZResult},
+    lexer::{parse_number, parse_text_literal, split_fstring},
     parser::buffer::{Buffer, BufferWindow},
     types::{
         position::GetSpan,
@@ -16,24 +16,6 @@ use crate::{
 };
 
 impl Buffer {
-    fn parse_str_literal(s: &str) -> ZResult<String> {
-        let mut out = String::new();
-        let mut chars = s.chars();
-        while let Some(c) = chars.next() {
-            if c != '\\' {
-                out.push(c);
-                continue;
-            }
-            out.push(match chars.next().z()? {
-                '\\' => '\\',
-                'n' => '\n',
-                't' => '\t',
-                'r' => '\r',
-                n => n,
-            });
-        }
-        Ok(out)
-    }
     fn parse_ident(token: &Token) -> Option<Ident> {
         if token.ty != Some(TokenType::Ident) {
             return None;
@@ -43,6 +25,23 @@ impl Buffer {
             name_span: Some(token.span.to_owned()),
         })
     }
+    fn parse_fstring(token: &Token) -> ZResult<FString> {
+        let parts = split_fstring(&token.value, &token.span.start_pos)?
+            .into_iter()
+            .map(|part| {
+                Ok(match part {
+                    Either::Left(text) => Either::Left(text),
+                    Either::Right((tokens, spec)) => {
+                        Either::Right((Box::new(Self::new(tokens).parse_as_expr()?), spec))
+                    }
+                })
+            })
+            .collect::<ZResult<Vec<_>>>()?;
+        Ok(FString {
+            span: Some(token.span.to_owned()),
+            parts,
+        })
+    }
     #[tracing::instrument(skip_all)]
     pub fn parse_var_literal_call(&mut self) -> ZResult<()> {
         self.reset_cursor();
@@ -73,22 +72,13 @@ impl Buffer {
                     let Some((catcher, _)) = &mut catcher else {
                         return Err(ZError::p022().with_span(dot_span));
                     };
-                    let selected = match self.next_or_err()? {
-                        Either::Left(c) => {
-                            if let Ast::Ident(ident) = c {
-                                ident.to_owned()
-                            } else {
-                                todo!("get item")
-                            }
-                        }
-                        Either::Right(c) => {
-                            if let Some(ident) = Self::parse_ident(&c) {
-                                ident
-                            } else {
-                                todo!("get item")
-                            }
-                        }
-                    };
+                    let next = self.next_or_err()?;
+                    let selected = match &next {
+                        Either::Left(Ast::Ident(ident)) => Some(ident.to_owned()),
+                        Either::Left(_) => None,
+                        Either::Right(c) => Self::parse_ident(c),
+                    }
+                    .ok_or_else(|| ZError::p026().with_span(&next))?;
                     debug!(pos = ?selected.span(), "Parsing ident");
                     *catcher = Ast::Member(Member {
                         ty: access_ty,
@@ -108,6 +98,12 @@ impl Buffer {
                     catcher = Some((ident, self.cursor));
                     trace!(catcher = ?catcher.as_ref().unwrap_or_else(|| unreachable!()).0);
                 }
+                Some(TokenType::LiteralString) if selected.value.starts_with('f') => {
+                    debug!(pos = ?selected.span, "Parsing formatted string");
+                    clear_catcher(self, &mut catcher, false);
+                    catcher = Some((Self::parse_fstring(&selected)?.as_variant(), self.cursor));
+                    trace!(catcher = ?catcher.as_ref().unwrap_or_else(|| unreachable!()).0);
+                }
                 Some(
                     TokenType::LiteralNumber | TokenType::LiteralMisc | TokenType::LiteralString,
                 ) => {
@@ -123,33 +119,10 @@ impl Buffer {
                                     "inf" => Value::F64(f64::INFINITY),
                                     _ => unreachable!("{}", selected.value),
                                 },
-                                Some(TokenType::LiteralNumber) => {
-                                    if selected.value.contains('.') {
-                                        Value::F64(
-                                            selected
-                                                .value
-                                                .parse()
-                                                .unwrap_or_else(|_| unreachable!()),
-                                        )
-                                        // TODO Decimal
-                                    } else if let Ok(val) = selected.value.parse::<i32>() {
-                                        Value::I32(val)
-                                    } else if let Ok(val) = selected.value.parse::<i64>() {
-                                        Value::I64(val)
-                                    } else if let Ok(val) = selected.value.parse::<i128>() {
-                                        Value::I128(val)
-                                    } else if let Ok(val) = selected.value.parse::<u128>() {
-                                        Value::U128(val)
-                                    } else if let Ok(val) = selected.value.parse::<BigInt>() {
-                                        Value::Ibig(val)
-                                    } else {
-                                        unreachable!()
-                                    }
+                                Some(TokenType::LiteralNumber) => parse_number(&selected.value)?,
+                                Some(TokenType::LiteralString) => {
+                                    parse_text_literal(&selected.value)?
                                 }
-                                Some(TokenType::LiteralString) => Value::Str({
-                                    let str = &selected.value[1..selected.value.len() - 1];
-                                    Self::parse_str_literal(str)?
-                                }),
                                 _ty => unreachable!("{_ty:?}"),
                             },
                         }),
@@ -172,10 +145,22 @@ impl Buffer {
                         TokenType::CloseParen,
                         TokenType::Comma,
                     )?;
-                    let args = contents.with_as_buffers(&|f| {
-                        let ele = f.parse_as_expr()?;
-                        Ok(ele)
-                    })?;
+                    let (kwargs, args): (HashMap<_, _>, Vec<_>) = contents
+                        .with_as_buffers(&|f| {
+                            // `name: value` is a named argument
+                            if let [Either::Right(name), Either::Right(colon), ..] = &*f.content {
+                                if name.ty == Some(TokenType::Ident)
+                                    && colon.ty == Some(TokenType::Colon)
+                                {
+                                    let name = name.value.to_owned();
+                                    f.content.drain(..2);
+                                    return Ok(Either::Left((name, f.parse_as_expr()?)));
+                                }
+                            }
+                            Ok(Either::Right(f.parse_as_expr()?))
+                        })?
+                        .into_iter()
+                        .partition_map(|arg| arg);
                     let close_paren_span = self
                         .this()
                         .and_then(|e| e.span())
@@ -184,7 +169,7 @@ impl Buffer {
                         called: catcher.to_owned().into(),
                         paren_spans: Some((open_paren_span, close_paren_span)),
                         args,
-                        kwargs: HashMap::default(),
+                        kwargs,
                     });
                     trace!(?catcher);
                 }
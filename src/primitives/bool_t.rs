@@ -4,8 +4,9 @@ use once_cell::sync::Lazy;
 
 use crate::{
     ast::Ident,
+    errors::ZError,
     primitives::{
-        utils::{comp_opr_default, concat, get_param, type_cast},
+        utils::{comp_opr_default, concat, get_param, type_cast, unary},
         *,
     },
     types::{
@@ -32,9 +33,16 @@ fn bool_t() -> BuiltinType {
     h.insert("_default", Value::Bool(false));
     concat(&mut h, &BOOL_T);
     comp_opr_default::<bool>(&mut h, &BOOL_T);
+    unary(
+        &mut h,
+        "_not",
+        Arc::new(|x: &Vec<Value>| Ok(Value::Bool(!get_param::<bool>(x, 0)?))),
+        &BOOL_T,
+        &BOOL_T,
+    );
 
     let typecast = Arc::new(|x: &Vec<Value>| {
-        Some(match get_param::<Arc<ValueType>>(x, 1)? {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
             p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&BOOL_T_VAL)),
             p if p == *STR_T_VAL => Value::Str(get_param::<bool>(x, 0)?.to_string()),
             p if p == *BOOL_T_VAL => x[0].to_owned(),
@@ -55,7 +63,7 @@ fn bool_t() -> BuiltinType {
             p if p == *F16_T_VAL => typecast_bool_to_num!(F16, x),
             p if p == *F32_T_VAL => typecast_bool_to_num!(F32, x),
             p if p == *F64_T_VAL => typecast_bool_to_num!(F64, x),
-            _ => return None,
+            _ => return Err(ZError::i001(x)),
         })
     });
     type_cast(&mut h, typecast, &BOOL_T);
@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use tracing::trace;
+
+use crate::{errors::ZError, primitives::*, typecast_int, types::value::Value, Type};
+#[allow(clippy::cognitive_complexity, clippy::float_cmp)]
+fn char_t() -> BuiltinType {
+    let mut h = HashMap::new();
+    trace!("Initialising char");
+    h.insert("_default", Value::Char('\0'));
+    concat(&mut h, &CHAR_T);
+    comp_opr_default::<char>(&mut h, &CHAR_T);
+
+    let typecast = Arc::new(|x: &Vec<Value>| {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
+            p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&CHAR_T_VAL)),
+            p if p == *STR_T_VAL => typecast_int!(char => str, x),
+            p if p == *CHAR_T_VAL => x[0].to_owned(),
+            p if p == *U8_T_VAL => typecast_int!(char => U8, x),
+            p if p == *U16_T_VAL => typecast_int!(char => U16, x),
+            p if p == *U32_T_VAL => Value::U32(get_param::<char>(x, 0)?.into()),
+            p if p == *U64_T_VAL => Value::U64(get_param::<char>(x, 0)?.into()),
+            _ => return Err(ZError::i001(x)),
+        })
+    });
+    type_cast(&mut h, typecast, &CHAR_T);
+
+    BuiltinType {
+        name: Some(Ident::new("char")),
+        namespace: h.drain().map(|(k, v)| (k.into(), v)).collect(),
+        fields: HashMap::default(),
+        type_args: vec![],
+    }
+}
+
+pub static CHAR_T: Lazy<Arc<Type>> = Lazy::new(|| Arc::new(char_t().into()));
+pub static CHAR_T_VAL: Lazy<Arc<ValueType>> = Lazy::new(|| Arc::new(char_t().into()));
+
+use std::sync::Arc;
+
+use crate::{
+    ast::Ident,
+    primitives::utils::{comp_opr_default, concat, get_param, type_cast},
+    types::r#type::{BuiltinType, ValueType},
+};
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, NaiveDate, NaiveTime, TimeDelta};
+use once_cell::sync::Lazy;
+use tracing::trace;
+
+use crate::{errors::ZError, primitives::*, types::value::Value, Type};
+
+fn date_part(h: &mut HashMap<&str, Value>, n: &'static str, f: fn(&NaiveDate) -> i32) {
+    unary(
+        h,
+        n,
+        Arc::new(move |x: &Vec<Value>| Ok(Value::I32(f(&get_param(x, 0)?)))),
+        &DATE_T,
+        &I32_T,
+    );
+}
+
+#[allow(clippy::cognitive_complexity, clippy::float_cmp)]
+fn date_t() -> BuiltinType {
+    let mut h = HashMap::new();
+    trace!("Initialising date");
+    h.insert("_default", Value::Date(NaiveDate::default()));
+    concat(&mut h, &DATE_T);
+    comp_opr_default::<NaiveDate>(&mut h, &DATE_T);
+    fmt_strftime_default::<NaiveDate>(&mut h, &DATE_T, |v| (Some(*v), None, None));
+    date_part(&mut h, "year", NaiveDate::year);
+    date_part(&mut h, "month", |v| v.month().cast_signed());
+    date_part(&mut h, "day", |v| v.day().cast_signed());
+    date_part(&mut h, "ordinal", |v| v.ordinal().cast_signed());
+    // from 1 for Monday to 7 for Sunday, as in ISO 8601
+    date_part(&mut h, "weekday", |v| {
+        v.weekday().number_from_monday().cast_signed()
+    });
+    let shifts: [(_, fn(NaiveDate, TimeDelta) -> Option<NaiveDate>); 2] = [
+        ("_add", NaiveDate::checked_add_signed),
+        ("_sub", NaiveDate::checked_sub_signed),
+    ];
+    for (n, f) in shifts {
+        binary(
+            &mut h,
+            n,
+            Arc::new(move |x: &Vec<Value>| {
+                f(get_param(x, 0)?, get_param(x, 1)?)
+                    .map(Value::Date)
+                    .ok_or_else(|| ZError::i002(n, x))
+            }),
+            &DATE_T,
+            &DURATION_T,
+            &DATE_T,
+        );
+    }
+    binary(
+        &mut h,
+        "since",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(Value::Duration(
+                get_param::<NaiveDate>(x, 0)?.signed_duration_since(get_param(x, 1)?),
+            ))
+        }),
+        &DATE_T,
+        &DATE_T,
+        &DURATION_T,
+    );
+    // in UTC, which `to_offset` can change
+    binary(
+        &mut h,
+        "at",
+        Arc::new(|x: &Vec<Value>| {
+            let time = get_param::<NaiveTime>(x, 1)?;
+            Ok(Value::DateTime(
+                get_param::<NaiveDate>(x, 0)?
+                    .and_time(time)
+                    .and_utc()
+                    .fixed_offset(),
+            ))
+        }),
+        &DATE_T,
+        &TIME_T,
+        &DATETIME_T,
+    );
+
+    let typecast = Arc::new(|x: &Vec<Value>| {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
+            p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&DATE_T_VAL)),
+            p if p == *STR_T_VAL => Value::Str(x[0].to_string()),
+            p if p == *DATE_T_VAL => x[0].to_owned(),
+            p if p == *DATETIME_T_VAL => Value::DateTime(
+                get_param::<NaiveDate>(x, 0)?
+                    .and_time(NaiveTime::MIN)
+                    .and_utc()
+                    .fixed_offset(),
+            ),
+            _ => return Err(ZError::i001(x)),
+        })
+    });
+    type_cast(&mut h, typecast, &DATE_T);
+
+    BuiltinType {
+        name: Some(Ident::new("date")),
+        namespace: h.drain().map(|(k, v)| (k.into(), v)).collect(),
+        fields: HashMap::default(),
+        type_args: vec![],
+    }
+}
+
+pub static DATE_T: Lazy<Arc<Type>> = Lazy::new(|| Arc::new(date_t().into()));
+pub static DATE_T_VAL: Lazy<Arc<ValueType>> = Lazy::new(|| Arc::new(date_t().into()));
+
+use std::sync::Arc;
+
+use crate::{
+    ast::Ident,
+    primitives::utils::{
+        binary, comp_opr_default, concat, fmt_strftime_default, get_param, type_cast, unary,
+    },
+    types::r#type::{BuiltinType, ValueType},
+};
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, FixedOffset, TimeDelta, Timelike};
+use once_cell::sync::Lazy;
+use tracing::trace;
+
+use crate::{errors::ZError, primitives::*, types::value::Value, Type};
+
+/// A part of the date and time, as it is in the datetime's offset
+fn datetime_part(
+    h: &mut HashMap<&str, Value>,
+    n: &'static str,
+    f: fn(&DateTime<FixedOffset>) -> i32,
+) {
+    unary(
+        h,
+        n,
+        Arc::new(move |x: &Vec<Value>| Ok(Value::I32(f(&get_param(x, 0)?)))),
+        &DATETIME_T,
+        &I32_T,
+    );
+}
+
+#[allow(clippy::cognitive_complexity, clippy::float_cmp)]
+fn datetime_t() -> BuiltinType {
+    let mut h = HashMap::new();
+    trace!("Initialising datetime");
+    h.insert(
+        "_default",
+        Value::DateTime(DateTime::UNIX_EPOCH.fixed_offset()),
+    );
+    concat(&mut h, &DATETIME_T);
+    comp_opr_default::<DateTime<FixedOffset>>(&mut h, &DATETIME_T);
+    fmt_strftime_default::<DateTime<FixedOffset>>(&mut h, &DATETIME_T, |v| {
+        (Some(v.date_naive()), Some(v.time()), Some(*v.offset()))
+    });
+    datetime_part(&mut h, "year", Datelike::year);
+    datetime_part(&mut h, "month", |v| v.month().cast_signed());
+    datetime_part(&mut h, "day", |v| v.day().cast_signed());
+    datetime_part(&mut h, "ordinal", |v| v.ordinal().cast_signed());
+    datetime_part(&mut h, "weekday", |v| {
+        v.weekday().number_from_monday().cast_signed()
+    });
+    datetime_part(&mut h, "hour", |v| v.hour().cast_signed());
+    datetime_part(&mut h, "minute", |v| v.minute().cast_signed());
+    datetime_part(&mut h, "second", |v| v.second().cast_signed());
+    datetime_part(&mut h, "nanosecond", |v| v.nanosecond().cast_signed());
+    // in seconds east of UTC
+    datetime_part(&mut h, "offset", |v| v.offset().local_minus_utc());
+    unary(
+        &mut h,
+        "date",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(Value::Date(
+                get_param::<DateTime<FixedOffset>>(x, 0)?.date_naive(),
+            ))
+        }),
+        &DATETIME_T,
+        &DATE_T,
+    );
+    unary(
+        &mut h,
+        "time",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(Value::Time(
+                get_param::<DateTime<FixedOffset>>(x, 0)?.time(),
+            ))
+        }),
+        &DATETIME_T,
+        &TIME_T,
+    );
+    // the same instant, at an offset in seconds east of UTC
+    binary(
+        &mut h,
+        "to_offset",
+        Arc::new(|x: &Vec<Value>| {
+            let dt = get_param::<DateTime<FixedOffset>>(x, 0)?;
+            FixedOffset::east_opt(get_param(x, 1)?)
+                .map(|offset| Value::DateTime(dt.with_timezone(&offset)))
+                .ok_or_else(|| ZError::i007("to_offset", x))
+        }),
+        &DATETIME_T,
+        &I32_T,
+        &DATETIME_T,
+    );
+    unary(
+        &mut h,
+        "to_utc",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(Value::DateTime(
+                get_param::<DateTime<FixedOffset>>(x, 0)?
+                    .to_utc()
+                    .fixed_offset(),
+            ))
+        }),
+        &DATETIME_T,
+        &DATETIME_T,
+    );
+    // in seconds since the Unix epoch
+    unary(
+        &mut h,
+        "timestamp",
+        Arc::new(|x: &Vec<Value>| {
+            let dt = get_param::<DateTime<FixedOffset>>(x, 0)?;
+            Ok(Value::F64(
+                (dt - DateTime::UNIX_EPOCH.fixed_offset()).as_seconds_f64(),
+            ))
+        }),
+        &DATETIME_T,
+        &F64_T,
+    );
+    let shifts: [(
+        _,
+        fn(DateTime<FixedOffset>, TimeDelta) -> Option<DateTime<FixedOffset>>,
+    ); 2] = [
+        ("_add", DateTime::checked_add_signed),
+        ("_sub", DateTime::checked_sub_signed),
+    ];
+    for (n, f) in shifts {
+        binary(
+            &mut h,
+            n,
+            Arc::new(move |x: &Vec<Value>| {
+                f(get_param(x, 0)?, get_param(x, 1)?)
+                    .map(Value::DateTime)
+                    .ok_or_else(|| ZError::i002(n, x))
+            }),
+            &DATETIME_T,
+            &DURATION_T,
+            &DATETIME_T,
+        );
+    }
+    binary(
+        &mut h,
+        "since",
+        Arc::new(|x: &Vec<Value>| {
+            let (a, b) = (
+                get_param::<DateTime<FixedOffset>>(x, 0)?,
+                get_param::<DateTime<FixedOffset>>(x, 1)?,
+            );
+            Ok(Value::Duration(a.signed_duration_since(b)))
+        }),
+        &DATETIME_T,
+        &DATETIME_T,
+        &DURATION_T,
+    );
+
+    let typecast = Arc::new(|x: &Vec<Value>| {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
+            p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&DATETIME_T_VAL)),
+            p if p == *STR_T_VAL => Value::Str(x[0].to_string()),
+            p if p == *DATETIME_T_VAL => x[0].to_owned(),
+            p if p == *DATE_T_VAL => {
+                Value::Date(get_param::<DateTime<FixedOffset>>(x, 0)?.date_naive())
+            }
+            p if p == *TIME_T_VAL => Value::Time(get_param::<DateTime<FixedOffset>>(x, 0)?.time()),
+            _ => return Err(ZError::i001(x)),
+        })
+    });
+    type_cast(&mut h, typecast, &DATETIME_T);
+
+    BuiltinType {
+        name: Some(Ident::new("datetime")),
+        namespace: h.drain().map(|(k, v)| (k.into(), v)).collect(),
+        fields: HashMap::default(),
+        type_args: vec![],
+    }
+}
+
+pub static DATETIME_T: Lazy<Arc<Type>> = Lazy::new(|| Arc::new(datetime_t().into()));
+pub static DATETIME_T_VAL: Lazy<Arc<ValueType>> = Lazy::new(|| Arc::new(datetime_t().into()));
+
+use std::sync::Arc;
+
+use crate::{
+    ast::Ident,
+    primitives::utils::{
+        binary, comp_opr_default, concat, fmt_strftime_default, get_param, type_cast, unary,
+    },
+    types::r#type::{BuiltinType, ValueType},
+};
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use chrono::TimeDelta;
+use once_cell::sync::Lazy;
+use tracing::trace;
+
+use crate::{errors::ZError, primitives::*, types::value::Value, Type};
+
+fn checked_opr_duration(
+    h: &mut HashMap<&str, Value>,
+    n: &'static str,
+    f: fn(&TimeDelta, &TimeDelta) -> Option<TimeDelta>,
+) {
+    binary(
+        h,
+        n,
+        Arc::new(move |x: &Vec<Value>| {
+            f(&get_param(x, 0)?, &get_param(x, 1)?)
+                .map(Value::Duration)
+                .ok_or_else(|| ZError::i002(n, x))
+        }),
+        &DURATION_T,
+        &DURATION_T,
+        &DURATION_T,
+    );
+}
+
+#[allow(clippy::cognitive_complexity, clippy::float_cmp)]
+fn duration_t() -> BuiltinType {
+    let mut h = HashMap::new();
+    trace!("Initialising duration");
+    h.insert("_default", Value::Duration(TimeDelta::zero()));
+    concat(&mut h, &DURATION_T);
+    comp_opr_default::<TimeDelta>(&mut h, &DURATION_T);
+    checked_opr_duration(&mut h, "_add", TimeDelta::checked_add);
+    checked_opr_duration(&mut h, "_sub", TimeDelta::checked_sub);
+    binary(
+        &mut h,
+        "_mul",
+        Arc::new(|x: &Vec<Value>| {
+            get_param::<TimeDelta>(x, 0)?
+                .checked_mul(get_param(x, 1)?)
+                .map(Value::Duration)
+                .ok_or_else(|| ZError::i002("_mul", x))
+        }),
+        &DURATION_T,
+        &I32_T,
+        &DURATION_T,
+    );
+    binary(
+        &mut h,
+        "_div",
+        Arc::new(|x: &Vec<Value>| {
+            get_param::<TimeDelta>(x, 0)?
+                .checked_div(get_param(x, 1)?)
+                .map(Value::Duration)
+                .ok_or_else(|| ZError::i003(x))
+        }),
+        &DURATION_T,
+        &I32_T,
+        &DURATION_T,
+    );
+    unary(
+        &mut h,
+        "_un_add",
+        Arc::new(|x: &Vec<Value>| Ok(x[0].to_owned())),
+        &DURATION_T,
+        &DURATION_T,
+    );
+    unary(
+        &mut h,
+        "_un_sub",
+        // durations range from -`i64::MAX` to `i64::MAX` milliseconds, so this can't overflow
+        Arc::new(|x: &Vec<Value>| Ok(Value::Duration(-get_param::<TimeDelta>(x, 0)?))),
+        &DURATION_T,
+        &DURATION_T,
+    );
+    unary(
+        &mut h,
+        "abs",
+        Arc::new(|x: &Vec<Value>| Ok(Value::Duration(get_param::<TimeDelta>(x, 0)?.abs()))),
+        &DURATION_T,
+        &DURATION_T,
+    );
+    unary(
+        &mut h,
+        "as_seconds",
+        Arc::new(|x: &Vec<Value>| Ok(Value::F64(get_param::<TimeDelta>(x, 0)?.as_seconds_f64()))),
+        &DURATION_T,
+        &F64_T,
+    );
+    unary(
+        &mut h,
+        "as_millis",
+        Arc::new(|x: &Vec<Value>| Ok(Value::I64(get_param::<TimeDelta>(x, 0)?.num_milliseconds()))),
+        &DURATION_T,
+        &I64_T,
+    );
+    unary(
+        &mut h,
+        "as_days",
+        Arc::new(|x: &Vec<Value>| Ok(Value::I64(get_param::<TimeDelta>(x, 0)?.num_days()))),
+        &DURATION_T,
+        &I64_T,
+    );
+
+    let typecast = Arc::new(|x: &Vec<Value>| {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
+            p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&DURATION_T_VAL)),
+            p if p == *STR_T_VAL => Value::Str(x[0].to_string()),
+            p if p == *DURATION_T_VAL => x[0].to_owned(),
+            _ => return Err(ZError::i001(x)),
+        })
+    });
+    type_cast(&mut h, typecast, &DURATION_T);
+
+    BuiltinType {
+        name: Some(Ident::new("duration")),
+        namespace: h.drain().map(|(k, v)| (k.into(), v)).collect(),
+        fields: HashMap::default(),
+        type_args: vec![],
+    }
+}
+
+pub static DURATION_T: Lazy<Arc<Type>> = Lazy::new(|| Arc::new(duration_t().into()));
+pub static DURATION_T_VAL: Lazy<Arc<ValueType>> = Lazy::new(|| Arc::new(duration_t().into()));
+
+use std::sync::Arc;
+
+use crate::{
+    ast::Ident,
+    primitives::utils::{binary, comp_opr_default, concat, get_param, type_cast, unary},
+    types::r#type::{BuiltinType, ValueType},
+};
@@ -10,8 +10,12 @@ use tracing::trace;
 
 use crate::{
     ast::Ident,
+    errors::ZError,
     primitives::{
-        utils::{arith_opr_float_default, comp_opr_default, concat, get_param, type_cast, unary},
+        utils::{
+            arith_opr_float_default, comp_opr_default, concat, fmt_float_default, get_param,
+            type_cast, unary,
+        },
         *,
     },
     typecast_float,
@@ -23,7 +27,12 @@ use crate::{
 };
 macro_rules! typecast_f16_to_int {
     ($vo:ident $f:ident, $x:ident) => {
-        Value::$vo(get_param::<f16>($x, 0)?.to_f64().$f()?)
+        Value::$vo(
+            get_param::<f16>($x, 0)?
+                .to_f64()
+                .$f()
+                .ok_or_else(|| ZError::i001($x))?,
+        )
     };
 }
 
@@ -36,14 +45,14 @@ fn f16_t() -> BuiltinType {
     unary(
         &mut h,
         "_un_add",
-        Arc::new(|x: &Vec<Value>| Some(x[0].to_owned())),
+        Arc::new(|x: &Vec<Value>| Ok(x[0].to_owned())),
         &F16_T,
         &F16_T,
     );
     unary(
         &mut h,
         "_un_sub",
-        Arc::new(|x: &Vec<Value>| Some(get_param::<f16>(x, 0)?.neg().into())),
+        Arc::new(|x: &Vec<Value>| Ok(get_param::<f16>(x, 0)?.neg().into())),
         &F16_T,
         &F16_T,
     );
@@ -51,20 +60,19 @@ fn f16_t() -> BuiltinType {
         &mut h,
         "_un_sub",
         Arc::new(|x: &Vec<Value>| {
-            Some(
-                (get_param::<f16>(x, 0)?.eq(&f16::ZERO)
-                    || get_param::<f16>(x, 0)?.eq(&f16::NEG_ZERO))
-                .into(),
-            )
+            Ok((get_param::<f16>(x, 0)?.eq(&f16::ZERO)
+                || get_param::<f16>(x, 0)?.eq(&f16::NEG_ZERO))
+            .into())
         }),
         &F16_T,
         &BOOL_T,
     );
     arith_opr_float_default::<f16>(&mut h, &F16_T);
     comp_opr_default::<f16>(&mut h, &F16_T);
+    fmt_float_default::<f16>(&mut h, &F16_T);
 
     let typecast = Arc::new(|x: &Vec<Value>| {
-        Some(match get_param::<Arc<ValueType>>(x, 1)? {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
             p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&F16_T_VAL)),
             p if p == *STR_T_VAL => typecast_float!(f16 => str, x),
             p if p == *BOOL_T_VAL => Value::Bool(
@@ -87,7 +95,7 @@ fn f16_t() -> BuiltinType {
             p if p == *F16_T_VAL => x[0].to_owned(),
             p if p == *F32_T_VAL => Value::F32(get_param::<f16>(x, 0)?.to_f32()),
             p if p == *F64_T_VAL => Value::F64(get_param::<f16>(x, 0)?.to_f64()),
-            _ => return None,
+            _ => return Err(ZError::i001(x)),
         })
     });
     type_cast(&mut h, typecast, &F16_T);
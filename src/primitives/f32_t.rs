@@ -10,10 +10,11 @@ use tracing::trace;
 
 use crate::{
     ast::Ident,
+    errors::ZError,
     primitives::{
         utils::{
-            arith_opr_float_default, comp_opr_default, concat, get_param, type_cast,
-            unary_float_default,
+            arith_opr_float_default, comp_opr_default, concat, fmt_float_default, get_param,
+            type_cast, unary_float_default,
         },
         *,
     },
@@ -33,9 +34,10 @@ fn f32_t() -> BuiltinType {
     unary_float_default::<f32>(&mut h, &F32_T);
     arith_opr_float_default::<f32>(&mut h, &F32_T);
     comp_opr_default::<f32>(&mut h, &F32_T);
+    fmt_float_default::<f32>(&mut h, &F32_T);
 
     let typecast = Arc::new(|x: &Vec<Value>| {
-        Some(match get_param::<Arc<ValueType>>(x, 1)? {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
             p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&F32_T_VAL)),
             p if p == *STR_T_VAL => typecast_float!(f32 => str, x),
             p if p == *BOOL_T_VAL => typecast_float!(f32 => bool, x),
@@ -56,7 +58,7 @@ fn f32_t() -> BuiltinType {
             p if p == *F16_T_VAL => typecast_float!(f32 => f16, x),
             p if p == *F32_T_VAL => x[0].to_owned(),
             p if p == *F64_T_VAL => typecast_float!(f32 => F64 to_f64, x),
-            _ => return None,
+            _ => return Err(ZError::i001(x)),
         })
     });
     type_cast(&mut h, typecast, &F32_T);
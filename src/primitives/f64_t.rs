@@ -10,10 +10,11 @@ use tracing::trace;
 
 use crate::{
     ast::Ident,
+    errors::ZError,
     primitives::{
         utils::{
-            arith_opr_float_default, comp_opr_default, concat, get_param, type_cast,
-            unary_float_default,
+            arith_opr_float_default, comp_opr_default, concat, fmt_float_default, get_param,
+            type_cast, unary_float_default,
         },
         *,
     },
@@ -34,9 +35,10 @@ fn f64_t() -> BuiltinType {
     unary_float_default::<f64>(&mut h, &F64_T);
     arith_opr_float_default::<f64>(&mut h, &F64_T);
     comp_opr_default::<f64>(&mut h, &F64_T);
+    fmt_float_default::<f64>(&mut h, &F64_T);
 
     let typecast = Arc::new(|x: &Vec<Value>| {
-        Some(match get_param::<Arc<ValueType>>(x, 1)? {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
             p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&F64_T_VAL)),
             p if p == *STR_T_VAL => typecast_float!(f64 => str, x),
             p if p == *BOOL_T_VAL => typecast_float!(f64 => bool, x),
@@ -57,7 +59,7 @@ fn f64_t() -> BuiltinType {
             p if p == *F16_T_VAL => typecast_float!(f64 => f16, x),
             p if p == *F32_T_VAL => typecast_float!(f64 => F32 to_f32, x),
             p if p == *F64_T_VAL => x[0].to_owned(),
-            _ => return None,
+            _ => return Err(ZError::i001(x)),
         })
     });
     type_cast(&mut h, typecast, &F64_T);
@@ -0,0 +1,183 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
+
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use tracing::trace;
+
+use crate::{errors::ZError, primitives::*, types::value::Value, Type};
+
+/// A map of any values to values, which keeps its keys in the order they were first set in. Keys
+/// are compared with `==` since values can't be hashed, and maps with the same entries are equal
+/// whatever their order.
+#[derive(Clone, Debug, Default)]
+pub struct Hmap(pub Vec<(Value, Value)>);
+impl PartialEq for Hmap {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len() && self.0.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+impl Display for Hmap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{{}}}",
+            self.0.iter().map(|(k, v)| format!("{k}: {v}")).join(", ")
+        )
+    }
+}
+impl Hmap {
+    #[must_use]
+    pub fn get(&self, k: &Value) -> Option<&Value> {
+        self.0.iter().find(|(k2, _)| k2 == k).map(|(_, v)| v)
+    }
+    pub fn set(&mut self, k: Value, v: Value) {
+        if let Some((_, slot)) = self.0.iter_mut().find(|(k2, _)| *k2 == k) {
+            *slot = v;
+        } else {
+            self.0.push((k, v));
+        }
+    }
+    pub fn remove(&mut self, k: &Value) -> Option<Value> {
+        let i = self.0.iter().position(|(k2, _)| k2 == k)?;
+        Some(self.0.remove(i).1)
+    }
+}
+
+fn comp_opr_hmap<'a>(h: &mut HashMap<&'a str, Value>, n: &'a str, eq: bool) {
+    binary(
+        h,
+        n,
+        Arc::new(move |x: &Vec<Value>| {
+            Ok(((get_param::<Hmap>(x, 0)? == get_param(x, 1)?) == eq).into())
+        }),
+        &HMAP_T,
+        &HMAP_T,
+        &BOOL_T,
+    );
+}
+
+/// Registers a method giving a vector of `f` of each entry, in order
+fn hmap_entries(h: &mut HashMap<&str, Value>, n: &'static str, f: fn((Value, Value)) -> Value) {
+    unary(
+        h,
+        n,
+        Arc::new(move |x: &Vec<Value>| {
+            Ok(Value::Vec(
+                get_param::<Hmap>(x, 0)?.0.into_iter().map(f).collect(),
+            ))
+        }),
+        &HMAP_T,
+        &VEC_T,
+    );
+}
+
+#[allow(clippy::cognitive_complexity, clippy::float_cmp)]
+fn hmap_t() -> BuiltinType {
+    let mut h = HashMap::new();
+    trace!("Initialising hmap");
+    h.insert("_default", Value::Hmap(Hmap::default()));
+    concat(&mut h, &HMAP_T);
+    comp_opr_hmap(&mut h, "_eq", true);
+    comp_opr_hmap(&mut h, "_ne", false);
+    unary(
+        &mut h,
+        "len",
+        Arc::new(|x: &Vec<Value>| {
+            i32::try_from(get_param::<Hmap>(x, 0)?.0.len())
+                .map(Value::I32)
+                .ok()
+                .ok_or_else(|| ZError::i002("len", x))
+        }),
+        &HMAP_T,
+        &I32_T,
+    );
+    unary(
+        &mut h,
+        "is_empty",
+        Arc::new(|x: &Vec<Value>| Ok(get_param::<Hmap>(x, 0)?.0.is_empty().into())),
+        &HMAP_T,
+        &BOOL_T,
+    );
+    binary(
+        &mut h,
+        "get",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(Value::Opt(
+                get_param::<Hmap>(x, 0)?
+                    .get(&x[1])
+                    .map(|v| Box::new(v.to_owned())),
+            ))
+        }),
+        &HMAP_T,
+        &ANY_T,
+        &OPT_T,
+    );
+    binary(
+        &mut h,
+        "contains",
+        Arc::new(|x: &Vec<Value>| Ok(get_param::<Hmap>(x, 0)?.get(&x[1]).is_some().into())),
+        &HMAP_T,
+        &ANY_T,
+        &BOOL_T,
+    );
+    // maps are values like vectors, so these give a changed copy
+    nary(
+        &mut h,
+        "set",
+        Arc::new(|x: &Vec<Value>| {
+            let mut m = get_param::<Hmap>(x, 0)?;
+            m.set(x[1].to_owned(), x[2].to_owned());
+            Ok(Value::Hmap(m))
+        }),
+        &[&HMAP_T, &ANY_T, &ANY_T],
+        &HMAP_T,
+    );
+    binary(
+        &mut h,
+        "remove",
+        Arc::new(|x: &Vec<Value>| {
+            let mut m = get_param::<Hmap>(x, 0)?;
+            m.remove(&x[1]);
+            Ok(Value::Hmap(m))
+        }),
+        &HMAP_T,
+        &ANY_T,
+        &HMAP_T,
+    );
+    hmap_entries(&mut h, "keys", |(k, _)| k);
+    hmap_entries(&mut h, "values", |(_, v)| v);
+    // pairs are vectors of two, like `zip` gives
+    hmap_entries(&mut h, "items", |(k, v)| Value::Vec(vec![k, v]));
+
+    let typecast = Arc::new(|x: &Vec<Value>| {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
+            p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&HMAP_T_VAL)),
+            p if p == *STR_T_VAL => Value::Str(x[0].to_string()),
+            p if p == *BOOL_T_VAL => Value::Bool(!get_param::<Hmap>(x, 0)?.0.is_empty()),
+            p if p == *HMAP_T_VAL => x[0].to_owned(),
+            _ => return Err(ZError::i001(x)),
+        })
+    });
+    type_cast(&mut h, typecast, &HMAP_T);
+
+    BuiltinType {
+        name: Some(Ident::new("hmap")),
+        namespace: h.drain().map(|(k, v)| (k.into(), v)).collect(),
+        fields: HashMap::default(),
+        type_args: vec![],
+    }
+}
+
+pub static HMAP_T: Lazy<Arc<Type>> = Lazy::new(|| Arc::new(hmap_t().into()));
+pub static HMAP_T_VAL: Lazy<Arc<ValueType>> = Lazy::new(|| Arc::new(hmap_t().into()));
+
+use std::sync::Arc;
+
+use crate::{
+    ast::Ident,
+    primitives::utils::{binary, concat, get_param, nary, type_cast, unary},
+    types::r#type::{BuiltinType, ValueType},
+};
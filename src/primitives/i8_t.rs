@@ -6,9 +6,11 @@ use tracing::trace;
 
 use crate::{
     ast::Ident,
+    errors::ZError,
     primitives::{
         utils::{
-            arith_opr_default, comp_opr_default, concat, get_param, type_cast, unary_signed_default,
+            arith_opr_default, bit_opr_default, comp_opr_default, concat, fmt_int_default,
+            get_param, type_cast, unary_signed_default,
         },
         *,
     },
@@ -29,9 +31,11 @@ fn i8_t() -> BuiltinType {
     unary_signed_default::<i8>(&mut h, &I8_T);
     arith_opr_default::<i8>(&mut h, &I8_T);
     comp_opr_default::<i8>(&mut h, &I8_T);
+    fmt_int_default::<i8>(&mut h, &I8_T);
+    bit_opr_default::<i8>(&mut h, &I8_T);
 
     let typecast = Arc::new(|x: &Vec<Value>| {
-        Some(match get_param::<Arc<ValueType>>(x, 1)? {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
             p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&I8_T_VAL)),
             p if p == *STR_T_VAL => typecast_int!(i8 => str, x),
             p if p == *BOOL_T_VAL => typecast_int!(i8 => bool, x),
@@ -52,7 +56,7 @@ fn i8_t() -> BuiltinType {
             p if p == *F16_T_VAL => typecast_int!(i8 => f16, x),
             p if p == *F32_T_VAL => typecast_int!(i8 => f32, x),
             p if p == *F64_T_VAL => typecast_int!(i8 => f64, x),
-            _ => return None,
+            _ => return Err(ZError::i001(x)),
         })
     });
     type_cast(&mut h, typecast, &I8_T);
@@ -1,11 +1,14 @@
-use std::{collections::HashMap, ops::Neg};
+use std::{
+    collections::HashMap,
+    ops::{Neg, Not},
+};
 
 use half::f16;
 use num_traits::{ToPrimitive, Zero};
 use once_cell::sync::Lazy;
 use tracing::trace;
 
-use crate::{primitives::*, typecast_int, types::value::Value, Type};
+use crate::{errors::ZError, primitives::*, typecast_int, types::value::Value, Type};
 
 #[allow(clippy::cognitive_complexity, clippy::float_cmp)]
 fn ibig_t() -> BuiltinType {
@@ -16,30 +19,38 @@ fn ibig_t() -> BuiltinType {
     unary(
         &mut h,
         "_un_add",
-        Arc::new(|x: &Vec<Value>| Some(x[0].to_owned())),
+        Arc::new(|x: &Vec<Value>| Ok(x[0].to_owned())),
         &IBIG_T,
         &IBIG_T,
     );
     unary(
         &mut h,
         "_un_sub",
-        Arc::new(|x: &Vec<Value>| Some(get_param::<BigInt>(x, 0)?.neg().into())),
+        Arc::new(|x: &Vec<Value>| Ok(get_param::<BigInt>(x, 0)?.neg().into())),
         &IBIG_T,
         &IBIG_T,
     );
     unary(
         &mut h,
         "_not",
-        Arc::new(|x: &Vec<Value>| Some(get_param::<BigInt>(x, 0)?.is_zero().into())),
+        Arc::new(|x: &Vec<Value>| Ok(get_param::<BigInt>(x, 0)?.is_zero().into())),
         &IBIG_T,
         &BOOL_T,
     );
     arith_opr_big_default::<BigInt>(&mut h, &IBIG_T);
-    arith_opr::<BigInt>(&mut h, "_rem", &std::ops::Rem::rem, &IBIG_T);
     comp_opr_default::<BigInt>(&mut h, &IBIG_T);
+    fmt_int_default::<BigInt>(&mut h, &IBIG_T);
+    bit_opr_big_default::<BigInt>(&mut h, &IBIG_T);
+    unary(
+        &mut h,
+        "_compl",
+        Arc::new(|x: &Vec<Value>| Ok(get_param::<BigInt>(x, 0)?.not().into())),
+        &IBIG_T,
+        &IBIG_T,
+    );
 
     let typecast = Arc::new(|x: &Vec<Value>| {
-        Some(match get_param::<Arc<ValueType>>(x, 1)? {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
             p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&IBIG_T_VAL)),
             p if p == *STR_T_VAL => typecast_int!(BigInt => str, x),
             p if p == *BOOL_T_VAL => Value::Bool(get_param::<BigInt>(x, 0)? == 0.into()),
@@ -60,7 +71,7 @@ fn ibig_t() -> BuiltinType {
             p if p == *F16_T_VAL => typecast_int!(big BigInt => f16, x),
             p if p == *F32_T_VAL => typecast_int!(big BigInt => f32, x),
             p if p == *F64_T_VAL => typecast_int!(big BigInt => f64, x),
-            _ => return None,
+            _ => return Err(ZError::i001(x)),
         })
     });
     type_cast(&mut h, typecast, &IBIG_T);
@@ -83,7 +94,8 @@ use num::BigInt;
 use crate::{
     ast::Ident,
     primitives::utils::{
-        arith_opr, arith_opr_big_default, comp_opr_default, concat, get_param, type_cast, unary,
+        arith_opr_big_default, bit_opr_big_default, comp_opr_default, concat, fmt_int_default,
+        get_param, type_cast, unary,
     },
     types::r#type::{BuiltinType, ValueType},
 };
@@ -0,0 +1,48 @@
+use std::{collections::HashMap, sync::Arc};
+
+use smol_str::SmolStr;
+use tracing::trace;
+
+use crate::{
+    errors::ZError,
+    primitives::{
+        utils::{binary, get_param, type_cast},
+        ANY_T, BOOL_T, STR_T_VAL, TYPE_T_VAL,
+    },
+    types::{r#type::ValueType, value::Value},
+};
+
+/// The builtins in the namespace of every class and struct, unless the class defines them itself
+#[must_use]
+pub fn instance_namespace() -> HashMap<SmolStr, Value> {
+    let mut h = HashMap::new();
+    trace!("Initialising class instance builtins");
+    binary(
+        &mut h,
+        "_eq",
+        Arc::new(|x: &Vec<Value>| Ok(Value::Bool(x[0] == x[1]))),
+        &ANY_T,
+        &ANY_T,
+        &BOOL_T,
+    );
+    binary(
+        &mut h,
+        "_ne",
+        Arc::new(|x: &Vec<Value>| Ok(Value::Bool(x[0] != x[1]))),
+        &ANY_T,
+        &ANY_T,
+        &BOOL_T,
+    );
+
+    let typecast = Arc::new(|x: &Vec<Value>| {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
+            p if p == *TYPE_T_VAL => Value::Type(x[0].value_ty()),
+            p if p == *STR_T_VAL => Value::Str(x[0].to_string()),
+            p if p == x[0].value_ty() => x[0].to_owned(),
+            _ => return Err(ZError::i001(x)),
+        })
+    });
+    type_cast(&mut h, typecast, &ANY_T);
+
+    h.drain().map(|(k, v)| (k.into(), v)).collect()
+}
@@ -4,7 +4,7 @@ use half::f16;
 use once_cell::sync::Lazy;
 use tracing::trace;
 
-use crate::{primitives::*, typecast_int, types::value::Value, Type};
+use crate::{errors::ZError, primitives::*, typecast_int, types::value::Value, Type};
 
 #[allow(clippy::cognitive_complexity, clippy::float_cmp)]
 fn isize_t() -> BuiltinType {
@@ -15,9 +15,11 @@ fn isize_t() -> BuiltinType {
     unary_signed_default::<isize>(&mut h, &ISIZE_T);
     arith_opr_default::<isize>(&mut h, &ISIZE_T);
     comp_opr_default::<isize>(&mut h, &ISIZE_T);
+    fmt_int_default::<isize>(&mut h, &ISIZE_T);
+    bit_opr_default::<isize>(&mut h, &ISIZE_T);
 
     let typecast = Arc::new(|x: &Vec<Value>| {
-        Some(match get_param::<Arc<ValueType>>(x, 1)? {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
             p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&ISIZE_T_VAL)),
             p if p == *STR_T_VAL => typecast_int!(isize => str, x),
             p if p == *BOOL_T_VAL => typecast_int!(isize => bool, x),
@@ -38,7 +40,7 @@ fn isize_t() -> BuiltinType {
             p if p == *F16_T_VAL => typecast_int!(isize => f16, x),
             p if p == *F32_T_VAL => typecast_int!(isize => f32, x),
             p if p == *F64_T_VAL => typecast_int!(isize => f64, x),
-            _ => return None,
+            _ => return Err(ZError::i001(x)),
         })
     });
     type_cast(&mut h, typecast, &ISIZE_T);
@@ -59,7 +61,8 @@ use std::sync::Arc;
 use crate::{
     ast::Ident,
     primitives::utils::{
-        arith_opr_default, comp_opr_default, concat, get_param, type_cast, unary_signed_default,
+        arith_opr_default, bit_opr_default, comp_opr_default, concat, fmt_int_default, get_param,
+        type_cast, unary_signed_default,
     },
     types::r#type::{BuiltinType, ValueType},
 };
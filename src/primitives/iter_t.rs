@@ -0,0 +1,277 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
+
+use once_cell::sync::Lazy;
+use tracing::trace;
+
+use crate::{
+    errors::{ZError, ZResult},
+    primitives::*,
+    types::{
+        sym_table::InterpretSymTable,
+        value::{Proc, Value},
+    },
+    Type,
+};
+
+/// Where the values of an `iter` come from
+#[derive(Clone, PartialEq)]
+pub enum IterSource {
+    Vec(Vec<Value>),
+    /// `start`, `start + 1`, ... up to but not including `end`, or up to `i32::MAX` if there's none
+    Range {
+        start: i32,
+        end: Option<i32>,
+    },
+}
+
+/// A step that an `iter` applies to its values as they're taken, not when it's added
+#[derive(Clone, PartialEq)]
+pub enum IterStage {
+    Map(Proc),
+    Filter(Proc),
+    TakeWhile(Proc),
+    SkipWhile(Proc),
+    Take(usize),
+    Skip(usize),
+    Enumerate,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Iter {
+    pub source: IterSource,
+    pub stages: Vec<IterStage>,
+}
+impl Display for Iter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "iter@{}", self.stages.len())
+    }
+}
+
+impl Iter {
+    #[must_use]
+    pub const fn new(source: IterSource) -> Self {
+        Self {
+            source,
+            stages: vec![],
+        }
+    }
+    #[must_use]
+    pub fn then(&self, stage: IterStage) -> Self {
+        let mut new_self = self.to_owned();
+        new_self.stages.push(stage);
+        new_self
+    }
+    /// Runs the stages on each value in turn, giving the ones that make it through to `f` until it
+    /// returns false. Procs are only called for as many values as are taken.
+    pub fn for_each(
+        &self,
+        val_symt: &mut InterpretSymTable,
+        mut f: impl FnMut(Value, &mut InterpretSymTable) -> ZResult<bool>,
+    ) -> ZResult<()> {
+        let values: Box<dyn Iterator<Item = Value>> = match &self.source {
+            IterSource::Vec(v) => Box::new(v.iter().cloned()),
+            IterSource::Range { start, end } => {
+                Box::new((*start..end.unwrap_or(i32::MAX)).map(Value::I32))
+            }
+        };
+        let mut counts = vec![0; self.stages.len()];
+        for value in values {
+            let mut value = Some(value);
+            // `take` has given its last value, so no more are needed after this one
+            let mut done = false;
+            for (stage, count) in self.stages.iter().zip(&mut counts) {
+                let Some(v) = value.take() else {
+                    break;
+                };
+                value = match stage {
+                    IterStage::Map(p) => Some(p.call(vec![v], val_symt)?),
+                    IterStage::Filter(p) => call_predicate(p, &v, val_symt)?.then_some(v),
+                    IterStage::TakeWhile(p) => {
+                        if !call_predicate(p, &v, val_symt)? {
+                            return Ok(());
+                        }
+                        Some(v)
+                    }
+                    IterStage::SkipWhile(p) => {
+                        if *count == 0 && call_predicate(p, &v, val_symt)? {
+                            None
+                        } else {
+                            *count = 1;
+                            Some(v)
+                        }
+                    }
+                    IterStage::Take(n) => {
+                        if *count >= *n {
+                            return Ok(());
+                        }
+                        *count += 1;
+                        done |= *count >= *n;
+                        Some(v)
+                    }
+                    IterStage::Skip(n) => {
+                        if *count < *n {
+                            *count += 1;
+                            None
+                        } else {
+                            Some(v)
+                        }
+                    }
+                    IterStage::Enumerate => {
+                        let i = i32::try_from(*count)
+                            .ok()
+                            .ok_or_else(|| ZError::i002("enumerate", std::slice::from_ref(&v)))?;
+                        *count += 1;
+                        Some(Value::Vec(vec![Value::I32(i), v]))
+                    }
+                };
+            }
+            if let Some(v) = value {
+                if !f(v, val_symt)? {
+                    return Ok(());
+                }
+            }
+            if done {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn iter_stage(
+    h: &mut HashMap<&str, Value>,
+    n: &'static str,
+    stage: fn(Proc) -> IterStage,
+    callback: &'static Lazy<Arc<Type>>,
+) {
+    binary(
+        h,
+        n,
+        Arc::new(move |x: &Vec<Value>| {
+            Ok(Value::Iter(
+                get_param::<Iter>(x, 0)?.then(stage(get_param(x, 1)?)),
+            ))
+        }),
+        &ITER_T,
+        callback,
+        &ITER_T,
+    );
+}
+
+fn iter_count_stage(h: &mut HashMap<&str, Value>, n: &'static str, stage: fn(usize) -> IterStage) {
+    binary(
+        h,
+        n,
+        Arc::new(move |x: &Vec<Value>| {
+            let count = usize::try_from(get_param::<i32>(x, 1)?)
+                .ok()
+                .ok_or_else(|| ZError::i007(n, x))?;
+            Ok(Value::Iter(get_param::<Iter>(x, 0)?.then(stage(count))))
+        }),
+        &ITER_T,
+        &I32_T,
+        &ITER_T,
+    );
+}
+
+#[allow(clippy::cognitive_complexity, clippy::float_cmp)]
+fn iter_t() -> BuiltinType {
+    let mut h = HashMap::new();
+    trace!("Initialising iter");
+    h.insert("_default", Value::Iter(Iter::new(IterSource::Vec(vec![]))));
+    concat(&mut h, &ITER_T);
+    iter_stage(&mut h, "map", IterStage::Map, &MAPPER_T);
+    iter_stage(&mut h, "filter", IterStage::Filter, &PREDICATE_T);
+    iter_stage(&mut h, "take_while", IterStage::TakeWhile, &PREDICATE_T);
+    iter_stage(&mut h, "skip_while", IterStage::SkipWhile, &PREDICATE_T);
+    iter_count_stage(&mut h, "take", IterStage::Take);
+    iter_count_stage(&mut h, "skip", IterStage::Skip);
+    unary(
+        &mut h,
+        "enumerate",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(Value::Iter(
+                get_param::<Iter>(x, 0)?.then(IterStage::Enumerate),
+            ))
+        }),
+        &ITER_T,
+        &ITER_T,
+    );
+    higher_order(
+        &mut h,
+        "collect",
+        Arc::new(|x: &Vec<Value>, val_symt: &mut InterpretSymTable| {
+            let mut out = vec![];
+            get_param::<Iter>(x, 0)?.for_each(val_symt, |v, _| {
+                out.push(v);
+                Ok(true)
+            })?;
+            Ok(Value::Vec(out))
+        }),
+        &[&ITER_T],
+        &VEC_T,
+    );
+    higher_order(
+        &mut h,
+        "count",
+        Arc::new(|x: &Vec<Value>, val_symt: &mut InterpretSymTable| {
+            let mut count = 0i32;
+            get_param::<Iter>(x, 0)?.for_each(val_symt, |_, _| {
+                count = count
+                    .checked_add(1)
+                    .ok_or_else(|| ZError::i002("count", x))?;
+                Ok(true)
+            })?;
+            Ok(Value::I32(count))
+        }),
+        &[&ITER_T],
+        &I32_T,
+    );
+    higher_order(
+        &mut h,
+        "first",
+        Arc::new(|x: &Vec<Value>, val_symt: &mut InterpretSymTable| {
+            let mut first = None;
+            get_param::<Iter>(x, 0)?.for_each(val_symt, |v, _| {
+                first = Some(Box::new(v));
+                Ok(false)
+            })?;
+            Ok(Value::Opt(first))
+        }),
+        &[&ITER_T],
+        &OPT_T,
+    );
+
+    let typecast = Arc::new(|x: &Vec<Value>| {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
+            p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&ITER_T_VAL)),
+            p if p == *STR_T_VAL => Value::Str(x[0].to_string()),
+            p if p == *ITER_T_VAL => x[0].to_owned(),
+            _ => return Err(ZError::i001(x)),
+        })
+    });
+    type_cast(&mut h, typecast, &ITER_T);
+
+    BuiltinType {
+        name: Some(Ident::new("iter")),
+        namespace: h.drain().map(|(k, v)| (k.into(), v)).collect(),
+        fields: HashMap::default(),
+        type_args: vec![],
+    }
+}
+
+pub static ITER_T: Lazy<Arc<Type>> = Lazy::new(|| Arc::new(iter_t().into()));
+pub static ITER_T_VAL: Lazy<Arc<ValueType>> = Lazy::new(|| Arc::new(iter_t().into()));
+
+use std::sync::Arc;
+
+use crate::{
+    ast::Ident,
+    primitives::utils::{
+        binary, call_predicate, concat, get_param, higher_order, type_cast, unary,
+    },
+    types::r#type::{BuiltinType, ValueType},
+};
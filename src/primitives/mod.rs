@@ -1,16 +1,28 @@
 mod bool_t;
+mod char_t;
+mod date_t;
+mod datetime_t;
+mod duration_t;
 mod f16_t;
 mod f32_t;
 mod f64_t;
+mod hmap_t;
 mod i128_t;
 mod i16_t;
 mod i32_t;
 mod i64_t;
 mod i8_t;
 mod ibig_t;
+mod instance;
 mod isize_t;
+mod iter_t;
+mod opt_t;
+mod overflow;
 mod proc_t;
+mod promotion;
+mod re_t;
 mod str_t;
+mod time_t;
 mod type_t;
 mod u128_t;
 mod u16_t;
@@ -20,23 +32,39 @@ mod u8_t;
 mod ubig_t;
 mod unit_t;
 mod usize_t;
-mod utils;
+mod vec_t;
+pub(crate) mod utils;
 
 use std::collections::HashMap;
 
 pub use bool_t::{BOOL_T, BOOL_T_VAL};
+pub use char_t::{CHAR_T, CHAR_T_VAL};
+pub use date_t::{DATE_T, DATE_T_VAL};
+pub use datetime_t::{DATETIME_T, DATETIME_T_VAL};
+pub use duration_t::{DURATION_T, DURATION_T_VAL};
 pub use f16_t::{F16_T, F16_T_VAL};
 pub use f32_t::{F32_T, F32_T_VAL};
 pub use f64_t::{F64_T, F64_T_VAL};
+pub use hmap_t::{Hmap, HMAP_T, HMAP_T_VAL};
 pub use i128_t::{I128_T, I128_T_VAL};
 pub use i16_t::{I16_T, I16_T_VAL};
 pub use i32_t::{I32_T, I32_T_VAL};
 pub use i64_t::{I64_T, I64_T_VAL};
 pub use i8_t::{I8_T, I8_T_VAL};
 pub use ibig_t::{IBIG_T, IBIG_T_VAL};
+pub use instance::instance_namespace;
 pub use isize_t::{ISIZE_T, ISIZE_T_VAL};
-pub use proc_t::{generic_proc, LazyGenericProc, PROC_T, PROC_T_VAL};
+pub use iter_t::{Iter, IterSource, IterStage, ITER_T, ITER_T_VAL};
+pub use opt_t::{OPT_T, OPT_T_VAL};
+pub use overflow::{overflow_mode, set_overflow_mode, OverflowMode};
+pub use proc_t::{
+    generic_proc, proc_signature, LazyGenericProc, COMPARATOR_T, MAPPER_T, NUMBER_T, PREDICATE_T,
+    PROC_T, PROC_T_VAL, REDUCER_T, VARGS_T,
+};
+pub use promotion::{adapt_int_literal, common_numeric_type, is_numeric, widen, widens_to};
+pub use re_t::{Re, RE_T, RE_T_VAL};
 pub use str_t::{STR_T, STR_T_VAL};
+pub use time_t::{TIME_T, TIME_T_VAL};
 pub use type_t::{TYPE_T, TYPE_T_VAL};
 pub use u128_t::{U128_T, U128_T_VAL};
 pub use u16_t::{U16_T, U16_T_VAL};
@@ -46,9 +74,13 @@ pub use u8_t::{U8_T, U8_T_VAL};
 pub use ubig_t::{UBIG_T, UBIG_T_VAL};
 pub use unit_t::{UNIT_T, UNIT_T_VAL};
 pub use usize_t::{USIZE_T, USIZE_T_VAL};
+pub use vec_t::{VEC_T, VEC_T_VAL};
 
 pub static ANY_T: Lazy<Arc<Type>> = Lazy::new(|| Arc::new(Type::Any));
 pub static ANY_T_VAL: Lazy<Arc<ValueType>> = Lazy::new(|| Arc::new(ValueType::Any));
+/// The type of a declaration that failed to type check, so that its uses aren't reported again.
+/// It's compared by pointer, so it's never the same as [`ANY_T`].
+pub static ERROR_T: Lazy<Arc<Type>> = Lazy::new(|| Arc::new(Type::Any));
 
 use std::sync::Arc;
 
@@ -60,6 +92,7 @@ use crate::types::r#type::{Type, ValueType};
 pub static PRIMS: Lazy<HashMap<&'static str, Arc<Type>>> = Lazy::new(|| {
     hashmap! {
         "bool" => Arc::clone(&BOOL_T),
+        "char" => Arc::clone(&CHAR_T),
         "f16" => Arc::clone(&F16_T),
         "f32" => Arc::clone(&F32_T),
         "f64" => Arc::clone(&F64_T),
@@ -70,7 +103,9 @@ pub static PRIMS: Lazy<HashMap<&'static str, Arc<Type>>> = Lazy::new(|| {
         "i8" => Arc::clone(&I8_T),
         "ibig" => Arc::clone(&IBIG_T),
         "isize" => Arc::clone(&ISIZE_T),
+        "opt" => Arc::clone(&OPT_T),
         "proc" => Arc::clone(&PROC_T),
+        "re" => Arc::clone(&RE_T),
         "str" => Arc::clone(&STR_T),
         "type" => Arc::clone(&TYPE_T),
         "u128" => Arc::clone(&U128_T),
@@ -88,6 +123,7 @@ pub static PRIMS: Lazy<HashMap<&'static str, Arc<Type>>> = Lazy::new(|| {
 pub static PRIMS_VAL: Lazy<HashMap<&'static str, Arc<ValueType>>> = Lazy::new(|| {
     hashmap! {
         "bool" => Arc::clone(&BOOL_T_VAL),
+        "char" => Arc::clone(&CHAR_T_VAL),
         "f16" => Arc::clone(&F16_T_VAL),
         "f32" => Arc::clone(&F32_T_VAL),
         "f64" => Arc::clone(&F64_T_VAL),
@@ -98,7 +134,9 @@ pub static PRIMS_VAL: Lazy<HashMap<&'static str, Arc<ValueType>>> = Lazy::new(||
         "i8" => Arc::clone(&I8_T_VAL),
         "ibig" => Arc::clone(&IBIG_T_VAL),
         "isize" => Arc::clone(&ISIZE_T_VAL),
+        "opt" => Arc::clone(&OPT_T_VAL),
         "proc" => Arc::clone(&PROC_T_VAL),
+        "re" => Arc::clone(&RE_T_VAL),
         "str" => Arc::clone(&STR_T_VAL),
         "type" => Arc::clone(&TYPE_T_VAL),
         "u128" => Arc::clone(&U128_T_VAL),
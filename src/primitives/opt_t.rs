@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use tracing::trace;
+
+use crate::{
+    errors::ZError,
+    primitives::*,
+    types::value::{Proc, Value},
+    Type,
+};
+
+fn comp_opr_opt<'a>(h: &mut HashMap<&'a str, Value>, n: &'a str, eq: bool) {
+    binary(
+        h,
+        n,
+        Arc::new(move |x: &Vec<Value>| {
+            Ok(((get_param::<Option<Box<Value>>>(x, 0)? == get_param(x, 1)?) == eq).into())
+        }),
+        &OPT_T,
+        &OPT_T,
+        &BOOL_T,
+    );
+}
+
+#[allow(clippy::cognitive_complexity, clippy::float_cmp)]
+fn opt_t() -> BuiltinType {
+    let mut h = HashMap::new();
+    trace!("Initialising opt");
+    h.insert("_default", Value::Opt(None));
+    concat(&mut h, &OPT_T);
+    comp_opr_opt(&mut h, "_eq", true);
+    comp_opr_opt(&mut h, "_ne", false);
+    unary(
+        &mut h,
+        "is_some",
+        Arc::new(|x: &Vec<Value>| Ok(get_param::<Option<Box<Value>>>(x, 0)?.is_some().into())),
+        &OPT_T,
+        &BOOL_T,
+    );
+    unary(
+        &mut h,
+        "is_none",
+        Arc::new(|x: &Vec<Value>| Ok(get_param::<Option<Box<Value>>>(x, 0)?.is_none().into())),
+        &OPT_T,
+        &BOOL_T,
+    );
+
+    // casting to any other type unwraps the value, then casts it
+    let typecast = Arc::new(|x: &Vec<Value>| {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
+            p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&OPT_T_VAL)),
+            p if p == *STR_T_VAL => Value::Str(x[0].to_string()),
+            p if p == *BOOL_T_VAL => get_param::<Option<Box<Value>>>(x, 0)?.is_some().into(),
+            p => {
+                let inner = *get_param::<Option<Box<Value>>>(x, 0)?.ok_or_else(ZError::i004)?;
+                if Arc::ptr_eq(&inner.value_ty(), &p) {
+                    return Ok(inner);
+                }
+                let typecast = inner.value_ty().namespace().get("_typecast").cloned();
+                let Some(Value::Proc(Proc::Builtin { f, .. })) = typecast else {
+                    return Err(ZError::i001(x));
+                };
+                f(&vec![inner, Value::Type(p)])?
+            }
+        })
+    });
+    type_cast(&mut h, typecast, &OPT_T);
+
+    BuiltinType {
+        name: Some(Ident::new("opt")),
+        namespace: h.drain().map(|(k, v)| (k.into(), v)).collect(),
+        fields: HashMap::default(),
+        type_args: vec![],
+    }
+}
+
+pub static OPT_T: Lazy<Arc<Type>> = Lazy::new(|| Arc::new(opt_t().into()));
+pub static OPT_T_VAL: Lazy<Arc<ValueType>> = Lazy::new(|| Arc::new(opt_t().into()));
+
+use std::sync::Arc;
+
+use crate::{
+    ast::Ident,
+    primitives::utils::{binary, concat, get_param, type_cast, unary},
+    types::r#type::{BuiltinType, ValueType},
+};
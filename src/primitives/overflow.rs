@@ -0,0 +1,26 @@
+use std::cell::Cell;
+
+/// What the arithmetic operators on fixed-width integers do when the result doesn't fit.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum OverflowMode {
+    /// Raise an I002 error
+    #[default]
+    Checked,
+    /// Wrap around at the boundary of the type
+    Wrapping,
+    /// Clamp to the minimum or maximum of the type
+    Saturating,
+}
+
+thread_local! {
+    static OVERFLOW_MODE: Cell<OverflowMode> = Cell::new(OverflowMode::default());
+}
+
+#[must_use]
+pub fn overflow_mode() -> OverflowMode {
+    OVERFLOW_MODE.with(Cell::get)
+}
+
+pub fn set_overflow_mode(mode: OverflowMode) {
+    OVERFLOW_MODE.with(|m| m.set(mode));
+}
@@ -8,6 +8,7 @@ use once_cell::sync::{Lazy, OnceCell};
 use tracing::trace;
 
 use crate::{
+    errors::ZError,
     primitives::*,
     types::value::{Proc, Value},
     Type,
@@ -19,10 +20,10 @@ fn proc_t() -> BuiltinType {
     concat(&mut h, &PROC_T);
 
     let typecast = Arc::new(|x: &Vec<Value>| {
-        Some(match get_param::<Arc<ValueType>>(x, 1)? {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
             p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&PROC_T_VAL)),
             p if p == *STR_T_VAL => Value::Str(get_param::<Proc>(x, 0)?.to_string()),
-            _ => return None,
+            _ => return Err(ZError::i001(x)),
         })
     });
     type_cast(&mut h, typecast, &PROC_T);
@@ -41,6 +42,32 @@ fn proc_t() -> BuiltinType {
 pub static PROC_T: Lazy<Arc<Type>> = Lazy::new(|| Arc::new(proc_t().into()));
 pub static PROC_T_VAL: Lazy<Arc<ValueType>> = Lazy::new(|| Arc::new(proc_t().into()));
 
+/// The last argument type of builtin procs that take any number of arguments of any type in its
+/// place, like `ter.out`
+pub static VARGS_T: Lazy<Arc<Type>> = Lazy::new(|| Arc::new(Type::Any));
+/// The argument type of builtin procs that take a value of any numeric primitive, like
+/// `math::sqrt`
+pub static NUMBER_T: Lazy<Arc<Type>> = Lazy::new(|| Arc::new(Type::Any));
+
+/// Callbacks that builtins like `collections::map` call with one or two values of any type, which
+/// are checked to have as many arguments and to give the right type when compiling
+pub static MAPPER_T: Lazy<Arc<Type>> =
+    Lazy::new(|| generic_proc(vec![Arc::clone(&ANY_T)], Arc::clone(&ANY_T)));
+pub static PREDICATE_T: Lazy<Arc<Type>> =
+    Lazy::new(|| generic_proc(vec![Arc::clone(&ANY_T)], Arc::clone(&BOOL_T)));
+pub static REDUCER_T: Lazy<Arc<Type>> = Lazy::new(|| {
+    generic_proc(
+        vec![Arc::clone(&ANY_T), Arc::clone(&ANY_T)],
+        Arc::clone(&ANY_T),
+    )
+});
+pub static COMPARATOR_T: Lazy<Arc<Type>> = Lazy::new(|| {
+    generic_proc(
+        vec![Arc::clone(&ANY_T), Arc::clone(&ANY_T)],
+        Arc::clone(&I32_T),
+    )
+});
+
 #[must_use]
 pub fn generic_proc(args: Vec<Arc<Type>>, ret: Arc<Type>) -> Arc<Type> {
     Arc::new(Type::Generic {
@@ -52,6 +79,24 @@ pub fn generic_proc(args: Vec<Arc<Type>>, ret: Arc<Type>) -> Arc<Type> {
     })
 }
 
+/// The argument and return types of a proc type made by [`generic_proc`]
+#[must_use]
+pub fn proc_signature(ty: &Type) -> Option<(Vec<Arc<Type>>, Arc<Type>)> {
+    let Type::Generic { type_args, base } = ty else {
+        return None;
+    };
+    if !Arc::ptr_eq(base, &PROC_T) {
+        return None;
+    }
+    let (_, Either::Right(Either::Left(args))) = type_args.iter().find(|(k, _)| *k == "A")? else {
+        unreachable!()
+    };
+    let (_, Either::Right(Either::Right(ret))) = type_args.iter().find(|(k, _)| *k == "R")? else {
+        unreachable!()
+    };
+    Some((args.to_owned(), Arc::clone(ret)))
+}
+
 #[derive(Clone)]
 pub struct LazyGenericProc {
     pub args: Vec<&'static Lazy<Arc<Type>>>,
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use crate::{
+    ast::{Ast, AstData, BinaryOpr, Literal},
+    primitives::{PRIMS, PRIMS_VAL},
+    types::{r#type::Type, token::OprType, value::Value},
+};
+
+/// Numeric primitives, in the order of preference when looking for a common type
+const NUMERIC: [&str; 17] = [
+    "i8", "u8", "i16", "u16", "i32", "u32", "i64", "u64", "i128", "u128", "f16", "f32", "f64",
+    "isize", "usize", "ibig", "ubig",
+];
+
+/// The types that a numeric primitive can be converted to without losing precision
+fn widenings(name: &str) -> &'static [&'static str] {
+    match name {
+        "i8" => &[
+            "i16", "i32", "i64", "i128", "isize", "ibig", "f16", "f32", "f64",
+        ],
+        "u8" => &[
+            "u16", "u32", "u64", "u128", "usize", "i16", "i32", "i64", "i128", "isize", "ubig",
+            "ibig", "f16", "f32", "f64",
+        ],
+        "i16" => &["i32", "i64", "i128", "isize", "ibig", "f32", "f64"],
+        "u16" => &[
+            "u32", "u64", "u128", "usize", "i32", "i64", "i128", "ubig", "ibig", "f32", "f64",
+        ],
+        "i32" => &["i64", "i128", "ibig", "f64"],
+        "u32" => &["u64", "u128", "i64", "i128", "ubig", "ibig", "f64"],
+        "i64" => &["i128", "ibig"],
+        "u64" => &["u128", "i128", "ubig", "ibig"],
+        "i128" | "isize" | "ubig" => &["ibig"],
+        "u128" | "usize" => &["ubig", "ibig"],
+        "f16" => &["f32", "f64"],
+        "f32" => &["f64"],
+        _ => &[],
+    }
+}
+
+fn numeric_name(ty: &Arc<Type>) -> Option<&'static str> {
+    NUMERIC
+        .into_iter()
+        .find(|name| Arc::ptr_eq(&PRIMS[name], ty))
+}
+
+#[must_use]
+pub fn is_numeric(ty: &Arc<Type>) -> bool {
+    numeric_name(ty).is_some()
+}
+
+#[must_use]
+pub fn widens_to(from: &Arc<Type>, to: &Arc<Type>) -> bool {
+    let (Some(from), Some(to)) = (numeric_name(from), numeric_name(to)) else {
+        return false;
+    };
+    from == to || widenings(from).contains(&to)
+}
+
+#[must_use]
+pub fn common_numeric_type(ty1: &Arc<Type>, ty2: &Arc<Type>) -> Option<Arc<Type>> {
+    NUMERIC
+        .into_iter()
+        .map(|name| &PRIMS[name])
+        .find(|ty| widens_to(ty1, ty) && widens_to(ty2, ty))
+        .map(Arc::clone)
+}
+
+/// Wraps `ast` in a typecast to the numeric primitive `ty`
+#[must_use]
+pub fn widen(ast: Ast, ty: &Arc<Type>) -> Ast {
+    let name = numeric_name(ty).unwrap_or_else(|| unreachable!());
+    BinaryOpr {
+        ty: OprType::TypeCast,
+        opr_span: None,
+        operand1: ast.into(),
+        operand2: Value::Type(Arc::clone(&PRIMS_VAL[name]))
+            .as_ast()
+            .into(),
+    }
+    .as_variant()
+}
+
+/// Converts an integer literal to the integer primitive `ty`, if it fits in it
+#[must_use]
+pub fn adapt_int_literal(ast: &Ast, ty: &Arc<Type>) -> Option<Ast> {
+    let Ast::Literal(Literal {
+        span,
+        content: Value::I32(n),
+    }) = ast
+    else {
+        return None;
+    };
+    let n = *n;
+    let content = match numeric_name(ty)? {
+        "i8" => Value::I8(n.try_into().ok()?),
+        "i16" => Value::I16(n.try_into().ok()?),
+        "i32" => Value::I32(n),
+        "i64" => Value::I64(n.into()),
+        "i128" => Value::I128(n.into()),
+        "isize" => Value::Isize(n.try_into().ok()?),
+        "ibig" => Value::Ibig(n.into()),
+        "u8" => Value::U8(n.try_into().ok()?),
+        "u16" => Value::U16(n.try_into().ok()?),
+        "u32" => Value::U32(n.try_into().ok()?),
+        "u64" => Value::U64(n.try_into().ok()?),
+        "u128" => Value::U128(n.try_into().ok()?),
+        "usize" => Value::Usize(n.try_into().ok()?),
+        "ubig" => Value::Ubig(u32::try_from(n).ok()?.into()),
+        _ => return None,
+    };
+    Some(
+        Literal {
+            span: span.to_owned(),
+            content,
+        }
+        .as_variant(),
+    )
+}
@@ -0,0 +1,227 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use tracing::trace;
+
+use crate::{
+    errors::{ZError, ZResult},
+    primitives::*,
+    types::value::Value,
+    Type,
+};
+
+/// A compiled regex, which is equal to others of the same pattern
+#[derive(Clone, Debug)]
+pub struct Re(pub Regex);
+impl PartialEq for Re {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+impl Display for Re {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.as_str())
+    }
+}
+impl Re {
+    pub fn new(pattern: &str) -> ZResult<Self> {
+        Regex::new(pattern)
+            .map(Self)
+            .map_err(|e| ZError::i011(pattern, e))
+    }
+}
+
+fn comp_opr_re<'a>(h: &mut HashMap<&'a str, Value>, n: &'a str, eq: bool) {
+    binary(
+        h,
+        n,
+        Arc::new(move |x: &Vec<Value>| {
+            Ok(((get_param::<Re>(x, 0)? == get_param(x, 1)?) == eq).into())
+        }),
+        &RE_T,
+        &RE_T,
+        &BOOL_T,
+    );
+}
+
+/// The text of a match or group as an `opt`
+fn opt_str(s: Option<&str>) -> Value {
+    Value::Opt(s.map(|s| Box::new(Value::Str(s.to_owned()))))
+}
+
+/// Registers a method taking a regex and a string, which gives `f` of the regex's first match in
+/// it, or none if there isn't one
+fn re_captures(
+    h: &mut HashMap<&str, Value>,
+    n: &'static str,
+    arg_ty: Option<&'static Lazy<Arc<Type>>>,
+    f: fn(&Captures, &[Value]) -> ZResult<Value>,
+) {
+    let f = Arc::new(move |x: &Vec<Value>| {
+        let (re, s) = (get_param::<Re>(x, 0)?, get_param::<String>(x, 1)?);
+        Ok(match re.0.captures(&s) {
+            Some(caps) => f(&caps, x)?,
+            None => Value::Opt(None),
+        })
+    });
+    if let Some(arg_ty) = arg_ty {
+        nary(h, n, f, &[&RE_T, &STR_T, arg_ty], &OPT_T);
+    } else {
+        binary(h, n, f, &RE_T, &STR_T, &OPT_T);
+    }
+}
+
+fn re_replace(h: &mut HashMap<&str, Value>, n: &'static str, all: bool) {
+    nary(
+        h,
+        n,
+        Arc::new(move |x: &Vec<Value>| {
+            let (re, s, template) = (
+                get_param::<Re>(x, 0)?,
+                get_param::<String>(x, 1)?,
+                get_param::<String>(x, 2)?,
+            );
+            // a limit of 0 replaces every match
+            let limit = usize::from(!all);
+            Ok(Value::Str(
+                re.0.replacen(&s, limit, &*template).into_owned(),
+            ))
+        }),
+        &[&RE_T, &STR_T, &STR_T],
+        &STR_T,
+    );
+}
+
+#[allow(clippy::cognitive_complexity, clippy::float_cmp)]
+fn re_t() -> BuiltinType {
+    let mut h = HashMap::new();
+    trace!("Initialising re");
+    concat(&mut h, &RE_T);
+    comp_opr_re(&mut h, "_eq", true);
+    comp_opr_re(&mut h, "_ne", false);
+    // `re::compile "..."`, which `"..." @ re` also does
+    unary(
+        &mut h,
+        "compile",
+        Arc::new(|x: &Vec<Value>| Ok(Value::Re(Re::new(&get_param::<String>(x, 0)?)?))),
+        &STR_T,
+        &RE_T,
+    );
+    binary(
+        &mut h,
+        "is_match",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(get_param::<Re>(x, 0)?
+                .0
+                .is_match(&get_param::<String>(x, 1)?)
+                .into())
+        }),
+        &RE_T,
+        &STR_T,
+        &BOOL_T,
+    );
+    binary(
+        &mut h,
+        "find",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(opt_str(
+                get_param::<Re>(x, 0)?
+                    .0
+                    .find(&get_param::<String>(x, 1)?)
+                    .map(|m| m.as_str()),
+            ))
+        }),
+        &RE_T,
+        &STR_T,
+        &OPT_T,
+    );
+    binary(
+        &mut h,
+        "find_all",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(Value::Vec(
+                get_param::<Re>(x, 0)?
+                    .0
+                    .find_iter(&get_param::<String>(x, 1)?)
+                    .map(|m| Value::Str(m.as_str().to_owned()))
+                    .collect(),
+            ))
+        }),
+        &RE_T,
+        &STR_T,
+        &VEC_T,
+    );
+    // the groups of the first match, with the whole match first and none for groups that didn't
+    // match anything
+    re_captures(&mut h, "captures", None, |caps, _| {
+        Ok(Value::Opt(Some(Box::new(Value::Vec(
+            caps.iter()
+                .map(|m| opt_str(m.map(|m| m.as_str())))
+                .collect(),
+        )))))
+    });
+    re_captures(&mut h, "group", Some(&I32_T), |caps, x| {
+        Ok(opt_str(
+            usize::try_from(get_param::<i32>(x, 2)?)
+                .ok()
+                .and_then(|i| caps.get(i))
+                .map(|m| m.as_str()),
+        ))
+    });
+    re_captures(&mut h, "named", Some(&STR_T), |caps, x| {
+        Ok(opt_str(
+            caps.name(&get_param::<String>(x, 2)?).map(|m| m.as_str()),
+        ))
+    });
+    // templates refer to groups with `$1` or `${name}`, and `$$` is a `$`
+    re_replace(&mut h, "replace", false);
+    re_replace(&mut h, "replace_all", true);
+    binary(
+        &mut h,
+        "split",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(Value::Vec(
+                get_param::<Re>(x, 0)?
+                    .0
+                    .split(&get_param::<String>(x, 1)?)
+                    .map(|part| Value::Str(part.to_owned()))
+                    .collect(),
+            ))
+        }),
+        &RE_T,
+        &STR_T,
+        &VEC_T,
+    );
+
+    let typecast = Arc::new(|x: &Vec<Value>| {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
+            p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&RE_T_VAL)),
+            p if p == *STR_T_VAL => Value::Str(x[0].to_string()),
+            p if p == *RE_T_VAL => x[0].to_owned(),
+            _ => return Err(ZError::i001(x)),
+        })
+    });
+    type_cast(&mut h, typecast, &RE_T);
+
+    BuiltinType {
+        name: Some(Ident::new("re")),
+        namespace: h.drain().map(|(k, v)| (k.into(), v)).collect(),
+        fields: HashMap::default(),
+        type_args: vec![],
+    }
+}
+
+pub static RE_T: Lazy<Arc<Type>> = Lazy::new(|| Arc::new(re_t().into()));
+pub static RE_T_VAL: Lazy<Arc<ValueType>> = Lazy::new(|| Arc::new(re_t().into()));
+
+use std::sync::Arc;
+
+use crate::{
+    ast::Ident,
+    primitives::utils::{binary, concat, get_param, nary, type_cast, unary},
+    types::r#type::{BuiltinType, ValueType},
+};
@@ -2,23 +2,90 @@ use std::collections::HashMap;
 
 use once_cell::sync::Lazy;
 
-use crate::{primitives::*, types::value::Value, Type};
+use crate::{errors::ZError, primitives::*, types::value::Value, Type};
 
 macro_rules! typecast_str_to_num {
-    ($v:ident, $x:ident) => {
-        Value::$v(get_param::<String>($x, 0)?.parse().ok()?)
-    };
+    ($v:ident, $x:ident) => {{
+        let s = get_param::<String>($x, 0)?;
+        Value::$v(s.parse().map_err(|e| {
+            ZError::i010(
+                &s,
+                &$x[1].as_type().map(ToString::to_string).unwrap_or_default(),
+                e,
+            )
+        })?)
+    }};
 }
 use tracing::trace;
+
+/// The byte offset of the `i`th char of `s`, if it's in `0..=s.chars().count()`
+fn char_offset(s: &str, i: i32) -> Option<usize> {
+    let i = usize::try_from(i).ok()?;
+    s.char_indices()
+        .map(|(offset, _)| offset)
+        .chain([s.len()])
+        .nth(i)
+}
+
+/// The index of the char at byte offset `offset` of `s`
+fn char_index(s: &str, offset: usize) -> Value {
+    // strings of more than `i32::MAX` chars can't be indexed anyway
+    let i = s.char_indices().take_while(|(o, _)| *o < offset).count();
+    Value::I32(i32::try_from(i).unwrap_or(i32::MAX))
+}
+
+fn str_fn(h: &mut HashMap<&str, Value>, n: &'static str, f: fn(&str) -> String) {
+    unary(
+        h,
+        n,
+        Arc::new(move |x: &Vec<Value>| Ok(Value::Str(f(&get_param::<String>(x, 0)?)))),
+        &STR_T,
+        &STR_T,
+    );
+}
+
+fn str_test(h: &mut HashMap<&str, Value>, n: &'static str, f: fn(&str, &str) -> bool) {
+    binary(
+        h,
+        n,
+        Arc::new(move |x: &Vec<Value>| {
+            Ok(Value::Bool(f(
+                &get_param::<String>(x, 0)?,
+                &get_param::<String>(x, 1)?,
+            )))
+        }),
+        &STR_T,
+        &STR_T,
+        &BOOL_T,
+    );
+}
+
+fn str_parts(h: &mut HashMap<&str, Value>, n: &'static str, f: fn(&str) -> Vec<&str>) {
+    unary(
+        h,
+        n,
+        Arc::new(move |x: &Vec<Value>| {
+            Ok(Value::Vec(
+                f(&get_param::<String>(x, 0)?)
+                    .into_iter()
+                    .map(|part| Value::Str(part.to_owned()))
+                    .collect(),
+            ))
+        }),
+        &STR_T,
+        &VEC_T,
+    );
+}
 #[allow(clippy::cognitive_complexity, clippy::float_cmp)]
 fn str_t() -> BuiltinType {
     let mut h = HashMap::new();
     trace!("Initialising str");
     h.insert("_default", Value::Str(String::new()));
     concat(&mut h, &STR_T);
+    comp_opr_default::<String>(&mut h, &STR_T);
 
     let typecast = Arc::new(|x: &Vec<Value>| {
-        Some(match get_param::<Arc<ValueType>>(x, 1)? {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
             p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&STR_T_VAL)),
             p if p == *STR_T_VAL => x[0].to_owned(),
             p if p == *BOOL_T_VAL => Value::Bool(get_param::<String>(x, 0)?.is_empty()),
@@ -39,7 +106,8 @@ fn str_t() -> BuiltinType {
             p if p == *F16_T_VAL => typecast_str_to_num!(F16, x),
             p if p == *F32_T_VAL => typecast_str_to_num!(F32, x),
             p if p == *F64_T_VAL => typecast_str_to_num!(F64, x),
-            _ => return None,
+            p if p == *RE_T_VAL => Value::Re(Re::new(&get_param::<String>(x, 0)?)?),
+            _ => return Err(ZError::i001(x)),
         })
     });
     type_cast(&mut h, typecast, &STR_T);
@@ -47,7 +115,7 @@ fn str_t() -> BuiltinType {
         &mut h,
         "_mul",
         Arc::new(|x: &Vec<Value>| {
-            Some(Value::Str(get_param::<String>(x, 0)?.repeat(get_param::<
+            Ok(Value::Str(get_param::<String>(x, 0)?.repeat(get_param::<
                 usize,
             >(
                 x, 1
@@ -57,6 +125,162 @@ fn str_t() -> BuiltinType {
         &USIZE_T,
         &STR_T,
     );
+    unary(
+        &mut h,
+        "len",
+        Arc::new(|x: &Vec<Value>| {
+            i32::try_from(get_param::<String>(x, 0)?.chars().count())
+                .map(Value::I32)
+                .ok()
+                .ok_or_else(|| ZError::i002("len", x))
+        }),
+        &STR_T,
+        &I32_T,
+    );
+    unary(
+        &mut h,
+        "byte_len",
+        Arc::new(|x: &Vec<Value>| {
+            i32::try_from(get_param::<String>(x, 0)?.len())
+                .map(Value::I32)
+                .ok()
+                .ok_or_else(|| ZError::i002("byte_len", x))
+        }),
+        &STR_T,
+        &I32_T,
+    );
+    unary(
+        &mut h,
+        "is_empty",
+        Arc::new(|x: &Vec<Value>| Ok(get_param::<String>(x, 0)?.is_empty().into())),
+        &STR_T,
+        &BOOL_T,
+    );
+    unary(
+        &mut h,
+        "is_ascii",
+        Arc::new(|x: &Vec<Value>| Ok(get_param::<String>(x, 0)?.is_ascii().into())),
+        &STR_T,
+        &BOOL_T,
+    );
+    str_fn(&mut h, "trim", |s| s.trim().to_owned());
+    str_fn(&mut h, "trim_start", |s| s.trim_start().to_owned());
+    str_fn(&mut h, "trim_end", |s| s.trim_end().to_owned());
+    str_fn(&mut h, "upper", str::to_uppercase);
+    str_fn(&mut h, "lower", str::to_lowercase);
+    str_fn(&mut h, "reverse", |s| s.chars().rev().collect());
+    str_test(&mut h, "starts_with", |s, p| s.starts_with(p));
+    str_test(&mut h, "ends_with", |s, p| s.ends_with(p));
+    str_test(&mut h, "contains", |s, p| s.contains(p));
+    str_parts(&mut h, "lines", |s| s.lines().collect());
+    str_parts(&mut h, "words", |s| s.split_whitespace().collect());
+    binary(
+        &mut h,
+        "split",
+        Arc::new(|x: &Vec<Value>| {
+            let (s, sep) = (get_param::<String>(x, 0)?, get_param::<String>(x, 1)?);
+            // splitting by "" would give empty strings at the ends, which `chars` doesn't
+            if sep.is_empty() {
+                return Err(ZError::i007("split", x));
+            }
+            Ok(Value::Vec(
+                s.split(&*sep)
+                    .map(|part| Value::Str(part.to_owned()))
+                    .collect(),
+            ))
+        }),
+        &STR_T,
+        &STR_T,
+        &VEC_T,
+    );
+    // `", ":.join(v)` puts the string between the values of `v`, as they are with `@ str`
+    binary(
+        &mut h,
+        "join",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(Value::Str(
+                get_param::<Vec<Value>>(x, 1)?
+                    .iter()
+                    .join(&get_param::<String>(x, 0)?),
+            ))
+        }),
+        &STR_T,
+        &VEC_T,
+        &STR_T,
+    );
+    unary(
+        &mut h,
+        "chars",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(Value::Vec(
+                get_param::<String>(x, 0)?
+                    .chars()
+                    .map(Value::Char)
+                    .collect(),
+            ))
+        }),
+        &STR_T,
+        &VEC_T,
+    );
+    // indices are of chars, not bytes
+    binary(
+        &mut h,
+        "char_at",
+        Arc::new(|x: &Vec<Value>| {
+            let s = get_param::<String>(x, 0)?;
+            Ok(Value::Opt(
+                usize::try_from(get_param::<i32>(x, 1)?)
+                    .ok()
+                    .and_then(|i| s.chars().nth(i))
+                    .map(|c| Box::new(Value::Char(c))),
+            ))
+        }),
+        &STR_T,
+        &I32_T,
+        &OPT_T,
+    );
+    // from char `start` up to but not including char `end`
+    nary(
+        &mut h,
+        "substr",
+        Arc::new(|x: &Vec<Value>| {
+            let s = get_param::<String>(x, 0)?;
+            char_offset(&s, get_param(x, 1)?)
+                .zip(char_offset(&s, get_param(x, 2)?))
+                .and_then(|(start, end)| s.get(start..end))
+                .map(|s| Value::Str(s.to_owned()))
+                .ok_or_else(|| ZError::i007("substr", x))
+        }),
+        &[&STR_T, &I32_T, &I32_T],
+        &STR_T,
+    );
+    // the char index of the first match, if there is one
+    binary(
+        &mut h,
+        "find",
+        Arc::new(|x: &Vec<Value>| {
+            let s = get_param::<String>(x, 0)?;
+            Ok(Value::Opt(
+                s.find(&*get_param::<String>(x, 1)?)
+                    .map(|offset| Box::new(char_index(&s, offset))),
+            ))
+        }),
+        &STR_T,
+        &STR_T,
+        &OPT_T,
+    );
+    nary(
+        &mut h,
+        "replace",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(Value::Str(get_param::<String>(x, 0)?.replace(
+                &*get_param::<String>(x, 1)?,
+                &get_param::<String>(x, 2)?,
+            )))
+        }),
+        &[&STR_T, &STR_T, &STR_T],
+        &STR_T,
+    );
 
     BuiltinType {
         name: Some(Ident::new("str")),
@@ -71,8 +295,10 @@ pub static STR_T_VAL: Lazy<Arc<ValueType>> = Lazy::new(|| Arc::new(str_t().into(
 
 use std::sync::Arc;
 
+use itertools::Itertools;
+
 use crate::{
     ast::Ident,
-    primitives::utils::{binary, concat, get_param, type_cast},
+    primitives::utils::{binary, comp_opr_default, concat, get_param, nary, type_cast, unary},
     types::r#type::{BuiltinType, ValueType},
 };
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveTime, TimeDelta, Timelike};
+use once_cell::sync::Lazy;
+use tracing::trace;
+
+use crate::{errors::ZError, primitives::*, types::value::Value, Type};
+
+fn time_part(h: &mut HashMap<&str, Value>, n: &'static str, f: fn(&NaiveTime) -> u32) {
+    unary(
+        h,
+        n,
+        Arc::new(move |x: &Vec<Value>| Ok(Value::I32(f(&get_param(x, 0)?).cast_signed()))),
+        &TIME_T,
+        &I32_T,
+    );
+}
+
+#[allow(clippy::cognitive_complexity, clippy::float_cmp)]
+fn time_t() -> BuiltinType {
+    let mut h = HashMap::new();
+    trace!("Initialising time");
+    h.insert("_default", Value::Time(NaiveTime::MIN));
+    concat(&mut h, &TIME_T);
+    comp_opr_default::<NaiveTime>(&mut h, &TIME_T);
+    fmt_strftime_default::<NaiveTime>(&mut h, &TIME_T, |v| (None, Some(*v), None));
+    time_part(&mut h, "hour", NaiveTime::hour);
+    time_part(&mut h, "minute", NaiveTime::minute);
+    time_part(&mut h, "second", NaiveTime::second);
+    time_part(&mut h, "nanosecond", NaiveTime::nanosecond);
+    // times wrap around midnight
+    let shifts: [(_, fn(&NaiveTime, TimeDelta) -> (NaiveTime, i64)); 2] = [
+        ("_add", NaiveTime::overflowing_add_signed),
+        ("_sub", NaiveTime::overflowing_sub_signed),
+    ];
+    for (n, f) in shifts {
+        binary(
+            &mut h,
+            n,
+            Arc::new(move |x: &Vec<Value>| {
+                Ok(Value::Time(f(&get_param(x, 0)?, get_param(x, 1)?).0))
+            }),
+            &TIME_T,
+            &DURATION_T,
+            &TIME_T,
+        );
+    }
+    binary(
+        &mut h,
+        "since",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(Value::Duration(
+                get_param::<NaiveTime>(x, 0)?.signed_duration_since(get_param(x, 1)?),
+            ))
+        }),
+        &TIME_T,
+        &TIME_T,
+        &DURATION_T,
+    );
+
+    let typecast = Arc::new(|x: &Vec<Value>| {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
+            p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&TIME_T_VAL)),
+            p if p == *STR_T_VAL => Value::Str(x[0].to_string()),
+            p if p == *TIME_T_VAL => x[0].to_owned(),
+            _ => return Err(ZError::i001(x)),
+        })
+    });
+    type_cast(&mut h, typecast, &TIME_T);
+
+    BuiltinType {
+        name: Some(Ident::new("time")),
+        namespace: h.drain().map(|(k, v)| (k.into(), v)).collect(),
+        fields: HashMap::default(),
+        type_args: vec![],
+    }
+}
+
+pub static TIME_T: Lazy<Arc<Type>> = Lazy::new(|| Arc::new(time_t().into()));
+pub static TIME_T_VAL: Lazy<Arc<ValueType>> = Lazy::new(|| Arc::new(time_t().into()));
+
+use std::sync::Arc;
+
+use crate::{
+    ast::Ident,
+    primitives::utils::{
+        binary, comp_opr_default, concat, fmt_strftime_default, get_param, type_cast, unary,
+    },
+    types::r#type::{BuiltinType, ValueType},
+};
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use once_cell::sync::Lazy;
 use tracing::trace;
 
-use crate::{primitives::*, types::value::Value, Type};
+use crate::{errors::ZError, primitives::*, types::value::Value, Type};
 #[allow(clippy::cognitive_complexity, clippy::float_cmp)]
 fn type_t() -> BuiltinType {
     let mut h = HashMap::new();
@@ -14,7 +14,7 @@ fn type_t() -> BuiltinType {
         &mut h,
         "_eq",
         Arc::new(|x: &Vec<Value>| {
-            Some(Value::Bool(
+            Ok(Value::Bool(
                 get_param::<Arc<ValueType>>(x, 0)? == get_param::<Arc<ValueType>>(x, 1)?,
             ))
         }),
@@ -26,7 +26,7 @@ fn type_t() -> BuiltinType {
         &mut h,
         "_ne",
         Arc::new(|x: &Vec<Value>| {
-            Some(Value::Bool(
+            Ok(Value::Bool(
                 get_param::<Arc<ValueType>>(x, 0)? != get_param::<Arc<ValueType>>(x, 1)?,
             ))
         }),
@@ -36,10 +36,10 @@ fn type_t() -> BuiltinType {
     );
 
     let typecast = Arc::new(|x: &Vec<Value>| {
-        Some(match get_param::<Arc<ValueType>>(x, 1)? {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
             p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&TYPE_T_VAL)),
             p if p == *STR_T_VAL => Value::Str(get_param::<Arc<ValueType>>(x, 0)?.to_string()),
-            _ => return None,
+            _ => return Err(ZError::i001(x)),
         })
     });
     type_cast(&mut h, typecast, &TYPE_T);
@@ -5,7 +5,7 @@ use num_traits::ToPrimitive;
 use once_cell::sync::Lazy;
 use tracing::trace;
 
-use crate::{primitives::*, typecast_int, types::value::Value, Type};
+use crate::{errors::ZError, primitives::*, typecast_int, types::value::Value, Type};
 #[allow(clippy::cognitive_complexity, clippy::float_cmp)]
 fn ubig_t() -> BuiltinType {
     let mut h = HashMap::new();
@@ -16,9 +16,11 @@ fn ubig_t() -> BuiltinType {
     arith_opr::<BigUint>(&mut h, "_rem", &std::ops::Rem::rem, &IBIG_T);
     arith_opr_big_default::<BigUint>(&mut h, &UBIG_T);
     comp_opr_default::<BigUint>(&mut h, &UBIG_T);
+    fmt_int_default::<BigUint>(&mut h, &UBIG_T);
+    bit_opr_big_default::<BigUint>(&mut h, &UBIG_T);
 
     let typecast = Arc::new(|x: &Vec<Value>| {
-        Some(match get_param::<Arc<ValueType>>(x, 1)? {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
             p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&UBIG_T_VAL)),
             p if p == *STR_T_VAL => typecast_int!(BigUint => str, x),
             p if p == *BOOL_T_VAL => Value::Bool(get_param::<BigUint>(x, 0)? == 0u8.into()),
@@ -39,7 +41,7 @@ fn ubig_t() -> BuiltinType {
             p if p == *F16_T_VAL => typecast_int!(big BigUint => f16, x),
             p if p == *F32_T_VAL => typecast_int!(big BigUint => f32, x),
             p if p == *F64_T_VAL => typecast_int!(big BigUint => f64, x),
-            _ => return None,
+            _ => return Err(ZError::i001(x)),
         })
     });
     type_cast(&mut h, typecast, &UBIG_T);
@@ -62,8 +64,8 @@ use num::BigUint;
 use crate::{
     ast::Ident,
     primitives::utils::{
-        arith_opr, arith_opr_big_default, comp_opr_default, concat, get_param, type_cast,
-        unary_unsigned_default,
+        arith_opr, arith_opr_big_default, bit_opr_big_default, comp_opr_default, concat,
+        fmt_int_default, get_param, type_cast, unary_unsigned_default,
     },
     types::r#type::{BuiltinType, ValueType},
 };
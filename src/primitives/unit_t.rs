@@ -3,12 +3,12 @@ use std::collections::HashMap;
 use once_cell::sync::Lazy;
 use tracing::trace;
 
-use crate::{primitives::*, types::value::Value, Type};
+use crate::{errors::ZError, primitives::*, types::value::Value, Type};
 fn comp_opr_unit<'a>(h: &mut HashMap<&'a str, Value>, n: &'a str, res: bool) {
     binary(
         h,
         n,
-        Arc::new(move |_| Some(res.into())),
+        Arc::new(move |_| Ok(res.into())),
         &UNIT_T,
         &UNIT_T,
         &BOOL_T,
@@ -30,10 +30,10 @@ fn unit_t() -> BuiltinType {
     comp_opr_unit(&mut h, "_le", true);
 
     let typecast = Arc::new(|x: &Vec<Value>| {
-        Some(match get_param::<Arc<ValueType>>(x, 1)? {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
             p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&UNIT_T_VAL)),
             p if p == *STR_T_VAL => Value::Str("()".into()),
-            _ => return None,
+            _ => return Err(ZError::i001(x)),
         })
     });
     type_cast(&mut h, typecast, &UNIT_T);
@@ -4,7 +4,7 @@ use half::f16;
 use once_cell::sync::Lazy;
 use tracing::trace;
 
-use crate::{primitives::*, typecast_int, types::value::Value, Type};
+use crate::{errors::ZError, primitives::*, typecast_int, types::value::Value, Type};
 #[allow(clippy::cognitive_complexity, clippy::float_cmp)]
 fn usize_t() -> BuiltinType {
     let mut h = HashMap::new();
@@ -14,9 +14,11 @@ fn usize_t() -> BuiltinType {
     unary_unsigned_default::<usize>(&mut h, &USIZE_T);
     arith_opr_default::<usize>(&mut h, &USIZE_T);
     comp_opr_default::<usize>(&mut h, &USIZE_T);
+    fmt_int_default::<usize>(&mut h, &USIZE_T);
+    bit_opr_default::<usize>(&mut h, &USIZE_T);
 
     let typecast = Arc::new(|x: &Vec<Value>| {
-        Some(match get_param::<Arc<ValueType>>(x, 1)? {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
             p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&USIZE_T_VAL)),
             p if p == *STR_T_VAL => typecast_int!(usize => str, x),
             p if p == *BOOL_T_VAL => typecast_int!(usize => bool, x),
@@ -37,7 +39,7 @@ fn usize_t() -> BuiltinType {
             p if p == *F16_T_VAL => typecast_int!(usize => f16, x),
             p if p == *F32_T_VAL => typecast_int!(usize => f32, x),
             p if p == *F64_T_VAL => typecast_int!(usize => f64, x),
-            _ => return None,
+            _ => return Err(ZError::i001(x)),
         })
     });
     type_cast(&mut h, typecast, &USIZE_T);
@@ -58,7 +60,8 @@ use std::sync::Arc;
 use crate::{
     ast::Ident,
     primitives::utils::{
-        arith_opr_default, comp_opr_default, concat, get_param, type_cast, unary_unsigned_default,
+        arith_opr_default, bit_opr_default, comp_opr_default, concat, fmt_int_default, get_param,
+        type_cast, unary_unsigned_default,
     },
     types::r#type::{BuiltinType, ValueType},
 };
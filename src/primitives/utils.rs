@@ -1,25 +1,54 @@
 use std::{
     cmp::PartialOrd,
     collections::HashMap,
-    ops::{Add, Div, Mul, Rem, Sub},
+    fmt::{Binary, Display, LowerExp, LowerHex, Octal, UpperHex, Write},
+    ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Rem, Shl, Shr, Sub},
     sync::Arc,
 };
 
+use chrono::{
+    format::{DelayedFormat, StrftimeItems},
+    FixedOffset, NaiveDate, NaiveTime,
+};
 use num_traits::{
-    CheckedAdd, CheckedDiv, CheckedMul, CheckedNeg, CheckedRem, CheckedSub, Float, Signed, Unsigned,
+    CheckedAdd, CheckedDiv, CheckedMul, CheckedNeg, CheckedRem, CheckedShl, CheckedShr, CheckedSub,
+    Float, PrimInt, SaturatingAdd, SaturatingMul, SaturatingSub, Signed, ToPrimitive, Unsigned,
+    WrappingAdd, WrappingMul, WrappingNeg, WrappingSub, Zero,
 };
 use once_cell::sync::Lazy;
 
 use crate::{
-    primitives::{LazyGenericProc, ANY_T, BOOL_T, STR_T, TYPE_T},
+    errors::{ZError, ZResult},
+    primitives::{
+        overflow_mode, LazyGenericProc, OverflowMode, ANY_T, BOOL_T, I64_T, OPT_T, STR_T, TYPE_T,
+    },
     types::{
         r#type::Type,
-        value::{BuiltinFunction, Proc, Value, ValueInner},
+        sym_table::InterpretSymTable,
+        value::{BuiltinFunction, HigherOrderFunction, Proc, Value, ValueInner},
     },
 };
 
-pub fn get_param<T: TryFrom<Value>>(x: &[Value], i: usize) -> Option<T> {
-    T::try_from(x[i].to_owned()).ok()
+pub fn get_param<T: TryFrom<Value>>(x: &[Value], i: usize) -> ZResult<T> {
+    x.get(i)
+        .and_then(|v| T::try_from(v.to_owned()).ok())
+        .ok_or_else(|| ZError::i001(x))
+}
+
+pub fn nullary<'a>(
+    h: &mut HashMap<&'a str, Value>,
+    n: &'a str,
+    f: Arc<BuiltinFunction>,
+    ret_ty: &'static Lazy<Arc<Type>>,
+) {
+    h.insert(
+        n,
+        Value::Proc(Proc::Builtin {
+            id: Arc::as_ptr(&f) as *const () as usize,
+            f,
+            ty: LazyGenericProc::new(vec![], ret_ty),
+        }),
+    );
 }
 
 pub fn unary<'a>(
@@ -39,28 +68,38 @@ pub fn unary<'a>(
     );
 }
 
-pub fn unary_signed_default<T: Signed + CheckedNeg + ValueInner>(
+pub fn unary_signed_default<T: PrimInt + Signed + CheckedNeg + WrappingNeg + ValueInner>(
     h: &mut HashMap<&str, Value>,
     this_ty: &'static Lazy<Arc<Type>>,
 ) {
     unary(
         h,
         "_un_add",
-        Arc::new(|x: &Vec<Value>| Some(x[0].to_owned())),
+        Arc::new(|x: &Vec<Value>| Ok(x[0].to_owned())),
         this_ty,
         this_ty,
     );
     unary(
         h,
         "_un_sub",
-        Arc::new(|x: &Vec<Value>| Some(get_param::<T>(x, 0)?.checked_neg()?.into())),
+        Arc::new(|x: &Vec<Value>| {
+            let a = get_param::<T>(x, 0)?;
+            match overflow_mode() {
+                OverflowMode::Checked => a
+                    .checked_neg()
+                    .map(Into::into)
+                    .ok_or_else(|| ZError::i002("_un_sub", x)),
+                OverflowMode::Wrapping => Ok(a.wrapping_neg().into()),
+                OverflowMode::Saturating => Ok(a.checked_neg().unwrap_or_else(T::max_value).into()),
+            }
+        }),
         this_ty,
         this_ty,
     );
     unary(
         h,
         "_not",
-        Arc::new(|x: &Vec<Value>| Some(get_param::<T>(x, 0)?.is_zero().into())),
+        Arc::new(|x: &Vec<Value>| Ok(get_param::<T>(x, 0)?.is_zero().into())),
         this_ty,
         &BOOL_T,
     );
@@ -73,14 +112,14 @@ pub fn unary_unsigned_default<T: Unsigned + ValueInner>(
     unary(
         h,
         "_un_add",
-        Arc::new(|x: &Vec<Value>| Some(x[0].to_owned())),
+        Arc::new(|x: &Vec<Value>| Ok(x[0].to_owned())),
         this_ty,
         this_ty,
     );
     unary(
         h,
         "_not",
-        Arc::new(|x: &Vec<Value>| Some(get_param::<T>(x, 0)?.is_zero().into())),
+        Arc::new(|x: &Vec<Value>| Ok(get_param::<T>(x, 0)?.is_zero().into())),
         this_ty,
         &BOOL_T,
     );
@@ -93,14 +132,14 @@ pub fn unary_float_default<T: Float + ValueInner>(
     unary(
         h,
         "_un_add",
-        Arc::new(|x: &Vec<Value>| Some(x[0].to_owned())),
+        Arc::new(|x: &Vec<Value>| Ok(x[0].to_owned())),
         this_ty,
         this_ty,
     );
     unary(
         h,
         "_un_sub",
-        Arc::new(|x: &Vec<Value>| Some(get_param::<T>(x, 0)?.neg().into())),
+        Arc::new(|x: &Vec<Value>| Ok(get_param::<T>(x, 0)?.neg().into())),
         this_ty,
         this_ty,
     );
@@ -108,7 +147,7 @@ pub fn unary_float_default<T: Float + ValueInner>(
         h,
         "_not",
         Arc::new(|x: &Vec<Value>| {
-            Some(
+            Ok(
                 (get_param::<T>(x, 0)?.is_zero() || get_param::<T>(x, 0)?.eq(&T::neg_zero()))
                     .into(),
             )
@@ -136,6 +175,48 @@ pub fn binary<'a>(
     );
 }
 
+pub fn nary<'a>(
+    h: &mut HashMap<&'a str, Value>,
+    n: &'a str,
+    f: Arc<BuiltinFunction>,
+    arg_tys: &[&'static Lazy<Arc<Type>>],
+    ret_ty: &'static Lazy<Arc<Type>>,
+) {
+    h.insert(
+        n,
+        Value::Proc(Proc::Builtin {
+            id: Arc::as_ptr(&f) as *const () as usize,
+            f,
+            ty: LazyGenericProc::new(arg_tys.to_vec(), ret_ty),
+        }),
+    );
+}
+
+pub fn higher_order<'a>(
+    h: &mut HashMap<&'a str, Value>,
+    n: &'a str,
+    f: Arc<HigherOrderFunction>,
+    arg_tys: &[&'static Lazy<Arc<Type>>],
+    ret_ty: &'static Lazy<Arc<Type>>,
+) {
+    h.insert(
+        n,
+        Value::Proc(Proc::HigherOrder {
+            id: Arc::as_ptr(&f) as *const () as usize,
+            f,
+            ty: LazyGenericProc::new(arg_tys.to_vec(), ret_ty),
+        }),
+    );
+}
+
+/// Calls a proc that decides something about a value, erroring if it doesn't give a `bool`
+pub fn call_predicate(f: &Proc, v: &Value, val_symt: &mut InterpretSymTable) -> ZResult<bool> {
+    match f.call(vec![v.to_owned()], val_symt)? {
+        Value::Bool(b) => Ok(b),
+        v => Err(ZError::t011(&BOOL_T, &v.ty())),
+    }
+}
+
 #[macro_export]
 macro_rules! typecast_int {
     ($v:ty => str, $x:ident) => {
@@ -154,16 +235,32 @@ macro_rules! typecast_int {
         Value::F16(f16::from_f64(get_param::<$v>($x, 0)? as f64))
     };
     (big $v:ty => f64, $x:ident) => {
-        Value::F64(get_param::<$v>($x, 0)?.to_f64()?)
+        Value::F64(
+            get_param::<$v>($x, 0)?
+                .to_f64()
+                .ok_or_else(|| $crate::errors::ZError::i001($x))?,
+        )
     };
     (big $v:ty => f32, $x:ident) => {
-        Value::F32(get_param::<$v>($x, 0)?.to_f32()?)
+        Value::F32(
+            get_param::<$v>($x, 0)?
+                .to_f32()
+                .ok_or_else(|| $crate::errors::ZError::i001($x))?,
+        )
     };
     (big $v:ty => f16, $x:ident) => {
-        Value::F16(f16::from_f64(get_param::<$v>($x, 0)?.to_f64()?))
+        Value::F16(f16::from_f64(
+            get_param::<$v>($x, 0)?
+                .to_f64()
+                .ok_or_else(|| $crate::errors::ZError::i001($x))?,
+        ))
     };
     ($v:ty => $vo:ident, $x:ident) => {
-        Value::$vo(get_param::<$v>($x, 0)?.try_into().ok()?)
+        Value::$vo(
+            get_param::<$v>($x, 0)?
+                .try_into()
+                .map_err(|_| $crate::errors::ZError::i001($x))?,
+        )
     };
 }
 
@@ -182,7 +279,11 @@ macro_rules! typecast_float {
         Value::F16(f16::from_f64(get_param::<f64>($x, 0)?))
     };
     ($v:ty => $vo:ident $f:ident, $x:ident) => {
-        Value::$vo(get_param::<$v>($x, 0)?.$f()?)
+        Value::$vo(
+            get_param::<$v>($x, 0)?
+                .$f()
+                .ok_or_else(|| $crate::errors::ZError::i001($x))?,
+        )
     };
 }
 
@@ -195,52 +296,176 @@ pub fn arith_opr<'a, T: ValueInner>(
     binary(
         h,
         n,
-        Arc::new(|x: &Vec<Value>| Some(f(get_param::<T>(x, 0)?, get_param::<T>(x, 1)?).into())),
+        Arc::new(|x: &Vec<Value>| Ok(f(get_param::<T>(x, 0)?, get_param::<T>(x, 1)?).into())),
         this_ty,
         this_ty,
         this_ty,
     );
 }
 
-pub fn arith_opr_op<'a, T: ValueInner>(
-    h: &mut HashMap<&'a str, Value>,
-    n: &'a str,
-    f: &'static (dyn Fn(&T, &T) -> Option<T> + Send + Sync),
+pub fn overflow_opr<T: Zero + ValueInner>(
+    h: &mut HashMap<&str, Value>,
+    n: &'static str,
+    mode: Option<OverflowMode>,
+    divides: bool,
+    f: &'static (dyn Fn(&T, &T, OverflowMode) -> Option<T> + Send + Sync),
     this_ty: &'static Lazy<Arc<Type>>,
 ) {
     binary(
         h,
         n,
-        Arc::new(|x: &Vec<Value>| Some(f(&get_param::<T>(x, 0)?, &get_param::<T>(x, 1)?)?.into())),
+        Arc::new(move |x: &Vec<Value>| {
+            let (lhs, rhs) = (get_param::<T>(x, 0)?, get_param::<T>(x, 1)?);
+            if divides && rhs.is_zero() {
+                return Err(ZError::i003(x));
+            }
+            f(&lhs, &rhs, mode.unwrap_or_else(overflow_mode))
+                .map(Into::into)
+                .ok_or_else(|| ZError::i002(n, x))
+        }),
+        this_ty,
         this_ty,
         this_ty,
+    );
+}
+
+pub fn checked_opr<T: Zero + ValueInner>(
+    h: &mut HashMap<&str, Value>,
+    n: &'static str,
+    f: &'static (dyn Fn(&T, &T, OverflowMode) -> Option<T> + Send + Sync),
+    this_ty: &'static Lazy<Arc<Type>>,
+) {
+    binary(
+        h,
+        n,
+        Arc::new(|x: &Vec<Value>| {
+            let (lhs, rhs) = (get_param::<T>(x, 0)?, get_param::<T>(x, 1)?);
+            Ok(Value::Opt(
+                f(&lhs, &rhs, OverflowMode::Checked).map(|v| Box::new(v.into())),
+            ))
+        }),
+        this_ty,
         this_ty,
+        &OPT_T,
     );
 }
 
 pub fn arith_opr_default<
-    T: CheckedAdd + CheckedSub + CheckedMul + CheckedDiv + CheckedRem + ValueInner,
+    T: PrimInt
+        + CheckedRem
+        + WrappingAdd
+        + WrappingSub
+        + WrappingMul
+        + SaturatingAdd
+        + SaturatingSub
+        + SaturatingMul
+        + ValueInner,
 >(
     h: &mut HashMap<&str, Value>,
     this_ty: &'static Lazy<Arc<Type>>,
 ) {
-    arith_opr_op(h, "_add", &T::checked_add, this_ty);
-    arith_opr_op(h, "_sub", &T::checked_sub, this_ty);
-    arith_opr_op(h, "_mul", &T::checked_mul, this_ty);
-    arith_opr_op(h, "_div", &T::checked_div, this_ty);
-    arith_opr_op(h, "_rem", &T::checked_rem, this_ty);
+    let add: &(dyn Fn(&T, &T, OverflowMode) -> Option<T> + Send + Sync) = &|a, b, mode| match mode {
+        OverflowMode::Checked => a.checked_add(b),
+        OverflowMode::Wrapping => Some(a.wrapping_add(b)),
+        OverflowMode::Saturating => Some(SaturatingAdd::saturating_add(a, b)),
+    };
+    let sub: &(dyn Fn(&T, &T, OverflowMode) -> Option<T> + Send + Sync) = &|a, b, mode| match mode {
+        OverflowMode::Checked => a.checked_sub(b),
+        OverflowMode::Wrapping => Some(a.wrapping_sub(b)),
+        OverflowMode::Saturating => Some(SaturatingSub::saturating_sub(a, b)),
+    };
+    let mul: &(dyn Fn(&T, &T, OverflowMode) -> Option<T> + Send + Sync) = &|a, b, mode| match mode {
+        OverflowMode::Checked => a.checked_mul(b),
+        OverflowMode::Wrapping => Some(a.wrapping_mul(b)),
+        OverflowMode::Saturating => Some(a.saturating_mul(b)),
+    };
+    // the only overflowing division is MIN / -1
+    let div: &(dyn Fn(&T, &T, OverflowMode) -> Option<T> + Send + Sync) = &|a, b, mode| match mode {
+        OverflowMode::Checked => a.checked_div(b),
+        OverflowMode::Wrapping => Some(a.checked_div(b).unwrap_or(*a)),
+        OverflowMode::Saturating => Some(a.checked_div(b).unwrap_or_else(T::max_value)),
+    };
+    let rem: &(dyn Fn(&T, &T, OverflowMode) -> Option<T> + Send + Sync) = &|a, b, mode| match mode {
+        OverflowMode::Checked => a.checked_rem(b),
+        OverflowMode::Wrapping | OverflowMode::Saturating => {
+            Some(a.checked_rem(b).unwrap_or_else(T::zero))
+        }
+    };
+
+    overflow_opr(h, "_add", None, false, add, this_ty);
+    overflow_opr(h, "_sub", None, false, sub, this_ty);
+    overflow_opr(h, "_mul", None, false, mul, this_ty);
+    overflow_opr(h, "_div", None, true, div, this_ty);
+    overflow_opr(h, "_rem", None, true, rem, this_ty);
+
+    let wrapping = Some(OverflowMode::Wrapping);
+    overflow_opr(h, "wrapping_add", wrapping, false, add, this_ty);
+    overflow_opr(h, "wrapping_sub", wrapping, false, sub, this_ty);
+    overflow_opr(h, "wrapping_mul", wrapping, false, mul, this_ty);
+    overflow_opr(h, "wrapping_div", wrapping, true, div, this_ty);
+    overflow_opr(h, "wrapping_rem", wrapping, true, rem, this_ty);
+
+    let saturating = Some(OverflowMode::Saturating);
+    overflow_opr(h, "saturating_add", saturating, false, add, this_ty);
+    overflow_opr(h, "saturating_sub", saturating, false, sub, this_ty);
+    overflow_opr(h, "saturating_mul", saturating, false, mul, this_ty);
+    overflow_opr(h, "saturating_div", saturating, true, div, this_ty);
+    overflow_opr(h, "saturating_rem", saturating, true, rem, this_ty);
+
+    checked_opr(h, "checked_add", add, this_ty);
+    checked_opr(h, "checked_sub", sub, this_ty);
+    checked_opr(h, "checked_mul", mul, this_ty);
+    checked_opr(h, "checked_div", div, this_ty);
+    checked_opr(h, "checked_rem", rem, this_ty);
 }
 
 pub fn arith_opr_big_default<
-    T: CheckedAdd + CheckedSub + CheckedMul + CheckedDiv + Rem<T> + ValueInner,
+    T: CheckedAdd + CheckedSub + CheckedMul + CheckedDiv + Rem<Output = T> + Zero + Clone + ValueInner,
 >(
     h: &mut HashMap<&str, Value>,
     this_ty: &'static Lazy<Arc<Type>>,
 ) {
-    arith_opr_op(h, "_add", &T::checked_add, this_ty);
-    arith_opr_op(h, "_sub", &T::checked_sub, this_ty);
-    arith_opr_op(h, "_mul", &T::checked_mul, this_ty);
-    arith_opr_op(h, "_div", &T::checked_div, this_ty);
+    // big integers only overflow below zero, which no mode can represent
+    overflow_opr(
+        h,
+        "_add",
+        None,
+        false,
+        &|a: &T, b, _| a.checked_add(b),
+        this_ty,
+    );
+    overflow_opr(
+        h,
+        "_sub",
+        None,
+        false,
+        &|a: &T, b, _| a.checked_sub(b),
+        this_ty,
+    );
+    overflow_opr(
+        h,
+        "_mul",
+        None,
+        false,
+        &|a: &T, b, _| a.checked_mul(b),
+        this_ty,
+    );
+    overflow_opr(
+        h,
+        "_div",
+        None,
+        true,
+        &|a: &T, b, _| a.checked_div(b),
+        this_ty,
+    );
+    overflow_opr(
+        h,
+        "_rem",
+        None,
+        true,
+        &|a: &T, b: &T, _| Some(a.to_owned() % b.to_owned()),
+        this_ty,
+    );
 }
 
 pub fn arith_opr_float_default<T: Float + ValueInner>(
@@ -254,6 +479,133 @@ pub fn arith_opr_float_default<T: Float + ValueInner>(
     arith_opr(h, "_rem", &Rem::<T>::rem, this_ty);
 }
 
+pub fn shift_opr<T: ValueInner>(
+    h: &mut HashMap<&str, Value>,
+    n: &'static str,
+    f: &'static (dyn Fn(&T, u32, OverflowMode) -> Option<T> + Send + Sync),
+    this_ty: &'static Lazy<Arc<Type>>,
+) {
+    binary(
+        h,
+        n,
+        Arc::new(move |x: &Vec<Value>| {
+            let amount = get_param::<i64>(x, 1)?;
+            let Ok(b) = u32::try_from(amount) else {
+                return Err(ZError::i014(n, amount));
+            };
+            f(&get_param::<T>(x, 0)?, b, overflow_mode())
+                .map(Into::into)
+                .ok_or_else(|| ZError::i002(n, x))
+        }),
+        this_ty,
+        &I64_T,
+        this_ty,
+    );
+}
+
+pub fn bit_opr_default<T: PrimInt + CheckedShl + CheckedShr + ValueInner>(
+    h: &mut HashMap<&str, Value>,
+    this_ty: &'static Lazy<Arc<Type>>,
+) {
+    arith_opr(h, "_and", &BitAnd::<T>::bitand, this_ty);
+    arith_opr(h, "_or", &BitOr::<T>::bitor, this_ty);
+    arith_opr(h, "_xor", &BitXor::<T>::bitxor, this_ty);
+    unary(
+        h,
+        "_compl",
+        Arc::new(|x: &Vec<Value>| Ok(get_param::<T>(x, 0)?.not().into())),
+        this_ty,
+        this_ty,
+    );
+    // a left shift overflows if shifting back doesn't give the same number
+    shift_opr(
+        h,
+        "_lsh",
+        &|a: &T, b, mode| {
+            let shifted = a.checked_shl(b).filter(|r| r.checked_shr(b) == Some(*a));
+            match mode {
+                OverflowMode::Checked => shifted,
+                OverflowMode::Wrapping => Some(a.checked_shl(b).unwrap_or_else(T::zero)),
+                OverflowMode::Saturating => Some(shifted.unwrap_or_else(|| {
+                    if a.is_zero() {
+                        T::zero()
+                    } else if *a < T::zero() {
+                        T::min_value()
+                    } else {
+                        T::max_value()
+                    }
+                })),
+            }
+        },
+        this_ty,
+    );
+    // right shifts by the width or more leave only the sign
+    shift_opr(
+        h,
+        "_rsh",
+        &|a: &T, b, _| {
+            Some(a.checked_shr(b).unwrap_or_else(|| {
+                if *a < T::zero() {
+                    !T::zero()
+                } else {
+                    T::zero()
+                }
+            }))
+        },
+        this_ty,
+    );
+    shift_opr(
+        h,
+        "_zrsh",
+        &|a: &T, b, _| {
+            Some(if b < T::zero().count_zeros() {
+                a.unsigned_shr(b)
+            } else {
+                T::zero()
+            })
+        },
+        this_ty,
+    );
+}
+
+pub fn bit_opr_big_default<
+    T: BitAnd<Output = T>
+        + BitOr<Output = T>
+        + BitXor<Output = T>
+        + Shl<usize, Output = T>
+        + Shr<usize, Output = T>
+        + Zero
+        + PartialOrd
+        + Clone
+        + ToPrimitive
+        + ValueInner,
+>(
+    h: &mut HashMap<&str, Value>,
+    this_ty: &'static Lazy<Arc<Type>>,
+) {
+    arith_opr(h, "_and", &BitAnd::<T>::bitand, this_ty);
+    arith_opr(h, "_or", &BitOr::<T>::bitor, this_ty);
+    arith_opr(h, "_xor", &BitXor::<T>::bitxor, this_ty);
+    shift_opr(
+        h,
+        "_lsh",
+        &|a: &T, b, _| Some(a.to_owned() << b.to_usize()?),
+        this_ty,
+    );
+    shift_opr(
+        h,
+        "_rsh",
+        &|a: &T, b, _| Some(a.to_owned() >> b.to_usize()?),
+        this_ty,
+    );
+    shift_opr(
+        h,
+        "_zrsh",
+        &|a: &T, b, _| (*a >= T::zero()).then(|| a.to_owned() >> b as usize),
+        this_ty,
+    );
+}
+
 pub fn comp_opr<'a, T: ValueInner>(
     h: &mut HashMap<&'a str, Value>,
     n: &'a str,
@@ -263,7 +615,7 @@ pub fn comp_opr<'a, T: ValueInner>(
     binary(
         h,
         n,
-        Arc::new(|x: &Vec<Value>| Some(f(&get_param::<T>(x, 0)?, &get_param::<T>(x, 1)?).into())),
+        Arc::new(|x: &Vec<Value>| Ok(f(&get_param::<T>(x, 0)?, &get_param::<T>(x, 1)?).into())),
         this_ty,
         this_ty,
         &BOOL_T,
@@ -286,13 +638,153 @@ pub fn concat(h: &mut HashMap<&str, Value>, this_ty: &'static Lazy<Arc<Type>>) {
     binary(
         h,
         "_concat",
-        Arc::new(|x: &Vec<Value>| Some(Value::Str(format!("{}{}", x[0], x[1])))),
+        Arc::new(|x: &Vec<Value>| Ok(Value::Str(format!("{}{}", x[0], x[1])))),
         this_ty,
         &ANY_T,
         &STR_T,
     );
 }
 
+/// Parses a format spec of the form `[0][width][.precision][x|X|o|b|e]`
+fn format_spec(spec: &str) -> Option<(bool, usize, Option<usize>, &str)> {
+    let (_, zero, width, precision, kind) =
+        lazy_regex::regex_captures!(r"^(0?)([0-9]*)(?:\.([0-9]+))?([xXobe]?)$", spec)?;
+    Some((
+        !zero.is_empty(),
+        width.parse().unwrap_or(0),
+        precision.parse().ok(),
+        kind,
+    ))
+}
+
+/// The format spec argument of integers' and floats' `_fmt`, which is checked against the value
+/// when compiling if it's a literal, as f-strings' are
+pub static INT_SPEC_T: Lazy<Arc<Type>> = Lazy::new(|| Arc::new(Type::Any));
+pub static FLOAT_SPEC_T: Lazy<Arc<Type>> = Lazy::new(|| Arc::new(Type::Any));
+
+/// Whether `spec` can format an integer, or a float if `float`, which can't be in hex, octal or
+/// binary while integers can't have a precision
+#[must_use]
+pub fn spec_is_valid(spec: &str, float: bool) -> bool {
+    match format_spec(spec) {
+        Some((_, _, _, kind)) if float => matches!(kind, "" | "e"),
+        Some((_, _, precision, kind)) => {
+            precision.is_none() && matches!(kind, "" | "x" | "X" | "o" | "b")
+        }
+        None => false,
+    }
+}
+
+fn pad_formatted(s: &str, zero: bool, width: usize) -> String {
+    if zero {
+        let (sign, digits) = s.strip_prefix('-').map_or(("", s), |digits| ("-", digits));
+        format!("{sign}{digits:0>0$}", width.saturating_sub(sign.len()))
+    } else {
+        format!("{s:>width$}")
+    }
+}
+
+pub fn fmt_int_default<T: Display + LowerHex + UpperHex + Octal + Binary + ValueInner>(
+    h: &mut HashMap<&str, Value>,
+    this_ty: &'static Lazy<Arc<Type>>,
+) {
+    binary(
+        h,
+        "_fmt",
+        Arc::new(|x: &Vec<Value>| {
+            let v = get_param::<T>(x, 0)?;
+            let spec = get_param::<String>(x, 1)?;
+            let Some((zero, width, None, kind)) = format_spec(&spec) else {
+                return Err(ZError::i005(&spec, &x[0]));
+            };
+            let s = match kind {
+                "x" => format!("{v:x}"),
+                "X" => format!("{v:X}"),
+                "o" => format!("{v:o}"),
+                "b" => format!("{v:b}"),
+                "" => v.to_string(),
+                _ => return Err(ZError::i005(&spec, &x[0])),
+            };
+            Ok(Value::Str(pad_formatted(&s, zero, width)))
+        }),
+        this_ty,
+        &INT_SPEC_T,
+        &STR_T,
+    );
+}
+
+pub fn fmt_float_default<T: Display + LowerExp + ValueInner>(
+    h: &mut HashMap<&str, Value>,
+    this_ty: &'static Lazy<Arc<Type>>,
+) {
+    binary(
+        h,
+        "_fmt",
+        Arc::new(|x: &Vec<Value>| {
+            let v = get_param::<T>(x, 0)?;
+            let spec = get_param::<String>(x, 1)?;
+            let Some((zero, width, precision, kind)) = format_spec(&spec) else {
+                return Err(ZError::i005(&spec, &x[0]));
+            };
+            let s = match (precision, kind) {
+                (Some(precision), "") => format!("{v:.precision$}"),
+                (Some(precision), "e") => format!("{v:.precision$e}"),
+                (None, "") => v.to_string(),
+                (None, "e") => format!("{v:e}"),
+                _ => return Err(ZError::i005(&spec, &x[0])),
+            };
+            Ok(Value::Str(pad_formatted(&s, zero, width)))
+        }),
+        this_ty,
+        &FLOAT_SPEC_T,
+        &STR_T,
+    );
+}
+
+/// Formats a date, time or both with a strftime-style pattern, erroring instead of panicking if the
+/// pattern is invalid or asks for a part that the value doesn't have
+fn strftime(
+    date: Option<NaiveDate>,
+    time: Option<NaiveTime>,
+    offset: Option<FixedOffset>,
+    pattern: &str,
+) -> Option<String> {
+    let items = StrftimeItems::new(pattern).parse().ok()?;
+    let mut s = String::new();
+    match offset {
+        Some(offset) => write!(
+            s,
+            "{}",
+            DelayedFormat::new_with_offset(date, time, &offset, items.iter())
+        ),
+        None => write!(s, "{}", DelayedFormat::new(date, time, items.iter())),
+    }
+    .ok()?;
+    Some(s)
+}
+
+/// Adds `format`, which takes a strftime-style pattern
+pub fn fmt_strftime_default<T: ValueInner>(
+    h: &mut HashMap<&str, Value>,
+    this_ty: &'static Lazy<Arc<Type>>,
+    parts: fn(&T) -> (Option<NaiveDate>, Option<NaiveTime>, Option<FixedOffset>),
+) {
+    binary(
+        h,
+        "format",
+        Arc::new(move |x: &Vec<Value>| {
+            let (date, time, offset) = parts(&get_param::<T>(x, 0)?);
+            let pattern = get_param::<String>(x, 1)?;
+            strftime(date, time, offset, &pattern)
+                .map(Value::Str)
+                .ok_or_else(|| ZError::i005(&pattern, &x[0]))
+        }),
+        this_ty,
+        &STR_T,
+        &STR_T,
+    );
+}
+
 pub fn type_cast(
     h: &mut HashMap<&str, Value>,
     f: Arc<BuiltinFunction>,
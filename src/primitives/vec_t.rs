@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use tracing::trace;
+
+use crate::{errors::ZError, primitives::*, types::value::Value, Type};
+
+fn comp_opr_vec<'a>(h: &mut HashMap<&'a str, Value>, n: &'a str, eq: bool) {
+    binary(
+        h,
+        n,
+        Arc::new(move |x: &Vec<Value>| {
+            Ok(((get_param::<Vec<Value>>(x, 0)? == get_param::<Vec<Value>>(x, 1)?) == eq).into())
+        }),
+        &VEC_T,
+        &VEC_T,
+        &BOOL_T,
+    );
+}
+
+/// The position of an index in a vector of length `len`, if it's in `0..=len`
+fn index(i: i32, len: usize) -> Option<usize> {
+    usize::try_from(i).ok().filter(|i| *i <= len)
+}
+
+#[allow(clippy::cognitive_complexity, clippy::float_cmp)]
+fn vec_t() -> BuiltinType {
+    let mut h = HashMap::new();
+    trace!("Initialising vec");
+    h.insert("_default", Value::Vec(vec![]));
+    concat(&mut h, &VEC_T);
+    comp_opr_vec(&mut h, "_eq", true);
+    comp_opr_vec(&mut h, "_ne", false);
+    unary(
+        &mut h,
+        "len",
+        Arc::new(|x: &Vec<Value>| {
+            i32::try_from(get_param::<Vec<Value>>(x, 0)?.len())
+                .map(Value::I32)
+                .ok()
+                .ok_or_else(|| ZError::i002("len", x))
+        }),
+        &VEC_T,
+        &I32_T,
+    );
+    unary(
+        &mut h,
+        "is_empty",
+        Arc::new(|x: &Vec<Value>| Ok(get_param::<Vec<Value>>(x, 0)?.is_empty().into())),
+        &VEC_T,
+        &BOOL_T,
+    );
+    binary(
+        &mut h,
+        "get",
+        Arc::new(|x: &Vec<Value>| {
+            let v = get_param::<Vec<Value>>(x, 0)?;
+            Ok(Value::Opt(
+                usize::try_from(get_param::<i32>(x, 1)?)
+                    .ok()
+                    .and_then(|i| v.get(i))
+                    .map(|v| Box::new(v.to_owned())),
+            ))
+        }),
+        &VEC_T,
+        &I32_T,
+        &OPT_T,
+    );
+    unary(
+        &mut h,
+        "first",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(Value::Opt(
+                get_param::<Vec<Value>>(x, 0)?
+                    .first()
+                    .cloned()
+                    .map(Box::new),
+            ))
+        }),
+        &VEC_T,
+        &OPT_T,
+    );
+    unary(
+        &mut h,
+        "last",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(Value::Opt(
+                get_param::<Vec<Value>>(x, 0)?.last().cloned().map(Box::new),
+            ))
+        }),
+        &VEC_T,
+        &OPT_T,
+    );
+    // vectors are values, so these give a changed copy
+    binary(
+        &mut h,
+        "push",
+        Arc::new(|x: &Vec<Value>| {
+            let mut v = get_param::<Vec<Value>>(x, 0)?;
+            v.push(x[1].to_owned());
+            Ok(Value::Vec(v))
+        }),
+        &VEC_T,
+        &ANY_T,
+        &VEC_T,
+    );
+    binary(
+        &mut h,
+        "append",
+        Arc::new(|x: &Vec<Value>| {
+            let mut v = get_param::<Vec<Value>>(x, 0)?;
+            v.extend(get_param::<Vec<Value>>(x, 1)?);
+            Ok(Value::Vec(v))
+        }),
+        &VEC_T,
+        &VEC_T,
+        &VEC_T,
+    );
+    nary(
+        &mut h,
+        "set",
+        Arc::new(|x: &Vec<Value>| {
+            let mut v = get_param::<Vec<Value>>(x, 0)?;
+            let slot = usize::try_from(get_param::<i32>(x, 1)?)
+                .ok()
+                .and_then(|i| v.get_mut(i))
+                .ok_or_else(|| ZError::i007("set", x))?;
+            x[2].clone_into(slot);
+            Ok(Value::Vec(v))
+        }),
+        &[&VEC_T, &I32_T, &ANY_T],
+        &VEC_T,
+    );
+    nary(
+        &mut h,
+        "insert",
+        Arc::new(|x: &Vec<Value>| {
+            let mut v = get_param::<Vec<Value>>(x, 0)?;
+            let i = index(get_param(x, 1)?, v.len()).ok_or_else(|| ZError::i007("insert", x))?;
+            v.insert(i, x[2].to_owned());
+            Ok(Value::Vec(v))
+        }),
+        &[&VEC_T, &I32_T, &ANY_T],
+        &VEC_T,
+    );
+    binary(
+        &mut h,
+        "remove",
+        Arc::new(|x: &Vec<Value>| {
+            let mut v = get_param::<Vec<Value>>(x, 0)?;
+            let i = index(get_param(x, 1)?, v.len())
+                .filter(|i| *i < v.len())
+                .ok_or_else(|| ZError::i007("remove", x))?;
+            v.remove(i);
+            Ok(Value::Vec(v))
+        }),
+        &VEC_T,
+        &I32_T,
+        &VEC_T,
+    );
+    // from `start` up to but not including `end`
+    nary(
+        &mut h,
+        "slice",
+        Arc::new(|x: &Vec<Value>| {
+            let v = get_param::<Vec<Value>>(x, 0)?;
+            index(get_param(x, 1)?, v.len())
+                .zip(index(get_param(x, 2)?, v.len()))
+                .and_then(|(start, end)| v.get(start..end))
+                .map(|v| Value::Vec(v.to_vec()))
+                .ok_or_else(|| ZError::i007("slice", x))
+        }),
+        &[&VEC_T, &I32_T, &I32_T],
+        &VEC_T,
+    );
+    binary(
+        &mut h,
+        "contains",
+        Arc::new(|x: &Vec<Value>| Ok(get_param::<Vec<Value>>(x, 0)?.contains(&x[1]).into())),
+        &VEC_T,
+        &ANY_T,
+        &BOOL_T,
+    );
+    binary(
+        &mut h,
+        "index_of",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(Value::Opt(
+                get_param::<Vec<Value>>(x, 0)?
+                    .iter()
+                    .position(|v| *v == x[1])
+                    .and_then(|i| i32::try_from(i).ok())
+                    .map(|i| Box::new(Value::I32(i))),
+            ))
+        }),
+        &VEC_T,
+        &ANY_T,
+        &OPT_T,
+    );
+    unary(
+        &mut h,
+        "iter",
+        Arc::new(|x: &Vec<Value>| Ok(Value::Iter(Iter::new(IterSource::Vec(get_param(x, 0)?))))),
+        &VEC_T,
+        &ITER_T,
+    );
+
+    let typecast = Arc::new(|x: &Vec<Value>| {
+        Ok(match get_param::<Arc<ValueType>>(x, 1)? {
+            p if p == *TYPE_T_VAL => Value::Type(Arc::clone(&VEC_T_VAL)),
+            p if p == *STR_T_VAL => Value::Str(x[0].to_string()),
+            p if p == *BOOL_T_VAL => Value::Bool(!get_param::<Vec<Value>>(x, 0)?.is_empty()),
+            p if p == *VEC_T_VAL => x[0].to_owned(),
+            _ => return Err(ZError::i001(x)),
+        })
+    });
+    type_cast(&mut h, typecast, &VEC_T);
+
+    BuiltinType {
+        name: Some(Ident::new("vec")),
+        namespace: h.drain().map(|(k, v)| (k.into(), v)).collect(),
+        fields: HashMap::default(),
+        type_args: vec![],
+    }
+}
+
+pub static VEC_T: Lazy<Arc<Type>> = Lazy::new(|| Arc::new(vec_t().into()));
+pub static VEC_T_VAL: Lazy<Arc<ValueType>> = Lazy::new(|| Arc::new(vec_t().into()));
+
+use std::sync::Arc;
+
+use crate::{
+    ast::Ident,
+    primitives::utils::{binary, concat, get_param, nary, type_cast, unary},
+    types::r#type::{BuiltinType, ValueType},
+};
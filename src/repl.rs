@@ -11,6 +11,7 @@ use tracing::info;
 use crate::{
     ast::AstData,
     compile,
+    stdlib::take_exit_code,
     types::{
         sym_table::{InterpretSymTable, TypeCheckSymTable},
         value::Value,
@@ -36,7 +37,7 @@ pub fn repl() -> Result<()> {
     );
     println!("{}", "`;exit` to exit".cyan());
     println!("{}", "`;help` for more commands".cyan());
-    loop {
+    'repl: loop {
         print!("{in_symbol} ");
         io::stdout().flush()?;
         let input = rl.readline(&in_symbol);
@@ -93,6 +94,7 @@ pub fn repl() -> Result<()> {
                                 println!("{out_symbol}{}", format!("{result:?}").yellow());
                             }
                         }
+                        Err(_) if take_exit_code().is_some() => break 'repl,
                         Err(e) => {
                             e.print()?;
                         }
@@ -0,0 +1,689 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    sync::Arc,
+};
+
+use itertools::Itertools;
+use maplit::hashmap;
+use once_cell::sync::Lazy;
+use smol_str::SmolStr;
+use tracing::trace;
+
+use crate::{
+    ast::Ident,
+    errors::{ZError, ZResult},
+    file_importer::Module,
+    primitives::{
+        instance_namespace,
+        utils::{binary, get_param, nary, unary},
+        ANY_T, BOOL_T, BOOL_T_VAL, OPT_T, STR_T, STR_T_VAL, TYPE_T, VARGS_T,
+    },
+    stdlib::{sys, ter},
+    types::{
+        r#type::{Type, ValueType, ValueTypeDef},
+        value::{Proc, Value},
+    },
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Positional,
+    Flag,
+    Option,
+}
+
+/// An argument of a parser, which is kept in the parser's value as an `arg`
+struct Arg {
+    kind: Kind,
+    name: String,
+    short: Option<char>,
+    help: String,
+    ty: Arc<ValueType>,
+    default: Option<Value>,
+}
+
+/// A parser's arguments and subcommands, in the order they were added
+struct Parser {
+    name: String,
+    about: String,
+    args: Vec<Arg>,
+    commands: Vec<(Self, Value)>,
+}
+
+/// Why parsing stopped before the arguments were all read
+enum Stop {
+    Help(String),
+    Usage(String, String),
+    Err(ZError),
+}
+impl From<ZError> for Stop {
+    fn from(e: ZError) -> Self {
+        Self::Err(e)
+    }
+}
+
+fn struct_type(
+    name: &str,
+    fields: &[(&str, &'static Lazy<Arc<Type>>, Option<Value>)],
+) -> Arc<ValueType> {
+    Arc::new(ValueType::Type(Box::new(ValueTypeDef {
+        name: Some(Ident::new(name)),
+        namespace: instance_namespace(),
+        fields: fields
+            .iter()
+            .map(|(k, ty, _)| ((*k).into(), Arc::clone(ty)))
+            .collect(),
+        mutable_fields: HashSet::new(),
+        params: Some(
+            fields
+                .iter()
+                .map(|(k, _, default)| ((*k).into(), default.to_owned()))
+                .collect(),
+        ),
+        type_args: vec![],
+    })))
+}
+
+static ARG_T_VAL: Lazy<Arc<ValueType>> = Lazy::new(|| {
+    struct_type(
+        "arg",
+        &[
+            ("kind", &STR_T, None),
+            ("name", &STR_T, None),
+            ("short", &STR_T, None),
+            ("help", &STR_T, None),
+            ("ty", &TYPE_T, None),
+            ("default", &OPT_T, None),
+        ],
+    )
+});
+
+/// A struct of the program's name and description. The arguments and subcommands are kept in
+/// it as hidden attrs, `arg0`, `arg1`... and `cmd0`, `cmd1`..., in the order they were added
+pub static PARSER_T_VAL: Lazy<Arc<ValueType>> = Lazy::new(|| {
+    let mut ty = (*struct_type(
+        "parser",
+        &[
+            ("name", &STR_T, None),
+            ("about", &STR_T, Some(Value::Str(String::new()))),
+        ],
+    ))
+    .to_owned();
+    if let ValueType::Type(def) = &mut ty {
+        def.namespace.extend(parser_methods());
+    }
+    Arc::new(ty)
+});
+pub static PARSER_T: Lazy<Arc<Type>> = Lazy::new(|| PARSER_T_VAL.to_type());
+
+/// The values that a parser read, with flags as `bool`s and the subcommand's values alongside
+/// its parent's
+pub static ARGS_T_VAL: Lazy<Arc<ValueType>> = Lazy::new(|| {
+    let mut namespace = instance_namespace();
+    namespace.extend(args_methods());
+    Arc::new(ValueType::Type(Box::new(ValueTypeDef {
+        name: Some(Ident::new("args")),
+        namespace,
+        fields: HashMap::new(),
+        mutable_fields: HashSet::new(),
+        params: None,
+        type_args: vec![],
+    })))
+});
+pub static ARGS_T: Lazy<Arc<Type>> = Lazy::new(|| ARGS_T_VAL.to_type());
+
+fn attr<T: TryFrom<Value>>(attrs: &HashMap<String, Value>, k: &str) -> ZResult<T> {
+    attrs
+        .get(k)
+        .and_then(|v| T::try_from(v.to_owned()).ok())
+        .ok_or_else(|| ZError::i001(&[]))
+}
+
+impl Arg {
+    fn from_value(v: &Value) -> ZResult<Self> {
+        let Value::ClassInstance { attrs, .. } = v else {
+            return Err(ZError::i001(std::slice::from_ref(v)));
+        };
+        Ok(Self {
+            kind: match &*attr::<String>(attrs, "kind")? {
+                "flag" => Kind::Flag,
+                "option" => Kind::Option,
+                _ => Kind::Positional,
+            },
+            name: attr(attrs, "name")?,
+            short: attr::<String>(attrs, "short")?.chars().next(),
+            help: attr(attrs, "help")?,
+            ty: attr(attrs, "ty")?,
+            default: attr::<Option<Box<Value>>>(attrs, "default")?.map(|v| *v),
+        })
+    }
+    fn to_value(&self) -> Value {
+        let kind = match self.kind {
+            Kind::Positional => "positional",
+            Kind::Flag => "flag",
+            Kind::Option => "option",
+        };
+        Value::ClassInstance {
+            ty: Arc::clone(&ARG_T_VAL),
+            attrs: hashmap! {
+                "kind".into() => Value::Str(kind.into()),
+                "name".into() => Value::Str(self.name.to_owned()),
+                "short".into() => Value::Str(self.short.map(String::from).unwrap_or_default()),
+                "help".into() => Value::Str(self.help.to_owned()),
+                "ty".into() => Value::Type(Arc::clone(&self.ty)),
+                "default".into() => Value::Opt(self.default.to_owned().map(Box::new)),
+            },
+        }
+    }
+    /// How it's shown in the usage and help
+    fn display(&self) -> String {
+        match (self.kind, self.short) {
+            (Kind::Positional, _) if self.default.is_some() => format!("[{}]", self.name),
+            (Kind::Positional, _) => format!("<{}>", self.name),
+            (Kind::Flag, Some(short)) => format!("-{short}, --{}", self.name),
+            (Kind::Flag, None) => format!("    --{}", self.name),
+            (Kind::Option, Some(short)) => format!("-{short}, --{} <{}>", self.name, self.ty),
+            (Kind::Option, None) => format!("    --{} <{}>", self.name, self.ty),
+        }
+    }
+}
+
+impl Parser {
+    fn from_value(v: &Value) -> ZResult<Self> {
+        let Value::ClassInstance { attrs, .. } = v else {
+            return Err(ZError::i001(std::slice::from_ref(v)));
+        };
+        let hidden = |prefix: &str| {
+            (0..)
+                .map_while(|i| attrs.get(&format!("{prefix}{i}")))
+                .collect::<Vec<_>>()
+        };
+        Ok(Self {
+            name: attr(attrs, "name")?,
+            about: attr(attrs, "about")?,
+            args: hidden("arg")
+                .into_iter()
+                .map(Arg::from_value)
+                .collect::<ZResult<_>>()?,
+            commands: hidden("cmd")
+                .into_iter()
+                .map(|v| Ok((Self::from_value(v)?, v.to_owned())))
+                .collect::<ZResult<_>>()?,
+        })
+    }
+    fn to_value(&self) -> Value {
+        let mut attrs = hashmap! {
+            "name".into() => Value::Str(self.name.to_owned()),
+            "about".into() => Value::Str(self.about.to_owned()),
+        };
+        for (i, arg) in self.args.iter().enumerate() {
+            attrs.insert(format!("arg{i}"), arg.to_value());
+        }
+        for (i, (_, v)) in self.commands.iter().enumerate() {
+            attrs.insert(format!("cmd{i}"), v.to_owned());
+        }
+        Value::ClassInstance {
+            ty: Arc::clone(&PARSER_T_VAL),
+            attrs,
+        }
+    }
+    fn positionals(&self) -> impl Iterator<Item = &Arg> {
+        self.args.iter().filter(|a| a.kind == Kind::Positional)
+    }
+    fn usage(&self, prog: &str) -> String {
+        let mut usage = format!("{prog} [options]");
+        for arg in self.positionals() {
+            let _ = write!(usage, " {}", arg.display());
+        }
+        if !self.commands.is_empty() {
+            usage.push_str(" <command>");
+        }
+        usage
+    }
+    fn help(&self, prog: &str) -> String {
+        let mut help = String::new();
+        if !self.about.is_empty() {
+            let _ = write!(help, "{}\n\n", self.about);
+        }
+        let _ = write!(help, "Usage: {}", self.usage(prog));
+        let section = |help: &mut String, title: &str, rows: Vec<(String, String)>| {
+            if rows.is_empty() {
+                return;
+            }
+            let width = rows
+                .iter()
+                .map(|(k, _)| k.chars().count())
+                .max()
+                .unwrap_or(0);
+            let _ = write!(help, "\n\n{title}:");
+            for (k, v) in rows {
+                let _ = write!(help, "\n  {k:width$}  {v}");
+            }
+        };
+        let row = |arg: &Arg| {
+            let help = match &arg.default {
+                Some(default) if arg.kind != Kind::Flag => {
+                    format!("{} [default: {default}]", arg.help)
+                }
+                _ => arg.help.to_owned(),
+            };
+            (arg.display(), help.trim().to_owned())
+        };
+        section(
+            &mut help,
+            "Arguments",
+            self.positionals().map(row).collect(),
+        );
+        section(
+            &mut help,
+            "Options",
+            self.args
+                .iter()
+                .filter(|a| a.kind != Kind::Positional)
+                .map(row)
+                .chain([("-h, --help".into(), "Show this help".into())])
+                .collect(),
+        );
+        section(
+            &mut help,
+            "Commands",
+            self.commands
+                .iter()
+                .map(|(c, _)| (c.name.to_owned(), c.about.to_owned()))
+                .collect(),
+        );
+        help
+    }
+
+    /// The names of the arguments of its subcommands, and of theirs, which `parse` puts alongside
+    /// its own
+    fn command_arg_names(&self) -> Vec<&str> {
+        self.commands
+            .iter()
+            .flat_map(|(c, _)| c.args.iter().map(|a| &*a.name).chain(c.command_arg_names()))
+            .collect()
+    }
+
+    /// Adds an argument, erroring if its name or short name is taken, including by a subcommand
+    fn with_arg(mut self, arg: Arg, x: &[Value]) -> ZResult<Value> {
+        let taken = self
+            .args
+            .iter()
+            .any(|a| a.name == arg.name || (a.short.is_some() && a.short == arg.short))
+            || self.command_arg_names().contains(&&*arg.name);
+        // `command` is where `parse` puts the subcommand
+        if arg.name.is_empty()
+            || arg.name.starts_with('-')
+            || arg.name == "command"
+            || arg.short == Some('h')
+            || taken
+        {
+            return Err(ZError::i007("argparse", x));
+        }
+        self.args.push(arg);
+        Ok(self.to_value())
+    }
+
+    /// Reads `argv` into `out`, giving the path of the subcommand that was run
+    fn parse(
+        &self,
+        prog: &str,
+        argv: &[String],
+        out: &mut HashMap<String, Value>,
+    ) -> Result<Option<String>, Stop> {
+        let usage = |msg: String| Stop::Usage(msg, self.usage(prog));
+        for arg in &self.args {
+            match (&arg.default, arg.kind) {
+                (_, Kind::Flag) => {
+                    out.insert(arg.name.to_owned(), Value::Bool(false));
+                }
+                (Some(default), _) => {
+                    out.insert(arg.name.to_owned(), default.to_owned());
+                }
+                (None, _) => {}
+            }
+        }
+        let mut positionals = self.positionals();
+        let mut tokens = argv.iter();
+        let mut options_ended = false;
+        let mut given = HashSet::new();
+        let mut command = None;
+        while let Some(token) = tokens.next() {
+            let positional = options_ended
+                || !token.starts_with('-')
+                || token == "-"
+                || token.chars().nth(1).is_some_and(|c| c.is_ascii_digit());
+            let args = if positional {
+                // a subcommand's name is never taken as a positional argument
+                if let Some((sub, _)) = self.commands.iter().find(|(c, _)| c.name == *token) {
+                    let rest = tokens.as_slice();
+                    let path = sub.parse(&format!("{prog} {}", sub.name), rest, out)?;
+                    command = Some(path.map_or_else(
+                        || sub.name.to_owned(),
+                        |path| format!("{} {path}", sub.name),
+                    ));
+                    break;
+                }
+                let Some(arg) = positionals.next() else {
+                    return Err(usage(if self.commands.is_empty() {
+                        format!("unexpected argument `{token}`")
+                    } else {
+                        format!("unknown command `{token}`")
+                    }));
+                };
+                vec![(arg, Some(token.to_owned()))]
+            } else if token == "--" {
+                options_ended = true;
+                continue;
+            } else if token == "-h" || token == "--help" {
+                return Err(Stop::Help(self.help(prog)));
+            } else if let Some(long) = token.strip_prefix("--") {
+                let (name, value) = long
+                    .split_once('=')
+                    .map_or((long, None), |(k, v)| (k, Some(v.to_owned())));
+                let Some(arg) = self
+                    .args
+                    .iter()
+                    .find(|a| a.kind != Kind::Positional && a.name == name)
+                else {
+                    return Err(usage(format!("unknown option `--{name}`")));
+                };
+                vec![(arg, value)]
+            } else {
+                // short flags can be grouped, up to one option that takes the rest as its value
+                let mut args = vec![];
+                let mut chars = token.chars().skip(1);
+                while let Some(c) = chars.next() {
+                    let Some(arg) = self
+                        .args
+                        .iter()
+                        .find(|a| a.kind != Kind::Positional && a.short == Some(c))
+                    else {
+                        return Err(usage(format!("unknown option `-{c}`")));
+                    };
+                    if arg.kind == Kind::Option {
+                        let rest = chars.collect::<String>();
+                        args.push((arg, (!rest.is_empty()).then_some(rest)));
+                        break;
+                    }
+                    args.push((arg, None));
+                }
+                args
+            };
+            for (arg, value) in args {
+                given.insert(arg.name.to_owned());
+                let v = match arg.kind {
+                    Kind::Flag if value.is_some() => {
+                        return Err(usage(format!("`--{}` doesn't take a value", arg.name)));
+                    }
+                    Kind::Flag => Value::Bool(true),
+                    _ => {
+                        let Some(v) = value.or_else(|| tokens.next().cloned()) else {
+                            return Err(usage(format!("`--{}` needs a value", arg.name)));
+                        };
+                        convert(&v, &arg.ty).ok_or_else(|| {
+                            usage(format!(
+                                "invalid value `{v}` for `{}`, which should be `{}`",
+                                arg.name, arg.ty
+                            ))
+                        })?
+                    }
+                };
+                out.insert(arg.name.to_owned(), v);
+            }
+        }
+        if let Some(arg) = self
+            .positionals()
+            .find(|a| a.default.is_none() && !given.contains(&a.name))
+        {
+            return Err(usage(format!("missing argument <{}>", arg.name)));
+        }
+        if command.is_none() && !self.commands.is_empty() {
+            return Err(usage("missing command".into()));
+        }
+        Ok(command)
+    }
+}
+
+/// Converts an argument to the type it's declared with, through the type's cast from `str`
+fn convert(s: &str, ty: &Arc<ValueType>) -> Option<Value> {
+    if *ty == *STR_T_VAL {
+        return Some(Value::Str(s.into()));
+    }
+    let Some(Value::Proc(Proc::Builtin { f, .. })) =
+        STR_T_VAL.namespace().get("_typecast").cloned()
+    else {
+        return None;
+    };
+    f(&vec![Value::Str(s.into()), Value::Type(Arc::clone(ty))]).ok()
+}
+
+/// Reads `argv`, printing the help or the usage error and exiting if it can't be read
+fn parse(x: &[Value], argv: &[String]) -> ZResult<Value> {
+    let parser = Parser::from_value(&x[0])?;
+    let mut attrs = HashMap::new();
+    match parser.parse(&parser.name, argv, &mut attrs) {
+        Ok(command) => {
+            attrs.insert(
+                "command".into(),
+                Value::Opt(command.map(|c| Box::new(Value::Str(c)))),
+            );
+            Ok(Value::ClassInstance {
+                ty: Arc::clone(&ARGS_T_VAL),
+                attrs,
+            })
+        }
+        Err(Stop::Help(help)) => {
+            println!("{help}");
+            Err(sys::exit(0))
+        }
+        Err(Stop::Usage(msg, usage)) => {
+            ter::err(&format!(
+                "error: {msg}\n\nUsage: {usage}\n\nFor more information, try `--help`."
+            ));
+            Err(sys::exit(2))
+        }
+        Err(Stop::Err(e)) => Err(e),
+    }
+}
+
+#[allow(clippy::cognitive_complexity)]
+fn parser_methods() -> HashMap<SmolStr, Value> {
+    let mut h = HashMap::new();
+    trace!("Initialising argparse parser");
+    nary(
+        &mut h,
+        "positional",
+        Arc::new(|x: &Vec<Value>| {
+            let arg = Arg {
+                kind: Kind::Positional,
+                name: get_param(x, 1)?,
+                short: None,
+                help: get_param(x, 2)?,
+                ty: get_param(x, 3)?,
+                default: None,
+            };
+            Parser::from_value(&x[0])?.with_arg(arg, x)
+        }),
+        &[&PARSER_T, &STR_T, &STR_T, &TYPE_T],
+        &PARSER_T,
+    );
+    for (n, kind) in [("flag", Kind::Flag), ("option", Kind::Option)] {
+        let tys: &[_] = if kind == Kind::Flag {
+            &[&PARSER_T, &STR_T, &STR_T, &STR_T]
+        } else {
+            &[&PARSER_T, &STR_T, &STR_T, &STR_T, &TYPE_T]
+        };
+        nary(
+            &mut h,
+            n,
+            Arc::new(move |x: &Vec<Value>| {
+                let short = get_param::<String>(x, 2)?;
+                if short.chars().count() > 1 {
+                    return Err(ZError::i007(n, x));
+                }
+                let arg = Arg {
+                    kind,
+                    name: get_param(x, 1)?,
+                    short: short.chars().next(),
+                    help: get_param(x, 3)?,
+                    ty: if kind == Kind::Flag {
+                        Arc::clone(&BOOL_T_VAL)
+                    } else {
+                        get_param(x, 4)?
+                    },
+                    default: None,
+                };
+                Parser::from_value(&x[0])?.with_arg(arg, x)
+            }),
+            tys,
+            &PARSER_T,
+        );
+    }
+    // makes a positional argument optional
+    nary(
+        &mut h,
+        "default",
+        Arc::new(|x: &Vec<Value>| {
+            let mut parser = Parser::from_value(&x[0])?;
+            let name = get_param::<String>(x, 1)?;
+            let Some(arg) = parser.args.iter_mut().find(|a| a.name == name) else {
+                return Err(ZError::i007("default", x));
+            };
+            if x[2].value_ty() != arg.ty {
+                return Err(ZError::i007("default", x));
+            }
+            arg.default = Some(x[2].to_owned());
+            Ok(parser.to_value())
+        }),
+        &[&PARSER_T, &STR_T, &ANY_T],
+        &PARSER_T,
+    );
+    // the subcommand is run with the subparser's name, and its arguments can't share names with
+    // the parser's, since their values go in the same place
+    binary(
+        &mut h,
+        "subcommand",
+        Arc::new(|x: &Vec<Value>| {
+            let mut parser = Parser::from_value(&x[0])?;
+            let sub = Parser::from_value(&x[1])?;
+            let shared = sub
+                .args
+                .iter()
+                .map(|a| &*a.name)
+                .chain(sub.command_arg_names())
+                .any(|name| parser.args.iter().any(|a| a.name == name));
+            if sub.name.is_empty()
+                || parser.commands.iter().any(|(c, _)| c.name == sub.name)
+                || shared
+            {
+                return Err(ZError::i007("subcommand", x));
+            }
+            parser.commands.push((sub, x[1].to_owned()));
+            Ok(parser.to_value())
+        }),
+        &PARSER_T,
+        &PARSER_T,
+        &PARSER_T,
+    );
+    unary(
+        &mut h,
+        "help",
+        Arc::new(|x: &Vec<Value>| {
+            let parser = Parser::from_value(&x[0])?;
+            Ok(Value::Str(parser.help(&parser.name)))
+        }),
+        &PARSER_T,
+        &STR_T,
+    );
+    // the arguments the script was run with, after its path
+    unary(
+        &mut h,
+        "parse",
+        Arc::new(|x: &Vec<Value>| parse(x, sys::args().get(1..).unwrap_or_default())),
+        &PARSER_T,
+        &ARGS_T,
+    );
+    binary(
+        &mut h,
+        "parse_from",
+        Arc::new(|x: &Vec<Value>| {
+            let argv = x[1..].iter().map(ToString::to_string).collect_vec();
+            parse(x, &argv)
+        }),
+        &PARSER_T,
+        &VARGS_T,
+        &ARGS_T,
+    );
+
+    h.drain().map(|(k, v)| (k.into(), v)).collect()
+}
+
+fn args_methods() -> HashMap<SmolStr, Value> {
+    let mut h = HashMap::new();
+    trace!("Initialising argparse args");
+    binary(
+        &mut h,
+        "get",
+        Arc::new(|x: &Vec<Value>| {
+            let Value::ClassInstance { attrs, .. } = &x[0] else {
+                return Err(ZError::i001(x));
+            };
+            let v = attrs.get(&get_param::<String>(x, 1)?).cloned();
+            Ok(Value::Opt(v.map(Box::new)))
+        }),
+        &ARGS_T,
+        &STR_T,
+        &OPT_T,
+    );
+    binary(
+        &mut h,
+        "flag",
+        Arc::new(|x: &Vec<Value>| {
+            let Value::ClassInstance { attrs, .. } = &x[0] else {
+                return Err(ZError::i001(x));
+            };
+            Ok(Value::Bool(
+                attrs.get(&get_param::<String>(x, 1)?) == Some(&Value::Bool(true)),
+            ))
+        }),
+        &ARGS_T,
+        &STR_T,
+        &BOOL_T,
+    );
+    // the subcommand that was run, with the subcommands it's in separated by spaces
+    unary(
+        &mut h,
+        "command",
+        Arc::new(|x: &Vec<Value>| {
+            let Value::ClassInstance { attrs, .. } = &x[0] else {
+                return Err(ZError::i001(x));
+            };
+            Ok(attrs.get("command").cloned().unwrap_or(Value::Opt(None)))
+        }),
+        &ARGS_T,
+        &OPT_T,
+    );
+
+    h.drain().map(|(k, v)| (k.into(), v)).collect()
+}
+
+fn argparse() -> HashMap<SmolStr, Value> {
+    hashmap! {
+        "parser".into() => Value::Type(Arc::clone(&PARSER_T_VAL)),
+        "args".into() => Value::Type(Arc::clone(&ARGS_T_VAL)),
+    }
+}
+
+pub static ARGPARSE: Lazy<Arc<Module>> = Lazy::new(|| {
+    Arc::new(Module::builtin(
+        "argparse",
+        "std::argparse",
+        argparse(),
+        &hashmap! {
+            "parser".into() => Arc::clone(&PARSER_T),
+            "args".into() => Arc::clone(&ARGS_T),
+        },
+    ))
+});
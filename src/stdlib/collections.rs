@@ -0,0 +1,372 @@
+use std::{cmp::Ordering, collections::HashMap, sync::Arc};
+
+use itertools::Itertools;
+use maplit::hashmap;
+use once_cell::sync::Lazy;
+use smol_str::SmolStr;
+use tracing::trace;
+
+use crate::{
+    errors::{ZError, ZResult},
+    file_importer::Module,
+    primitives::{
+        utils::{binary, call_predicate, get_param, higher_order, unary},
+        Hmap, Iter, IterSource, ANY_T, BOOL_T, COMPARATOR_T, HMAP_T, HMAP_T_VAL, I32_T, ITER_T,
+        ITER_T_VAL, MAPPER_T, OPT_T, PREDICATE_T, REDUCER_T, VARGS_T, VEC_T, VEC_T_VAL,
+    },
+    types::{
+        sym_table::{InterpretFrameType, InterpretSymTable},
+        value::{Proc, Value},
+    },
+};
+
+/// Calls the method `name` of `this`'s type with `other`, as `this:.name(other)` does, if there is
+/// one
+fn call_method(
+    this: &Value,
+    name: &str,
+    other: &Value,
+    val_symt: &mut InterpretSymTable,
+) -> ZResult<Option<Value>> {
+    let ty = this.value_ty();
+    let Some(Value::Proc(f)) = ty.namespace().get(name).cloned() else {
+        return Ok(None);
+    };
+    let args = vec![this.to_owned(), other.to_owned()];
+    if let Proc::Defined { .. } = f {
+        val_symt.add_frame(InterpretFrameType::Normal);
+        val_symt.declare_val("$ty", Value::Type(Arc::clone(&ty)));
+        let res = f.call(args, val_symt);
+        val_symt.pop_frame()?;
+        res.map(Some)
+    } else {
+        f.call(args, val_symt).map(Some)
+    }
+}
+
+/// The order of an `i32` given by `_cmp` or a comparator, which is <0, 0 or >0
+fn ordering(v: &Value) -> ZResult<Ordering> {
+    match v {
+        Value::I32(i) => Ok(i.cmp(&0)),
+        v => Err(ZError::t011(&I32_T, &v.ty())),
+    }
+}
+
+/// Orders two values with their type's `_cmp`, or else its `_lt` and `_gt`, as `<` and `>` do,
+/// erroring if they're unordered like `NaN` is, since that would leave the result unsorted
+fn compare(a: &Value, b: &Value, val_symt: &mut InterpretSymTable) -> ZResult<Ordering> {
+    if let Some(res) = call_method(a, "_cmp", b, val_symt)? {
+        return ordering(&res);
+    }
+    for (name, ord) in [("_lt", Ordering::Less), ("_gt", Ordering::Greater)] {
+        match call_method(a, name, b, val_symt)? {
+            Some(Value::Bool(true)) => return Ok(ord),
+            Some(Value::Bool(false)) => {}
+            Some(v) => return Err(ZError::t011(&BOOL_T, &v.ty())),
+            None => return Err(ZError::t005(&a.ty(), name)),
+        }
+    }
+    match call_method(a, "_eq", b, val_symt)? {
+        Some(Value::Bool(false)) => Err(ZError::i007("_cmp", &[a.to_owned(), b.to_owned()])),
+        _ => Ok(Ordering::Equal),
+    }
+}
+
+/// A stable merge sort, which unlike `sort_by` can stop at the first error and doesn't rely on the
+/// order being consistent
+fn merge_sort(
+    mut v: Vec<Value>,
+    cmp: &mut dyn FnMut(&Value, &Value) -> ZResult<Ordering>,
+) -> ZResult<Vec<Value>> {
+    if v.len() <= 1 {
+        return Ok(v);
+    }
+    let right = v.split_off(v.len() / 2);
+    let mut left = merge_sort(v, cmp)?.into_iter().peekable();
+    let mut right = merge_sort(right, cmp)?.into_iter().peekable();
+    let mut out = Vec::with_capacity(left.len() + right.len());
+    while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+        // only taking from the right if it's strictly less keeps equal values in order
+        out.extend(if cmp(r, l)? == Ordering::Less {
+            right.next()
+        } else {
+            left.next()
+        });
+    }
+    out.extend(left.chain(right));
+    Ok(out)
+}
+
+/// The least value of a vector if `keep` is `Less`, or the greatest if it's `Greater`, taking the
+/// first of equal ones
+fn extreme(
+    v: Vec<Value>,
+    keep: Ordering,
+    val_symt: &mut InterpretSymTable,
+) -> ZResult<Option<Box<Value>>> {
+    let mut out: Option<Value> = None;
+    for e in v {
+        out = Some(match out {
+            Some(cur) if compare(&e, &cur, val_symt)? != keep => cur,
+            _ => e,
+        });
+    }
+    Ok(out.map(Box::new))
+}
+
+fn index(i: usize) -> Value {
+    // vectors of more than `i32::MAX` values can't be made
+    Value::I32(i32::try_from(i).unwrap_or(i32::MAX))
+}
+
+#[allow(clippy::cognitive_complexity)]
+fn collections() -> HashMap<SmolStr, Value> {
+    let mut h = HashMap::new();
+    trace!("Initialising std::collections");
+    h.insert("vec", Value::Type(Arc::clone(&VEC_T_VAL)));
+    h.insert("iter", Value::Type(Arc::clone(&ITER_T_VAL)));
+    h.insert("hmap", Value::Type(Arc::clone(&HMAP_T_VAL)));
+
+    unary(
+        &mut h,
+        "vec_of",
+        Arc::new(|x: &Vec<Value>| Ok(Value::Vec(x.to_owned()))),
+        &VARGS_T,
+        &VEC_T,
+    );
+    // keys and values alternate, and later values of a key replace earlier ones
+    unary(
+        &mut h,
+        "hmap_of",
+        Arc::new(|x: &Vec<Value>| {
+            if !x.len().is_multiple_of(2) {
+                return Err(ZError::i007("hmap_of", x));
+            }
+            let mut m = Hmap::default();
+            for (k, v) in x.iter().tuples() {
+                m.set(k.to_owned(), v.to_owned());
+            }
+            Ok(Value::Hmap(m))
+        }),
+        &VARGS_T,
+        &HMAP_T,
+    );
+    // from `start` up to but not including `end`
+    binary(
+        &mut h,
+        "range",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(Value::Iter(Iter::new(IterSource::Range {
+                start: get_param(x, 0)?,
+                end: Some(get_param(x, 1)?),
+            })))
+        }),
+        &I32_T,
+        &I32_T,
+        &ITER_T,
+    );
+    // goes on until `take` or a proc that takes values stops it, or up to `i32::MAX`
+    unary(
+        &mut h,
+        "count_from",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(Value::Iter(Iter::new(IterSource::Range {
+                start: get_param(x, 0)?,
+                end: None,
+            })))
+        }),
+        &I32_T,
+        &ITER_T,
+    );
+
+    higher_order(
+        &mut h,
+        "map",
+        Arc::new(|x: &Vec<Value>, val_symt: &mut InterpretSymTable| {
+            let f = get_param::<Proc>(x, 1)?;
+            get_param::<Vec<Value>>(x, 0)?
+                .into_iter()
+                .map(|v| f.call(vec![v], val_symt))
+                .collect::<ZResult<_>>()
+                .map(Value::Vec)
+        }),
+        &[&VEC_T, &MAPPER_T],
+        &VEC_T,
+    );
+    higher_order(
+        &mut h,
+        "filter",
+        Arc::new(|x: &Vec<Value>, val_symt: &mut InterpretSymTable| {
+            let f = get_param::<Proc>(x, 1)?;
+            let mut out = vec![];
+            for v in get_param::<Vec<Value>>(x, 0)? {
+                if call_predicate(&f, &v, val_symt)? {
+                    out.push(v);
+                }
+            }
+            Ok(Value::Vec(out))
+        }),
+        &[&VEC_T, &PREDICATE_T],
+        &VEC_T,
+    );
+    // none if the vector is empty
+    higher_order(
+        &mut h,
+        "reduce",
+        Arc::new(|x: &Vec<Value>, val_symt: &mut InterpretSymTable| {
+            let f = get_param::<Proc>(x, 1)?;
+            let mut values = get_param::<Vec<Value>>(x, 0)?.into_iter();
+            let Some(mut acc) = values.next() else {
+                return Ok(Value::Opt(None));
+            };
+            for v in values {
+                acc = f.call(vec![acc, v], val_symt)?;
+            }
+            Ok(Value::Opt(Some(Box::new(acc))))
+        }),
+        &[&VEC_T, &REDUCER_T],
+        &OPT_T,
+    );
+    higher_order(
+        &mut h,
+        "fold",
+        Arc::new(|x: &Vec<Value>, val_symt: &mut InterpretSymTable| {
+            let f = get_param::<Proc>(x, 2)?;
+            get_param::<Vec<Value>>(x, 0)?
+                .into_iter()
+                .try_fold(x[1].to_owned(), |acc, v| f.call(vec![acc, v], val_symt))
+        }),
+        &[&VEC_T, &ANY_T, &REDUCER_T],
+        &ANY_T,
+    );
+    higher_order(
+        &mut h,
+        "sort",
+        Arc::new(|x: &Vec<Value>, val_symt: &mut InterpretSymTable| {
+            merge_sort(get_param(x, 0)?, &mut |a, b| compare(a, b, val_symt)).map(Value::Vec)
+        }),
+        &[&VEC_T],
+        &VEC_T,
+    );
+    // the comparator gives <0, 0 or >0, like `_cmp`
+    higher_order(
+        &mut h,
+        "sort_by",
+        Arc::new(|x: &Vec<Value>, val_symt: &mut InterpretSymTable| {
+            let f = get_param::<Proc>(x, 1)?;
+            merge_sort(get_param(x, 0)?, &mut |a, b| {
+                ordering(&f.call(vec![a.to_owned(), b.to_owned()], val_symt)?)
+            })
+            .map(Value::Vec)
+        }),
+        &[&VEC_T, &COMPARATOR_T],
+        &VEC_T,
+    );
+    // the index of the value in a sorted vector, or none if it isn't in it
+    higher_order(
+        &mut h,
+        "binary_search",
+        Arc::new(|x: &Vec<Value>, val_symt: &mut InterpretSymTable| {
+            let v = get_param::<Vec<Value>>(x, 0)?;
+            let (mut lo, mut hi) = (0, v.len());
+            while lo < hi {
+                let mid = lo + (hi - lo) / 2;
+                match compare(&v[mid], &x[1], val_symt)? {
+                    Ordering::Less => lo = mid + 1,
+                    Ordering::Greater => hi = mid,
+                    Ordering::Equal => return Ok(Value::Opt(Some(Box::new(index(mid))))),
+                }
+            }
+            Ok(Value::Opt(None))
+        }),
+        &[&VEC_T, &ANY_T],
+        &OPT_T,
+    );
+    // pairs are vectors of two, and there are as many as there are values in the shorter vector
+    binary(
+        &mut h,
+        "zip",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(Value::Vec(
+                get_param::<Vec<Value>>(x, 0)?
+                    .into_iter()
+                    .zip(get_param::<Vec<Value>>(x, 1)?)
+                    .map(|(a, b)| Value::Vec(vec![a, b]))
+                    .collect(),
+            ))
+        }),
+        &VEC_T,
+        &VEC_T,
+        &VEC_T,
+    );
+    unary(
+        &mut h,
+        "enumerate",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(Value::Vec(
+                get_param::<Vec<Value>>(x, 0)?
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, v)| Value::Vec(vec![index(i), v]))
+                    .collect(),
+            ))
+        }),
+        &VEC_T,
+        &VEC_T,
+    );
+    unary(
+        &mut h,
+        "reverse",
+        Arc::new(|x: &Vec<Value>| {
+            let mut v = get_param::<Vec<Value>>(x, 0)?;
+            v.reverse();
+            Ok(Value::Vec(v))
+        }),
+        &VEC_T,
+        &VEC_T,
+    );
+    for (n, all) in [("any", false), ("all", true)] {
+        higher_order(
+            &mut h,
+            n,
+            Arc::new(move |x: &Vec<Value>, val_symt: &mut InterpretSymTable| {
+                let f = get_param::<Proc>(x, 1)?;
+                for v in get_param::<Vec<Value>>(x, 0)? {
+                    if call_predicate(&f, &v, val_symt)? != all {
+                        return Ok(Value::Bool(!all));
+                    }
+                }
+                Ok(Value::Bool(all))
+            }),
+            &[&VEC_T, &PREDICATE_T],
+            &BOOL_T,
+        );
+    }
+    // none if the vector is empty
+    for (n, keep) in [("min", Ordering::Less), ("max", Ordering::Greater)] {
+        higher_order(
+            &mut h,
+            n,
+            Arc::new(move |x: &Vec<Value>, val_symt: &mut InterpretSymTable| {
+                extreme(get_param(x, 0)?, keep, val_symt).map(Value::Opt)
+            }),
+            &[&VEC_T],
+            &OPT_T,
+        );
+    }
+
+    h.drain().map(|(k, v)| (k.into(), v)).collect()
+}
+
+pub static COLLECTIONS: Lazy<Arc<Module>> = Lazy::new(|| {
+    Arc::new(Module::builtin(
+        "collections",
+        "std::collections",
+        collections(),
+        &hashmap! {
+            "vec".into() => Arc::clone(&VEC_T),
+            "iter".into() => Arc::clone(&ITER_T),
+            "hmap".into() => Arc::clone(&HMAP_T),
+        },
+    ))
+});
@@ -0,0 +1,217 @@
+use std::{collections::HashMap, sync::Arc, time::SystemTime};
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, ParseError, TimeDelta};
+use maplit::hashmap;
+use once_cell::sync::Lazy;
+use smol_str::SmolStr;
+use tracing::trace;
+
+use crate::{
+    errors::ZError,
+    file_importer::Module,
+    primitives::{
+        utils::{binary, get_param, nary, nullary, unary},
+        DATETIME_T, DATETIME_T_VAL, DATE_T, DATE_T_VAL, DURATION_T, DURATION_T_VAL, F64_T, I32_T,
+        STR_T, TIME_T, TIME_T_VAL,
+    },
+    types::value::Value,
+};
+
+/// A duration of a number of seconds, if it's finite and in range
+fn duration_of(secs: f64) -> Option<TimeDelta> {
+    let d = TimeDelta::from_std(std::time::Duration::try_from_secs_f64(secs.abs()).ok()?).ok()?;
+    Some(if secs < 0.0 { -d } else { d })
+}
+
+/// Parses a datetime in RFC 3339 (ISO 8601 with an offset), or without an offset as UTC
+fn parse(s: &str) -> Result<DateTime<FixedOffset>, ParseError> {
+    DateTime::parse_from_rfc3339(s).or_else(|e| {
+        s.parse::<NaiveDateTime>()
+            .ok()
+            .map(|dt| dt.and_utc().fixed_offset())
+            .ok_or(e)
+    })
+}
+
+/// Parses a datetime with a strftime-style pattern, taking it as UTC if the pattern has no offset
+/// and as midnight if it has no time
+fn parse_fmt(s: &str, pattern: &str) -> Result<DateTime<FixedOffset>, ParseError> {
+    DateTime::parse_from_str(s, pattern).or_else(|e| {
+        NaiveDateTime::parse_from_str(s, pattern)
+            .ok()
+            .or_else(|| {
+                NaiveDate::parse_from_str(s, pattern)
+                    .ok()
+                    .map(|d| d.and_time(NaiveTime::MIN))
+            })
+            .map(|dt| dt.and_utc().fixed_offset())
+            .ok_or(e)
+    })
+}
+
+#[allow(clippy::cognitive_complexity)]
+fn datetime() -> HashMap<SmolStr, Value> {
+    let mut h = HashMap::new();
+    trace!("Initialising std::datetime");
+    h.insert("datetime", Value::Type(Arc::clone(&DATETIME_T_VAL)));
+    h.insert("date", Value::Type(Arc::clone(&DATE_T_VAL)));
+    h.insert("time", Value::Type(Arc::clone(&TIME_T_VAL)));
+    h.insert("duration", Value::Type(Arc::clone(&DURATION_T_VAL)));
+
+    // in UTC, as the system's timezone isn't known
+    nullary(
+        &mut h,
+        "now",
+        Arc::new(|x: &Vec<Value>| {
+            let now = SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .ok()
+                .and_then(|d| TimeDelta::from_std(d).ok())
+                .and_then(|d| DateTime::UNIX_EPOCH.checked_add_signed(d))
+                .ok_or_else(|| ZError::i001(x))?;
+            Ok(Value::DateTime(now.fixed_offset()))
+        }),
+        &DATETIME_T,
+    );
+    unary(
+        &mut h,
+        "unix",
+        Arc::new(|x: &Vec<Value>| {
+            duration_of(get_param(x, 0)?)
+                .and_then(|d| DateTime::UNIX_EPOCH.checked_add_signed(d))
+                .map(|dt| Value::DateTime(dt.fixed_offset()))
+                .ok_or_else(|| ZError::i007("unix", x))
+        }),
+        &F64_T,
+        &DATETIME_T,
+    );
+    nary(
+        &mut h,
+        "from_ymd",
+        Arc::new(|x: &Vec<Value>| {
+            let (y, m, d) = (
+                get_param::<i32>(x, 0)?,
+                get_param::<i32>(x, 1)?,
+                get_param::<i32>(x, 2)?,
+            );
+            u32::try_from(m)
+                .ok()
+                .zip(u32::try_from(d).ok())
+                .and_then(|(m, d)| NaiveDate::from_ymd_opt(y, m, d))
+                .map(Value::Date)
+                .ok_or_else(|| ZError::i007("from_ymd", x))
+        }),
+        &[&I32_T, &I32_T, &I32_T],
+        &DATE_T,
+    );
+    nary(
+        &mut h,
+        "from_hms",
+        Arc::new(|x: &Vec<Value>| {
+            let (hour, min, sec) = (
+                get_param::<i32>(x, 0)?,
+                get_param::<i32>(x, 1)?,
+                get_param::<f64>(x, 2)?,
+            );
+            u32::try_from(hour)
+                .ok()
+                .zip(u32::try_from(min).ok())
+                .and_then(|(hour, min)| NaiveTime::from_hms_opt(hour, min, 0))
+                .zip(
+                    (0.0..60.0)
+                        .contains(&sec)
+                        .then(|| duration_of(sec))
+                        .flatten(),
+                )
+                .map(|(t, sec)| Value::Time(t.overflowing_add_signed(sec).0))
+                .ok_or_else(|| ZError::i007("from_hms", x))
+        }),
+        &[&I32_T, &I32_T, &F64_T],
+        &TIME_T,
+    );
+    for (n, secs) in [
+        ("millis", 0.001),
+        ("seconds", 1.0),
+        ("minutes", 60.0),
+        ("hours", 3600.0),
+        ("days", 86400.0),
+    ] {
+        unary(
+            &mut h,
+            n,
+            Arc::new(move |x: &Vec<Value>| {
+                duration_of(get_param::<f64>(x, 0)? * secs)
+                    .map(Value::Duration)
+                    .ok_or_else(|| ZError::i007(n, x))
+            }),
+            &F64_T,
+            &DURATION_T,
+        );
+    }
+
+    unary(
+        &mut h,
+        "parse",
+        Arc::new(|x: &Vec<Value>| {
+            let s = get_param::<String>(x, 0)?;
+            parse(&s)
+                .map(Value::DateTime)
+                .map_err(|e| ZError::i010(&s, "datetime", e))
+        }),
+        &STR_T,
+        &DATETIME_T,
+    );
+    unary(
+        &mut h,
+        "parse_date",
+        Arc::new(|x: &Vec<Value>| {
+            let s = get_param::<String>(x, 0)?;
+            s.parse()
+                .map(Value::Date)
+                .map_err(|e| ZError::i010(&s, "date", e))
+        }),
+        &STR_T,
+        &DATE_T,
+    );
+    unary(
+        &mut h,
+        "parse_time",
+        Arc::new(|x: &Vec<Value>| {
+            let s = get_param::<String>(x, 0)?;
+            s.parse()
+                .map(Value::Time)
+                .map_err(|e| ZError::i010(&s, "time", e))
+        }),
+        &STR_T,
+        &TIME_T,
+    );
+    binary(
+        &mut h,
+        "parse_fmt",
+        Arc::new(|x: &Vec<Value>| {
+            let (s, pattern) = (get_param::<String>(x, 0)?, get_param::<String>(x, 1)?);
+            parse_fmt(&s, &pattern)
+                .map(Value::DateTime)
+                .map_err(|e| ZError::i010(&s, &pattern, e))
+        }),
+        &STR_T,
+        &STR_T,
+        &DATETIME_T,
+    );
+
+    h.drain().map(|(k, v)| (k.into(), v)).collect()
+}
+
+pub static DATETIME: Lazy<Arc<Module>> = Lazy::new(|| {
+    Arc::new(Module::builtin(
+        "datetime",
+        "std::datetime",
+        datetime(),
+        &hashmap! {
+            "datetime".into() => Arc::clone(&DATETIME_T),
+            "date".into() => Arc::clone(&DATE_T),
+            "time".into() => Arc::clone(&TIME_T),
+            "duration".into() => Arc::clone(&DURATION_T),
+        },
+    ))
+});
@@ -0,0 +1,288 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    io::Write,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+    time::UNIX_EPOCH,
+};
+
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use smol_str::SmolStr;
+use tracing::trace;
+
+use crate::{
+    errors::{ZError, ZResult},
+    file_importer::Module,
+    primitives::{
+        utils::{binary, get_param, unary},
+        BOOL_T, F64_T, STR_T, U64_T, UNIT_T, VEC_T,
+    },
+    types::value::Value,
+};
+
+/// Makes an I/O error say which path it's about
+fn io_err(path: &str) -> impl Fn(std::io::Error) -> ZError + '_ {
+    move |e| ZError::i008(format!("`{path}`: {e}"))
+}
+
+/// The bytes in a `vec` of `u8`s
+fn vec_to_bytes(n: &str, x: &[Value], v: &[Value]) -> ZResult<Vec<u8>> {
+    v.iter()
+        .map(|v| v.as_u8().copied().ok_or_else(|| ZError::i007(n, x)))
+        .collect()
+}
+
+fn write(path: &str, contents: &[u8], append: bool) -> ZResult<Value> {
+    std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)
+        .and_then(|mut file| file.write_all(contents))
+        .map_err(io_err(path))?;
+    Ok(Value::Unit)
+}
+
+/// Removes `.` and resolves `..` without looking at the file system
+fn normalize(path: &str) -> String {
+    let mut out = PathBuf::new();
+    for component in Path::new(path).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if matches!(out.components().next_back(), Some(Component::Normal(_))) {
+                    out.pop();
+                } else if !out.has_root() {
+                    out.push("..");
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    if out.as_os_str().is_empty() {
+        ".".into()
+    } else {
+        out.to_string_lossy().into_owned()
+    }
+}
+
+fn path_str(path: Option<impl Display>) -> Value {
+    Value::Str(path.map(|p| p.to_string()).unwrap_or_default())
+}
+
+#[allow(clippy::cognitive_complexity)]
+fn fs() -> HashMap<SmolStr, Value> {
+    let mut h = HashMap::new();
+    trace!("Initialising std::fs");
+    unary(
+        &mut h,
+        "read",
+        Arc::new(|x: &Vec<Value>| {
+            let path = get_param::<String>(x, 0)?;
+            Ok(Value::Str(
+                std::fs::read_to_string(&path).map_err(io_err(&path))?,
+            ))
+        }),
+        &STR_T,
+        &STR_T,
+    );
+    unary(
+        &mut h,
+        "read_bytes",
+        Arc::new(|x: &Vec<Value>| {
+            let path = get_param::<String>(x, 0)?;
+            let bytes = std::fs::read(&path).map_err(io_err(&path))?;
+            Ok(Value::Vec(bytes.into_iter().map(Value::U8).collect()))
+        }),
+        &STR_T,
+        &VEC_T,
+    );
+    for (n, append) in [("write", false), ("append", true)] {
+        binary(
+            &mut h,
+            n,
+            Arc::new(move |x: &Vec<Value>| {
+                let path = get_param::<String>(x, 0)?;
+                write(&path, get_param::<String>(x, 1)?.as_bytes(), append)
+            }),
+            &STR_T,
+            &STR_T,
+            &UNIT_T,
+        );
+    }
+    for (n, append) in [("write_bytes", false), ("append_bytes", true)] {
+        binary(
+            &mut h,
+            n,
+            Arc::new(move |x: &Vec<Value>| {
+                let path = get_param::<String>(x, 0)?;
+                write(
+                    &path,
+                    &vec_to_bytes(n, x, &get_param::<Vec<Value>>(x, 1)?)?,
+                    append,
+                )
+            }),
+            &STR_T,
+            &VEC_T,
+            &UNIT_T,
+        );
+    }
+    unary(
+        &mut h,
+        "list_dir",
+        Arc::new(|x: &Vec<Value>| {
+            let path = get_param::<String>(x, 0)?;
+            let names = std::fs::read_dir(&path)
+                .and_then(|entries| {
+                    entries
+                        .map(|entry| Ok(entry?.file_name().to_string_lossy().into_owned()))
+                        .collect::<std::io::Result<Vec<_>>>()
+                })
+                .map_err(io_err(&path))?;
+            Ok(Value::Vec(
+                names.into_iter().sorted().map(Value::Str).collect(),
+            ))
+        }),
+        &STR_T,
+        &VEC_T,
+    );
+
+    unary(
+        &mut h,
+        "exists",
+        Arc::new(|x: &Vec<Value>| Ok(Path::new(&get_param::<String>(x, 0)?).exists().into())),
+        &STR_T,
+        &BOOL_T,
+    );
+    unary(
+        &mut h,
+        "is_file",
+        Arc::new(|x: &Vec<Value>| Ok(Path::new(&get_param::<String>(x, 0)?).is_file().into())),
+        &STR_T,
+        &BOOL_T,
+    );
+    unary(
+        &mut h,
+        "is_dir",
+        Arc::new(|x: &Vec<Value>| Ok(Path::new(&get_param::<String>(x, 0)?).is_dir().into())),
+        &STR_T,
+        &BOOL_T,
+    );
+    unary(
+        &mut h,
+        "size",
+        Arc::new(|x: &Vec<Value>| {
+            let path = get_param::<String>(x, 0)?;
+            Ok(Value::U64(
+                std::fs::metadata(&path).map_err(io_err(&path))?.len(),
+            ))
+        }),
+        &STR_T,
+        &U64_T,
+    );
+    unary(
+        &mut h,
+        "modified",
+        Arc::new(|x: &Vec<Value>| {
+            let path = get_param::<String>(x, 0)?;
+            let modified = std::fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .map_err(io_err(&path))?;
+            Ok(Value::F64(
+                modified
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs_f64(),
+            ))
+        }),
+        &STR_T,
+        &F64_T,
+    );
+
+    unary(
+        &mut h,
+        "create_dir",
+        Arc::new(|x: &Vec<Value>| {
+            let path = get_param::<String>(x, 0)?;
+            std::fs::create_dir_all(&path).map_err(io_err(&path))?;
+            Ok(Value::Unit)
+        }),
+        &STR_T,
+        &UNIT_T,
+    );
+    unary(
+        &mut h,
+        "remove_dir",
+        Arc::new(|x: &Vec<Value>| {
+            let path = get_param::<String>(x, 0)?;
+            std::fs::remove_dir(&path).map_err(io_err(&path))?;
+            Ok(Value::Unit)
+        }),
+        &STR_T,
+        &UNIT_T,
+    );
+    unary(
+        &mut h,
+        "remove_file",
+        Arc::new(|x: &Vec<Value>| {
+            let path = get_param::<String>(x, 0)?;
+            std::fs::remove_file(&path).map_err(io_err(&path))?;
+            Ok(Value::Unit)
+        }),
+        &STR_T,
+        &UNIT_T,
+    );
+
+    binary(
+        &mut h,
+        "join",
+        Arc::new(|x: &Vec<Value>| {
+            let (a, b) = (get_param::<String>(x, 0)?, get_param::<String>(x, 1)?);
+            Ok(Value::Str(Path::new(&a).join(b).to_string_lossy().into()))
+        }),
+        &STR_T,
+        &STR_T,
+        &STR_T,
+    );
+    unary(
+        &mut h,
+        "normalize",
+        Arc::new(|x: &Vec<Value>| Ok(Value::Str(normalize(&get_param::<String>(x, 0)?)))),
+        &STR_T,
+        &STR_T,
+    );
+    unary(
+        &mut h,
+        "parent",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(path_str(
+                Path::new(&get_param::<String>(x, 0)?)
+                    .parent()
+                    .map(Path::display),
+            ))
+        }),
+        &STR_T,
+        &STR_T,
+    );
+    unary(
+        &mut h,
+        "file_name",
+        Arc::new(|x: &Vec<Value>| {
+            Ok(path_str(
+                Path::new(&get_param::<String>(x, 0)?)
+                    .file_name()
+                    .map(|name| name.to_string_lossy()),
+            ))
+        }),
+        &STR_T,
+        &STR_T,
+    );
+
+    h.drain().map(|(k, v)| (k.into(), v)).collect()
+}
+
+pub static FS: Lazy<Arc<Module>> =
+    Lazy::new(|| Arc::new(Module::builtin("fs", "std::fs", fs(), &HashMap::new())));
@@ -0,0 +1,432 @@
+use std::{collections::HashMap, fmt::Write, iter::Peekable, str::Chars, sync::Arc};
+
+use maplit::hashmap;
+use num::BigInt;
+use once_cell::sync::Lazy;
+use smol_str::SmolStr;
+use tracing::trace;
+
+use crate::{
+    errors::{ZError, ZResult},
+    file_importer::Module,
+    primitives::{
+        utils::{binary, get_param, unary},
+        Hmap, ANY_T, BOOL_T, STR_T,
+    },
+    types::value::Value,
+};
+
+/// How deeply arrays and objects can be nested before parsing gives up, so that input can't
+/// overflow the stack
+const MAX_DEPTH: usize = 128;
+
+/// A JSON parser over chars, which keeps track of the line and column of the next one for errors
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Self {
+        Self {
+            chars: s.chars().peekable(),
+            line: 1,
+            column: 1,
+            depth: 0,
+        }
+    }
+    fn err(&self, reason: impl std::fmt::Display) -> ZError {
+        ZError::i012(self.line, self.column, reason)
+    }
+    fn unexpected(&mut self) -> ZError {
+        match self.chars.peek().copied() {
+            Some(c) => self.err(format!("unexpected `{}`", c.escape_debug())),
+            None => self.err("unexpected end of input"),
+        }
+    }
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+    fn next_if(&mut self, c: char) -> bool {
+        let matches = self.chars.peek() == Some(&c);
+        if matches {
+            self.next();
+        }
+        matches
+    }
+    fn expect(&mut self, c: char) -> ZResult<()> {
+        if self.next_if(c) {
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+    fn skip_whitespace(&mut self) {
+        while matches!(self.chars.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.next();
+        }
+    }
+    fn document(&mut self) -> ZResult<Value> {
+        let value = self.value()?;
+        self.skip_whitespace();
+        if self.chars.peek().is_some() {
+            return Err(self.unexpected());
+        }
+        Ok(value)
+    }
+    fn value(&mut self) -> ZResult<Value> {
+        self.skip_whitespace();
+        match self.chars.peek() {
+            Some('{') => self.nested(Self::object),
+            Some('[') => self.nested(Self::array),
+            Some('"') => self.string().map(Value::Str),
+            Some('-' | '0'..='9') => self.number(),
+            Some('t') => self.keyword("true", Value::Bool(true)),
+            Some('f') => self.keyword("false", Value::Bool(false)),
+            Some('n') => self.keyword("null", Value::Unit),
+            _ => Err(self.unexpected()),
+        }
+    }
+    fn nested(&mut self, f: fn(&mut Self) -> ZResult<Value>) -> ZResult<Value> {
+        if self.depth >= MAX_DEPTH {
+            return Err(self.err("nested too deeply"));
+        }
+        self.depth += 1;
+        let res = f(self);
+        self.depth -= 1;
+        res
+    }
+    fn keyword(&mut self, word: &str, value: Value) -> ZResult<Value> {
+        for c in word.chars() {
+            self.expect(c)?;
+        }
+        Ok(value)
+    }
+    /// Calls `f` for each item between `open` and `close`, which are separated by commas
+    fn items(
+        &mut self,
+        open: char,
+        close: char,
+        mut f: impl FnMut(&mut Self) -> ZResult<()>,
+    ) -> ZResult<()> {
+        self.expect(open)?;
+        self.skip_whitespace();
+        if self.next_if(close) {
+            return Ok(());
+        }
+        loop {
+            f(self)?;
+            self.skip_whitespace();
+            if self.next_if(close) {
+                return Ok(());
+            }
+            self.expect(',')?;
+        }
+    }
+    fn array(&mut self) -> ZResult<Value> {
+        let mut out = vec![];
+        self.items('[', ']', |p| {
+            out.push(p.value()?);
+            Ok(())
+        })?;
+        Ok(Value::Vec(out))
+    }
+    /// Later values of a key replace earlier ones
+    fn object(&mut self) -> ZResult<Value> {
+        let mut out = Hmap::default();
+        self.items('{', '}', |p| {
+            p.skip_whitespace();
+            if p.chars.peek() != Some(&'"') {
+                return Err(p.unexpected());
+            }
+            let k = p.string()?;
+            p.skip_whitespace();
+            p.expect(':')?;
+            out.set(Value::Str(k), p.value()?);
+            Ok(())
+        })?;
+        Ok(Value::Hmap(out))
+    }
+    fn string(&mut self) -> ZResult<String> {
+        self.expect('"')?;
+        let mut out = String::new();
+        loop {
+            match self.chars.peek() {
+                Some('"') => {
+                    self.next();
+                    return Ok(out);
+                }
+                Some('\\') => {
+                    self.next();
+                    out.push(self.escape()?);
+                }
+                Some(c) if *c >= ' ' => {
+                    out.extend(self.next());
+                }
+                _ => return Err(self.unexpected()),
+            }
+        }
+    }
+    fn escape(&mut self) -> ZResult<char> {
+        let c = match self.chars.peek() {
+            Some('"') => '"',
+            Some('\\') => '\\',
+            Some('/') => '/',
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('u') => {
+                self.next();
+                return self.unicode_escape();
+            }
+            _ => return Err(self.unexpected()),
+        };
+        self.next();
+        Ok(c)
+    }
+    /// The char of a `\u` escape, whose `\u` has been read, joining surrogate pairs
+    fn unicode_escape(&mut self) -> ZResult<char> {
+        let high = self.hex4()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high).ok_or_else(|| self.err("unpaired surrogate"));
+        }
+        if !(self.next_if('\\') && self.next_if('u')) {
+            return Err(self.err("unpaired surrogate"));
+        }
+        let low = self.hex4()?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(self.err("unpaired surrogate"));
+        }
+        char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+            .ok_or_else(|| self.err("unpaired surrogate"))
+    }
+    fn hex4(&mut self) -> ZResult<u32> {
+        let mut n = 0;
+        for _ in 0..4 {
+            let Some(digit) = self.chars.peek().and_then(|c| c.to_digit(16)) else {
+                return Err(self.unexpected());
+            };
+            self.next();
+            n = n * 16 + digit;
+        }
+        Ok(n)
+    }
+    fn digits(&mut self, out: &mut String) -> ZResult<()> {
+        if !matches!(self.chars.peek(), Some('0'..='9')) {
+            return Err(self.unexpected());
+        }
+        while let Some(c @ '0'..='9') = self.chars.peek().copied() {
+            out.push(c);
+            self.next();
+        }
+        Ok(())
+    }
+    /// Whole numbers are `i32`s, or `i64`s or `ibig`s if they're too big, and others are `f64`s
+    fn number(&mut self) -> ZResult<Value> {
+        let (line, column) = (self.line, self.column);
+        let mut s = String::new();
+        if self.next_if('-') {
+            s.push('-');
+        }
+        if self.next_if('0') {
+            s.push('0');
+        } else {
+            self.digits(&mut s)?;
+        }
+        if self.next_if('.') {
+            s.push('.');
+            self.digits(&mut s)?;
+        }
+        if let Some(e @ ('e' | 'E')) = self.chars.peek().copied() {
+            s.push(e);
+            self.next();
+            if let Some(sign @ ('+' | '-')) = self.chars.peek().copied() {
+                s.push(sign);
+                self.next();
+            }
+            self.digits(&mut s)?;
+        }
+        if !s.contains(['.', 'e', 'E']) {
+            if let Ok(i) = s.parse::<i32>() {
+                return Ok(Value::I32(i));
+            }
+            if let Ok(i) = s.parse::<i64>() {
+                return Ok(Value::I64(i));
+            }
+            if let Ok(i) = s.parse::<BigInt>() {
+                return Ok(Value::Ibig(i));
+            }
+        }
+        s.parse::<f64>()
+            .ok()
+            .filter(|f| f.is_finite())
+            .map(Value::F64)
+            .ok_or_else(|| ZError::i012(line, column, format!("`{s}` is out of range")))
+    }
+}
+
+fn write_str(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Writes the items of an array or object between `open` and `close`, on lines of their own
+/// indented by `indent` more than the brackets if it's pretty
+fn write_items<T>(
+    out: &mut String,
+    (open, close): (char, char),
+    items: impl ExactSizeIterator<Item = T>,
+    indent: Option<usize>,
+    mut f: impl FnMut(&mut String, T, Option<usize>) -> ZResult<()>,
+) -> ZResult<()> {
+    out.push(open);
+    let empty = items.len() == 0;
+    let inner = indent.map(|i| i + 1);
+    for (i, item) in items.enumerate() {
+        if i != 0 {
+            out.push(',');
+        }
+        if let Some(inner) = inner {
+            out.push('\n');
+            out.push_str(&"  ".repeat(inner));
+        }
+        f(out, item, inner)?;
+    }
+    if let (Some(indent), false) = (indent, empty) {
+        out.push('\n');
+        out.push_str(&"  ".repeat(indent));
+    }
+    out.push(close);
+    Ok(())
+}
+
+/// Writes an object entry, whose key has to be a `str` or `char`
+fn write_entry(out: &mut String, (k, v): (&Value, &Value), indent: Option<usize>) -> ZResult<()> {
+    match k {
+        Value::Str(k) => write_str(out, k),
+        Value::Char(k) => write_str(out, &k.to_string()),
+        k => return Err(ZError::i013(k)),
+    }
+    out.push_str(if indent.is_some() { ": " } else { ":" });
+    stringify(out, v, indent)
+}
+
+/// Writes a value as JSON, with `indent` levels of indentation if it's pretty. Opts are their value
+/// or `null`, and dates, times and durations are strings
+fn stringify(out: &mut String, value: &Value, indent: Option<usize>) -> ZResult<()> {
+    match value {
+        Value::Unit | Value::Opt(None) => out.push_str("null"),
+        Value::Opt(Some(v)) | Value::Return(v) => stringify(out, v, indent)?,
+        Value::F16(v) if !v.is_finite() => return Err(ZError::i013(value)),
+        Value::F32(v) if !v.is_finite() => return Err(ZError::i013(value)),
+        Value::F64(v) if !v.is_finite() => return Err(ZError::i013(value)),
+        Value::Bool(_)
+        | Value::I8(_)
+        | Value::I16(_)
+        | Value::I32(_)
+        | Value::I64(_)
+        | Value::I128(_)
+        | Value::Isize(_)
+        | Value::Ibig(_)
+        | Value::U8(_)
+        | Value::U16(_)
+        | Value::U32(_)
+        | Value::U64(_)
+        | Value::U128(_)
+        | Value::Usize(_)
+        | Value::Ubig(_)
+        | Value::F16(_)
+        | Value::F32(_)
+        | Value::F64(_) => {
+            let _ = write!(out, "{value}");
+        }
+        Value::Str(_)
+        | Value::Char(_)
+        | Value::DateTime(_)
+        | Value::Date(_)
+        | Value::Time(_)
+        | Value::Duration(_) => write_str(out, &value.to_string()),
+        Value::Vec(v) => write_items(out, ('[', ']'), v.iter(), indent, |out, v, indent| {
+            stringify(out, v, indent)
+        })?,
+        Value::Hmap(m) => write_items(
+            out,
+            ('{', '}'),
+            m.0.iter().map(|(k, v)| (k, v)),
+            indent,
+            write_entry,
+        )?,
+        Value::ClassInstance { ty, attrs } => {
+            let attrs = Value::ordered_attrs(ty, attrs)
+                .map(|(k, v)| (Value::Str(k), v))
+                .collect::<Vec<_>>();
+            write_items(
+                out,
+                ('{', '}'),
+                attrs.iter(),
+                indent,
+                |out, (k, v), indent| write_entry(out, (k, v), indent),
+            )?;
+        }
+        Value::Iter(_) | Value::Re(_) | Value::Type(_) | Value::Proc(_) => {
+            return Err(ZError::i013(value))
+        }
+    }
+    Ok(())
+}
+
+fn json() -> HashMap<SmolStr, Value> {
+    let mut h = HashMap::new();
+    trace!("Initialising std::json");
+
+    // objects are `hmap`s with `str` keys, arrays are `vec`s and `null` is `()`
+    unary(
+        &mut h,
+        "parse",
+        Arc::new(|x: &Vec<Value>| Parser::new(&get_param::<String>(x, 0)?).document()),
+        &STR_T,
+        &ANY_T,
+    );
+    // pretty JSON has an item per line, indented by two spaces per level
+    binary(
+        &mut h,
+        "stringify",
+        Arc::new(|x: &Vec<Value>| {
+            let mut out = String::new();
+            stringify(&mut out, &x[0], get_param::<bool>(x, 1)?.then_some(0))?;
+            Ok(Value::Str(out))
+        }),
+        &ANY_T,
+        &BOOL_T,
+        &STR_T,
+    );
+
+    h.drain().map(|(k, v)| (k.into(), v)).collect()
+}
+
+pub static JSON: Lazy<Arc<Module>> =
+    Lazy::new(|| Arc::new(Module::builtin("json", "std::json", json(), &hashmap! {})));
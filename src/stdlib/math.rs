@@ -0,0 +1,242 @@
+use std::{
+    collections::{HashMap, HashSet},
+    f64::consts::{E, PI, TAU},
+    sync::Arc,
+};
+
+use maplit::hashmap;
+use num::{bigint::BigInt, One, Signed, ToPrimitive};
+use once_cell::sync::Lazy;
+use smol_str::SmolStr;
+use tracing::trace;
+
+use crate::{
+    ast::Ident,
+    errors::{ZError, ZResult},
+    file_importer::Module,
+    primitives::{
+        instance_namespace,
+        utils::{binary, get_param, unary},
+        F64_T, IBIG_T, NUMBER_T,
+    },
+    types::{
+        r#type::{Type, ValueType, ValueTypeDef},
+        value::Value,
+    },
+};
+
+/// A struct type of two `f64` fields, for the results of coordinate conversions
+fn coord_type(name: &str, fields: [&str; 2]) -> Arc<ValueType> {
+    Arc::new(ValueType::Type(Box::new(ValueTypeDef {
+        name: Some(Ident::new(name)),
+        namespace: instance_namespace(),
+        fields: fields
+            .iter()
+            .map(|k| ((*k).into(), Arc::clone(&F64_T)))
+            .collect(),
+        mutable_fields: HashSet::new(),
+        params: Some(fields.iter().map(|k| ((*k).into(), None)).collect()),
+        type_args: vec![],
+    })))
+}
+
+pub static POLAR_T_VAL: Lazy<Arc<ValueType>> = Lazy::new(|| coord_type("Polar", ["r", "theta"]));
+pub static POLAR_T: Lazy<Arc<Type>> = Lazy::new(|| POLAR_T_VAL.to_type());
+pub static CARTESIAN_T_VAL: Lazy<Arc<ValueType>> =
+    Lazy::new(|| coord_type("Cartesian", ["x", "y"]));
+pub static CARTESIAN_T: Lazy<Arc<Type>> = Lazy::new(|| CARTESIAN_T_VAL.to_type());
+
+fn coord(ty: &Arc<ValueType>, fields: [(&str, f64); 2]) -> Value {
+    Value::ClassInstance {
+        ty: Arc::clone(ty),
+        attrs: fields
+            .into_iter()
+            .map(|(k, v)| (k.to_owned(), Value::F64(v)))
+            .collect(),
+    }
+}
+
+/// The `i`th argument, which can be of any numeric primitive, as an `f64`
+fn number(x: &[Value], i: usize) -> ZResult<f64> {
+    match x.get(i) {
+        Some(Value::I8(n)) => Some(f64::from(*n)),
+        Some(Value::I16(n)) => Some(f64::from(*n)),
+        Some(Value::I32(n)) => Some(f64::from(*n)),
+        Some(Value::I64(n)) => n.to_f64(),
+        Some(Value::I128(n)) => n.to_f64(),
+        Some(Value::Isize(n)) => n.to_f64(),
+        Some(Value::Ibig(n)) => n.to_f64(),
+        Some(Value::U8(n)) => Some(f64::from(*n)),
+        Some(Value::U16(n)) => Some(f64::from(*n)),
+        Some(Value::U32(n)) => Some(f64::from(*n)),
+        Some(Value::U64(n)) => n.to_f64(),
+        Some(Value::U128(n)) => n.to_f64(),
+        Some(Value::Usize(n)) => n.to_f64(),
+        Some(Value::Ubig(n)) => n.to_f64(),
+        Some(Value::F16(n)) => Some(f64::from(*n)),
+        Some(Value::F32(n)) => Some(f64::from(*n)),
+        Some(Value::F64(n)) => Some(*n),
+        _ => None,
+    }
+    .ok_or_else(|| ZError::i001(x))
+}
+
+/// A proc from a number to `f64`, where `f` gives `None` outside of its domain
+fn unary_f64(h: &mut HashMap<&'static str, Value>, n: &'static str, f: fn(f64) -> Option<f64>) {
+    unary(
+        h,
+        n,
+        Arc::new(move |x: &Vec<Value>| {
+            f(number(x, 0)?)
+                .map(Value::F64)
+                .ok_or_else(|| ZError::i007(n, x))
+        }),
+        &NUMBER_T,
+        &F64_T,
+    );
+}
+
+/// A proc from two numbers to `f64`, where `f` gives `None` outside of its domain
+fn binary_f64(
+    h: &mut HashMap<&'static str, Value>,
+    n: &'static str,
+    f: fn(f64, f64) -> Option<f64>,
+) {
+    binary(
+        h,
+        n,
+        Arc::new(move |x: &Vec<Value>| {
+            f(number(x, 0)?, number(x, 1)?)
+                .map(Value::F64)
+                .ok_or_else(|| ZError::i007(n, x))
+        }),
+        &NUMBER_T,
+        &NUMBER_T,
+        &F64_T,
+    );
+}
+
+/// The `n`th root of `x`, which is only defined for negative `x` if `n` is an odd integer
+fn rt(x: f64, n: f64) -> Option<f64> {
+    if n == 0.0 {
+        None
+    } else if x >= 0.0 {
+        Some(x.powf(n.recip()))
+    } else if n.fract() == 0.0 && n % 2.0 != 0.0 {
+        Some(-(-x).powf(n.recip()))
+    } else {
+        None
+    }
+}
+
+#[allow(clippy::cognitive_complexity, clippy::float_cmp)]
+fn math() -> HashMap<SmolStr, Value> {
+    let mut h = HashMap::new();
+    trace!("Initialising std::math");
+    h.insert("pi", Value::F64(PI));
+    h.insert("e", Value::F64(E));
+    h.insert("tau", Value::F64(TAU));
+    h.insert("Polar", Value::Type(Arc::clone(&POLAR_T_VAL)));
+    h.insert("Cartesian", Value::Type(Arc::clone(&CARTESIAN_T_VAL)));
+
+    binary_f64(&mut h, "rt", rt);
+    unary_f64(&mut h, "sqrt", |x| (x >= 0.0).then(|| x.sqrt()));
+    unary_f64(&mut h, "cbrt", |x| Some(x.cbrt()));
+    unary_f64(&mut h, "exp", |x| Some(x.exp()));
+    binary_f64(&mut h, "log", |x, base| {
+        (x > 0.0 && base > 0.0 && base != 1.0).then(|| x.log(base))
+    });
+    unary_f64(&mut h, "log10", |x| (x > 0.0).then(|| x.log10()));
+    unary_f64(&mut h, "log2", |x| (x > 0.0).then(|| x.log2()));
+    unary_f64(&mut h, "ln", |x| (x > 0.0).then(|| x.ln()));
+    // the poles of gamma are at zero and the negative integers
+    unary_f64(&mut h, "gamma", |x| {
+        (x > 0.0 || x.fract() != 0.0).then(|| x.gamma())
+    });
+
+    unary_f64(&mut h, "sin", |x| Some(x.sin()));
+    unary_f64(&mut h, "cos", |x| Some(x.cos()));
+    unary_f64(&mut h, "tan", |x| (x.cos() != 0.0).then(|| x.tan()));
+    unary_f64(&mut h, "csc", |x| (x.sin() != 0.0).then(|| x.sin().recip()));
+    unary_f64(&mut h, "sec", |x| (x.cos() != 0.0).then(|| x.cos().recip()));
+    unary_f64(&mut h, "cot", |x| (x.sin() != 0.0).then(|| x.tan().recip()));
+    unary_f64(&mut h, "asin", |x| {
+        (-1.0..=1.0).contains(&x).then(|| x.asin())
+    });
+    unary_f64(&mut h, "acos", |x| {
+        (-1.0..=1.0).contains(&x).then(|| x.acos())
+    });
+    unary_f64(&mut h, "atan", |x| Some(x.atan()));
+    binary_f64(&mut h, "atan2", |y, x| Some(y.atan2(x)));
+
+    binary(
+        &mut h,
+        "to_polar",
+        Arc::new(|x: &Vec<Value>| {
+            let (a, b) = (number(x, 0)?, number(x, 1)?);
+            Ok(coord(
+                &POLAR_T_VAL,
+                [("r", a.hypot(b)), ("theta", b.atan2(a))],
+            ))
+        }),
+        &NUMBER_T,
+        &NUMBER_T,
+        &POLAR_T,
+    );
+    binary(
+        &mut h,
+        "to_cartesian",
+        Arc::new(|x: &Vec<Value>| {
+            let (r, theta) = (number(x, 0)?, number(x, 1)?);
+            Ok(coord(
+                &CARTESIAN_T_VAL,
+                [("x", r * theta.cos()), ("y", r * theta.sin())],
+            ))
+        }),
+        &NUMBER_T,
+        &NUMBER_T,
+        &CARTESIAN_T,
+    );
+
+    unary(
+        &mut h,
+        "factorial",
+        Arc::new(|x: &Vec<Value>| {
+            let n = get_param::<BigInt>(x, 0)?;
+            if n.is_negative() {
+                return Err(ZError::i007("factorial", x));
+            }
+            let n = n.to_u64().ok_or_else(|| ZError::i002("factorial", x))?;
+            Ok(Value::Ibig((1..=n).fold(BigInt::one(), |acc, i| acc * i)))
+        }),
+        &IBIG_T,
+        &IBIG_T,
+    );
+    unary(
+        &mut h,
+        "isqrt",
+        Arc::new(|x: &Vec<Value>| {
+            let n = get_param::<BigInt>(x, 0)?;
+            if n.is_negative() {
+                return Err(ZError::i007("isqrt", x));
+            }
+            Ok(Value::Ibig(n.sqrt()))
+        }),
+        &IBIG_T,
+        &IBIG_T,
+    );
+
+    h.drain().map(|(k, v)| (k.into(), v)).collect()
+}
+
+pub static MATH: Lazy<Arc<Module>> = Lazy::new(|| {
+    Arc::new(Module::builtin(
+        "math",
+        "std::math",
+        math(),
+        &hashmap! {
+            "Polar".into() => Arc::clone(&POLAR_T),
+            "Cartesian".into() => Arc::clone(&CARTESIAN_T),
+        },
+    ))
+});
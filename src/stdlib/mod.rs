@@ -0,0 +1,30 @@
+mod argparse;
+mod collections;
+mod datetime;
+mod fs;
+mod json;
+mod math;
+mod sys;
+mod ter;
+
+use std::sync::Arc;
+
+pub use sys::{set_args, take_exit_code};
+pub use ter::{TER, TER_T, TER_T_VAL};
+
+use crate::file_importer::Module;
+
+/// The module built into the interpreter that `use` imports for the given segments, if any
+#[must_use]
+pub fn builtin_module(segments: &[&str]) -> Option<Arc<Module>> {
+    match segments {
+        ["std", "argparse"] => Some(Arc::clone(&argparse::ARGPARSE)),
+        ["std", "collections"] => Some(Arc::clone(&collections::COLLECTIONS)),
+        ["std", "datetime"] => Some(Arc::clone(&datetime::DATETIME)),
+        ["std", "fs"] => Some(Arc::clone(&fs::FS)),
+        ["std", "json"] => Some(Arc::clone(&json::JSON)),
+        ["std", "math"] => Some(Arc::clone(&math::MATH)),
+        ["std", "sys"] => Some(Arc::clone(&sys::SYS)),
+        _ => None,
+    }
+}
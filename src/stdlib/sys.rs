@@ -0,0 +1,166 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use once_cell::sync::Lazy;
+use smol_str::SmolStr;
+use tracing::trace;
+
+use crate::{
+    errors::ZError,
+    file_importer::Module,
+    primitives::{
+        utils::{binary, get_param, nullary, unary},
+        F64_T, I32_T, OPT_T, STR_T, UNIT_T,
+    },
+    types::value::Value,
+};
+
+thread_local! {
+    /// The script's path followed by the arguments given to it
+    static ARGS: RefCell<Vec<String>> = const { RefCell::new(vec![]) };
+    /// The code that `sys::exit` was called with, while the interpreter unwinds to the top level
+    static EXIT_CODE: Cell<Option<i32>> = const { Cell::new(None) };
+}
+
+/// When `std::sys` was first imported, which `sys::monotonic` counts from
+static START: Lazy<Instant> = Lazy::new(Instant::now);
+
+/// Sets the arguments that `sys::arg` gives, starting with the script's path
+pub fn set_args(args: Vec<String>) {
+    ARGS.with(|a| *a.borrow_mut() = args);
+}
+
+/// The code that `sys::exit` was called with, if the error being handled is from it
+#[must_use]
+pub fn take_exit_code() -> Option<i32> {
+    EXIT_CODE.with(Cell::take)
+}
+
+/// The arguments that `sys::arg` gives, starting with the script's path
+pub(super) fn args() -> Vec<String> {
+    ARGS.with(|a| a.borrow().to_owned())
+}
+
+/// Exits with `code` once the interpreter has unwound to the top level, so that the defers of
+/// every scope it's in still run
+pub(super) fn exit(code: i32) -> ZError {
+    EXIT_CODE.with(|c| c.set(Some(code)));
+    ZError::i009(code)
+}
+
+fn sys() -> HashMap<SmolStr, Value> {
+    let mut h = HashMap::new();
+    trace!("Initialising std::sys");
+    Lazy::force(&START);
+    nullary(
+        &mut h,
+        "argc",
+        Arc::new(|_: &Vec<Value>| {
+            Ok(Value::I32(
+                ARGS.with(|a| a.borrow().len())
+                    .try_into()
+                    .unwrap_or(i32::MAX),
+            ))
+        }),
+        &I32_T,
+    );
+    unary(
+        &mut h,
+        "arg",
+        Arc::new(|x: &Vec<Value>| {
+            let i = get_param::<i32>(x, 0)?;
+            let arg = usize::try_from(i)
+                .ok()
+                .and_then(|i| ARGS.with(|a| a.borrow().get(i).cloned()));
+            Ok(Value::Opt(arg.map(|arg| Box::new(Value::Str(arg)))))
+        }),
+        &I32_T,
+        &OPT_T,
+    );
+    unary(
+        &mut h,
+        "env",
+        Arc::new(|x: &Vec<Value>| {
+            let var = std::env::var(get_param::<String>(x, 0)?).ok();
+            Ok(Value::Opt(var.map(|var| Box::new(Value::Str(var)))))
+        }),
+        &STR_T,
+        &OPT_T,
+    );
+    binary(
+        &mut h,
+        "set_env",
+        Arc::new(|x: &Vec<Value>| {
+            let (k, v) = (get_param::<String>(x, 0)?, get_param::<String>(x, 1)?);
+            if k.is_empty() || k.contains(['=', '\0']) || v.contains('\0') {
+                return Err(ZError::i007("set_env", x));
+            }
+            std::env::set_var(k, v);
+            Ok(Value::Unit)
+        }),
+        &STR_T,
+        &STR_T,
+        &UNIT_T,
+    );
+    unary(
+        &mut h,
+        "remove_env",
+        Arc::new(|x: &Vec<Value>| {
+            let k = get_param::<String>(x, 0)?;
+            if k.is_empty() || k.contains(['=', '\0']) {
+                return Err(ZError::i007("remove_env", x));
+            }
+            std::env::remove_var(k);
+            Ok(Value::Unit)
+        }),
+        &STR_T,
+        &UNIT_T,
+    );
+    unary(
+        &mut h,
+        "exit",
+        Arc::new(|x: &Vec<Value>| Err(exit(get_param::<i32>(x, 0)?))),
+        &I32_T,
+        &UNIT_T,
+    );
+    nullary(
+        &mut h,
+        "time",
+        Arc::new(|_: &Vec<Value>| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            Ok(Value::F64(now.as_secs_f64()))
+        }),
+        &F64_T,
+    );
+    nullary(
+        &mut h,
+        "monotonic",
+        Arc::new(|_: &Vec<Value>| Ok(Value::F64(START.elapsed().as_secs_f64()))),
+        &F64_T,
+    );
+    unary(
+        &mut h,
+        "sleep",
+        Arc::new(|x: &Vec<Value>| {
+            let secs = get_param::<f64>(x, 0)?;
+            let Ok(duration) = Duration::try_from_secs_f64(secs) else {
+                return Err(ZError::i007("sleep", x));
+            };
+            std::thread::sleep(duration);
+            Ok(Value::Unit)
+        }),
+        &F64_T,
+        &UNIT_T,
+    );
+
+    h.drain().map(|(k, v)| (k.into(), v)).collect()
+}
+
+pub static SYS: Lazy<Arc<Module>> =
+    Lazy::new(|| Arc::new(Module::builtin("sys", "std::sys", sys(), &HashMap::new())));
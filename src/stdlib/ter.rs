@@ -0,0 +1,138 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::{BufRead, Write},
+    sync::Arc,
+};
+
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use smol_str::SmolStr;
+use tracing::trace;
+
+use crate::{
+    ast::Ident,
+    errors::{ZError, ZResult},
+    primitives::{
+        instance_namespace,
+        utils::{nullary, unary},
+        STR_T, UNIT_T, VARGS_T,
+    },
+    types::{
+        r#type::{Type, ValueType, ValueTypeDef},
+        value::Value,
+    },
+};
+
+/// The arguments of a call to one of `ter`'s output procs, separated by spaces
+fn joined(x: &[Value]) -> String {
+    x.iter().map(ToString::to_string).join(" ")
+}
+
+/// Writes a line to stderr, as `ter.err` does
+pub(super) fn err(line: &str) {
+    eprintln!("{line}");
+}
+
+/// Reads a line of input after the prompt's been printed, without its line ending
+fn read_line() -> ZResult<Value> {
+    std::io::stdout().flush().map_err(ZError::i008)?;
+    let mut line = String::new();
+    std::io::stdin()
+        .lock()
+        .read_line(&mut line)
+        .map_err(ZError::i008)?;
+    let len = line.trim_end_matches(['\n', '\r']).len();
+    line.truncate(len);
+    Ok(Value::Str(line))
+}
+
+fn ter() -> HashMap<SmolStr, Value> {
+    let mut h = HashMap::new();
+    trace!("Initialising ter");
+    unary(
+        &mut h,
+        "out",
+        Arc::new(|x: &Vec<Value>| {
+            println!("{}", joined(x));
+            Ok(Value::Unit)
+        }),
+        &VARGS_T,
+        &UNIT_T,
+    );
+    unary(
+        &mut h,
+        "print",
+        Arc::new(|x: &Vec<Value>| {
+            print!("{}", joined(x));
+            Ok(Value::Unit)
+        }),
+        &VARGS_T,
+        &UNIT_T,
+    );
+    unary(
+        &mut h,
+        "err",
+        Arc::new(|x: &Vec<Value>| {
+            err(&joined(x));
+            Ok(Value::Unit)
+        }),
+        &VARGS_T,
+        &UNIT_T,
+    );
+    unary(
+        &mut h,
+        "in",
+        Arc::new(|x: &Vec<Value>| {
+            print!("{}", joined(x));
+            read_line()
+        }),
+        &VARGS_T,
+        &STR_T,
+    );
+    unary(
+        &mut h,
+        "inln",
+        Arc::new(|x: &Vec<Value>| {
+            println!("{}", joined(x));
+            read_line()
+        }),
+        &VARGS_T,
+        &STR_T,
+    );
+    nullary(
+        &mut h,
+        "flush",
+        Arc::new(|_: &Vec<Value>| {
+            std::io::stdout().flush().map_err(ZError::i008)?;
+            Ok(Value::Unit)
+        }),
+        &UNIT_T,
+    );
+
+    h.drain().map(|(k, v)| (k.into(), v)).collect()
+}
+
+static TER_PROCS: Lazy<HashMap<SmolStr, Value>> = Lazy::new(ter);
+
+pub static TER_T_VAL: Lazy<Arc<ValueType>> = Lazy::new(|| {
+    Arc::new(ValueType::Type(Box::new(ValueTypeDef {
+        name: Some(Ident::new("ter")),
+        namespace: instance_namespace(),
+        fields: TER_PROCS
+            .iter()
+            .map(|(k, v)| (k.to_owned(), v.ty()))
+            .collect(),
+        mutable_fields: HashSet::new(),
+        params: None,
+        type_args: vec![],
+    })))
+});
+pub static TER_T: Lazy<Arc<Type>> = Lazy::new(|| TER_T_VAL.to_type());
+/// The console, whose fields are the procs for reading from and writing to it
+pub static TER: Lazy<Value> = Lazy::new(|| Value::ClassInstance {
+    ty: Arc::clone(&TER_T_VAL),
+    attrs: TER_PROCS
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_owned()))
+        .collect(),
+});
@@ -10,9 +10,10 @@ use smol_str::SmolStr;
 use crate::{
     ast::{Ast, AstData},
     errors::{ToZResult, ZError, ZResult},
-    primitives::{I32_T, PRIMS, PRIMS_VAL, TYPE_T},
+    primitives::{ERROR_T, I32_T, PRIMS, PRIMS_VAL, TYPE_T},
+    stdlib::{TER, TER_T},
     types::{
-        position::GetSpan,
+        position::{GetSpan, Span},
         r#type::{Type, TypeCheckType},
         value::Value,
     },
@@ -32,19 +33,29 @@ pub struct TypeCheckSymTable(pub VecDeque<TypeCheckFrame>);
 #[derive(Debug, Clone)]
 pub struct TypeCheckFrame {
     pub ty: TypeCheckFrameType,
-    pub table: HashMap<SmolStr, TypeCheckType>,
+    pub table: HashMap<SmolStr, TypeCheckEntry>,
     pub defer: Vec<Ast>,
 }
 
+#[derive(Debug, Clone)]
+pub struct TypeCheckEntry {
+    pub ty: TypeCheckType,
+    pub mutable: bool,
+    pub decl_span: Option<Span>,
+}
+
 impl Default for TypeCheckSymTable {
     fn default() -> Self {
         let mut table = Self(VecDeque::new());
         table.add_frame(TypeCheckFrameType::Constants);
         for (k, v) in &*PRIMS {
             table
-                .declare_val(k, TypeCheckType::Const(Arc::clone(v)))
+                .declare_val(k, TypeCheckType::Const(Arc::clone(v)), false, None::<Span>)
                 .unwrap_or_else(|_| unreachable!());
         }
+        table
+            .declare_val("ter", Arc::clone(&TER_T).into(), false, None::<Span>)
+            .unwrap_or_else(|_| unreachable!());
         table.add_frame(TypeCheckFrameType::NormalReturnable(Some(Arc::clone(
             &I32_T,
         ))));
@@ -87,22 +98,31 @@ impl TypeCheckSymTable {
     }
 
     #[tracing::instrument(skip(self))]
-    pub fn get_block_return(&self) -> Arc<Type> {
-        for frame in &self.0 {
-            if let TypeCheckFrameType::Function(ret_ty)
-            | TypeCheckFrameType::NormalReturnable(ret_ty) = &frame.ty
-            {
-                if let Some(ret_ty) = ret_ty {
-                    return Arc::clone(ret_ty);
-                }
+    pub fn get_block_return(&self) -> Option<Arc<Type>> {
+        self.0.iter().find_map(|frame| match &frame.ty {
+            TypeCheckFrameType::Function(ret_ty) | TypeCheckFrameType::NormalReturnable(ret_ty) => {
+                ret_ty.as_ref().map(Arc::clone)
             }
-        }
-        unreachable!()
+            _ => None,
+        })
     }
 
     #[tracing::instrument(skip(self))]
-    pub fn declare_val(&mut self, name: &str, value: TypeCheckType) -> ZResult<()> {
-        self.front_mut()?.table.insert(name.into(), value);
+    pub fn declare_val(
+        &mut self,
+        name: &str,
+        value: TypeCheckType,
+        mutable: bool,
+        decl_span: impl GetSpan,
+    ) -> ZResult<()> {
+        self.front_mut()?.table.insert(
+            name.into(),
+            TypeCheckEntry {
+                ty: value,
+                mutable,
+                decl_span: decl_span.span(),
+            },
+        );
         Ok(())
     }
     pub fn pop_frame(&mut self) -> ZResult<()> {
@@ -128,10 +148,21 @@ impl TypeCheckSymTable {
                 if frame.ty == TypeCheckFrameType::Constants {
                     return Err(ZError::t001().with_span(span));
                 }
-                if !Arc::ptr_eq(&frame.table[name], &value) {
-                    return Err(ZError::t011(&frame.table[name], &value).with_span(span));
+                let Some(entry) = frame.table.get_mut(name) else {
+                    return Err(ZError::t002(name).with_span(span));
+                };
+                if Arc::ptr_eq(&entry.ty, &ERROR_T) {
+                    return Err(ZError::t027(name).with_span(span));
                 }
-                frame.table.insert(name.into(), value);
+                if !entry.mutable {
+                    return Err(ZError::t018(name)
+                        .with_span(span)
+                        .add_span(&entry.decl_span));
+                }
+                if !Arc::ptr_eq(&entry.ty, &value) {
+                    return Err(ZError::t011(&entry.ty, &value).with_span(span));
+                }
+                entry.ty = value;
                 return Ok(());
             }
             if let TypeCheckFrameType::Function(_) = frame.ty {
@@ -142,13 +173,20 @@ impl TypeCheckSymTable {
     }
 
     #[tracing::instrument(skip(self))]
-    pub fn get_val(&mut self, name: &str, span: impl GetSpan) -> ZResult<TypeCheckType> {
+    pub fn get_entry(&self, name: &str, span: impl GetSpan) -> ZResult<&TypeCheckEntry> {
         let mut only_consts = false;
         for frame in &self.0 {
             if (only_consts && frame.ty == TypeCheckFrameType::Constants)
                 || frame.table.contains_key(name)
             {
-                return Ok(frame.table[name].to_owned());
+                let entry = frame
+                    .table
+                    .get(name)
+                    .ok_or_else(|| ZError::t002(name).with_span(&span))?;
+                if Arc::ptr_eq(&entry.ty, &ERROR_T) {
+                    return Err(ZError::t027(name).with_span(span));
+                }
+                return Ok(entry);
             }
             if let TypeCheckFrameType::Function(_) = frame.ty {
                 only_consts = true;
@@ -157,6 +195,10 @@ impl TypeCheckSymTable {
         Err(ZError::t002(name).with_span(span))
     }
     #[tracing::instrument(skip(self))]
+    pub fn get_val(&mut self, name: &str, span: impl GetSpan) -> ZResult<TypeCheckType> {
+        Ok(self.get_entry(name, span)?.ty.to_owned())
+    }
+    #[tracing::instrument(skip(self))]
     pub fn get_type(&mut self, name: &str, span: impl GetSpan) -> ZResult<Arc<Type>> {
         Ok(Arc::clone(self.get_val(name, span)?.as_const()?))
     }
@@ -167,11 +209,35 @@ impl TypeCheckSymTable {
             return Err(ZError::t002(name).with_span(span))
         };
         if let Some(v) = first_frame.table.remove(name) {
-            Ok(v)
+            Ok(v.ty)
         } else {
             Err(ZError::t002(name).with_span(span))
         }
     }
+    /// Removes the frames that a statement which failed to type check left above `depth`, and
+    /// declares what it would have declared with [`ERROR_T`], so that type checking can go on to
+    /// the next statement
+    pub fn recover(&mut self, depth: usize, statement: &Ast) {
+        self.0.drain(..self.0.len().saturating_sub(depth));
+        match statement {
+            Ast::Declare(declare) => {
+                // a member like `a.b` is assigned to rather than declared, so there's nothing to add
+                if let Ast::Ident(ident) = &*declare.variable {
+                    self.declare_error(&ident.name, ident);
+                }
+            }
+            Ast::Use(r#use) => {
+                if let Some(name) = r#use.name() {
+                    self.declare_error(&name, &r#use.path);
+                }
+            }
+            _ => {}
+        }
+    }
+    /// Declares `name` with [`ERROR_T`], so that using it doesn't give another error
+    pub fn declare_error(&mut self, name: &str, decl_span: impl GetSpan) {
+        let _ = self.declare_val(name, Arc::clone(&ERROR_T).into(), true, decl_span);
+    }
     #[tracing::instrument(skip(self))]
     pub fn add_defer(&mut self, content: Ast) -> ZResult<()> {
         self.front_mut()?.defer.push(content);
@@ -203,6 +269,7 @@ impl Default for InterpretSymTable {
         for (k, v) in &*PRIMS_VAL {
             table.declare_val(k, Value::Type(Arc::clone(v)));
         }
+        table.declare_val("ter", TER.to_owned());
         table.add_frame(InterpretFrameType::Normal);
         table
     }
@@ -48,6 +48,13 @@ pub enum OprType {
     SubAdd,
     And,
     Or,
+    BitAnd,
+    BitOr,
+    BitXor,
+    BitCompl,
+    Lsh,
+    Rsh,
+    Zrsh,
     Gt,
     Lt,
     Ge,
@@ -74,11 +81,17 @@ impl OprType {
     #[must_use]
     pub const fn order(&self) -> usize {
         match self {
-            Self::UnPlus | Self::UnMinus | Self::Not | Self::Ref | Self::Deref => 1,
+            Self::UnPlus
+            | Self::UnMinus
+            | Self::Not
+            | Self::BitCompl
+            | Self::Ref
+            | Self::Deref => 1,
             Self::TypeCast => 2,
             Self::Pow => 3,
             Self::Mul | Self::Div | Self::Mod => 6,
             Self::Add | Self::Sub | Self::AddSub | Self::SubAdd => 8,
+            Self::Lsh | Self::Rsh | Self::Zrsh => 9,
             Self::Gt
             | Self::Lt
             | Self::Ge
@@ -91,6 +104,9 @@ impl OprType {
             | Self::Isnt
             | Self::Iseq
             | Self::Isnteq => 10,
+            Self::BitAnd => 11,
+            Self::BitXor => 12,
+            Self::BitOr => 13,
             Self::And => 14,
             Self::Or => 16,
             Self::Concat => 18,
@@ -103,6 +119,7 @@ impl OprType {
 pub enum Flag {
     Hoi,
     Pub,
+    Pubp,
     Priv,
     Prot,
     Const,
@@ -124,6 +141,7 @@ pub enum Keyword {
     Defer,
     Class,
     Struct,
+    Use,
 }
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum AccessType {
@@ -138,12 +156,13 @@ pub enum TokenType {
     CommentEnd,                     // \n
     MultilineCommentStart,          // /*
     MultilineCommentEnd,            // */
-    Flag(Flag),                     // hoi, pub, priv, prot, const
+    Flag(Flag),                     // hoi, pub, pubp, priv, prot, const
     UnaryOpr(OprType),              // \~, ++, ! etc
     AssignmentOpr(Option<OprType>), // =, += etc
-    BinaryOpr(OprType), // +, -, /f, rt, \&, ==, >, is, &&, ||, ^^, .., ><, istype, isnttype etc
+    BinaryOpr(OprType), // +, -, /f, rt, \&, \<<, ==, >, is, &&, ||, ^^, .., ><, istype, isnttype etc
     DotOpr(AccessType), // .
     DeclarationOpr,     // :=
+    MutDeclarationOpr,  // .=
     LiteralMisc,        // true, null, etc
     LiteralNumber,      // 3, 24, -34.5 etc
     LiteralString,      // "abc" etc
@@ -195,7 +214,9 @@ impl TokenType {
             Self::UnaryOpr(OprType::Not | OprType::Ref | OprType::Deref, ..) => {
                 vec![TokenCategory::Operator, TokenCategory::ValueStart]
             }
-            Self::BinaryOpr(..) | Self::DeclarationOpr => vec![TokenCategory::Operator],
+            Self::BinaryOpr(..) | Self::DeclarationOpr | Self::MutDeclarationOpr => {
+                vec![TokenCategory::Operator]
+            }
             Self::Bar => vec![
                 TokenCategory::Literal,
                 TokenCategory::ValueStart,
@@ -1,6 +1,6 @@
 use std::{
     borrow::Cow,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt::{Debug, Display, Formatter},
     ops::Deref,
     sync::Arc,
@@ -13,34 +13,46 @@ use smol_str::SmolStr;
 use crate::{
     ast::Ident,
     errors::{ToZResult, ZError, ZResult},
-    primitives::{ANY_T_VAL, PRIMS, PRIMS_VAL, TYPE_T},
+    primitives::{ANY_T, ANY_T_VAL, PRIMS, PRIMS_VAL, TYPE_T},
     types::value::Value,
 };
 
 #[derive(Clone, PartialEq)]
 pub enum Type {
     Any,
-    Type {
-        name: Option<Ident>,
-        namespace: HashMap<SmolStr, LazyType<Value>>,
-        fields: HashMap<SmolStr, Arc<Type>>,
-        type_args: Vec<(SmolStr, LazyType<Value>)>,
-    },
+    Type(Box<TypeDef>),
     Generic {
         type_args: Vec<(SmolStr, Either<Value, Either<Vec<Arc<Type>>, Arc<Type>>>)>,
         base: Arc<Type>,
     },
 }
 
+#[derive(Clone, PartialEq)]
+pub struct TypeDef {
+    pub name: Option<Ident>,
+    pub namespace: HashMap<SmolStr, LazyType<Value>>,
+    pub fields: HashMap<SmolStr, Arc<Type>>,
+    pub mutable_fields: HashSet<SmolStr>,
+    /// For structs, the constructor's fields in order, and whether each has a default
+    pub params: Option<Vec<(SmolStr, bool)>>,
+    pub type_args: Vec<(SmolStr, LazyType<Value>)>,
+}
+
 #[derive(Clone)]
 pub struct LazyType<T: Clone + Debug> {
     pub data: Option<T>,
+    /// For namespace items that are types themselves, the type they stand for
+    pub const_ty: Option<Arc<Type>>,
     ty: OnceCell<Arc<Type>>,
     f: Arc<dyn Fn(&Option<T>) -> Arc<Type> + Send + Sync>,
 }
 impl<T: Clone + Debug> PartialEq for LazyType<T> {
     fn eq(&self, other: &Self) -> bool {
-        self.ty == other.ty
+        // builtin types refer to themselves through their namespaces, so this can't compare deeply
+        match (self.ty.get(), other.ty.get()) {
+            (Some(ty1), Some(ty2)) => Arc::ptr_eq(ty1, ty2),
+            (ty1, ty2) => ty1.is_none() && ty2.is_none(),
+        }
     }
 }
 impl<T: Clone + Debug> Debug for LazyType<T> {
@@ -63,16 +75,41 @@ impl<T: Clone + Debug + 'static> LazyType<T> {
     pub fn new_lazy(data: T, f: fn(&T) -> Arc<Type>) -> Self {
         Self {
             data: Some(data),
+            const_ty: None,
             f: Arc::new(move |v| f(v.as_ref().unwrap())),
             ty: OnceCell::new(),
         }
     }
 }
 
+impl<T: Clone + Debug> LazyType<T> {
+    /// A type that's only known once `cell` is filled, and is `_any` if it's needed before that
+    #[must_use]
+    pub fn new_deferred(cell: Arc<OnceCell<Arc<Type>>>) -> Self {
+        Self {
+            data: None,
+            const_ty: None,
+            f: Arc::new(move |_| Arc::clone(cell.get().unwrap_or(&ANY_T))),
+            ty: OnceCell::new(),
+        }
+    }
+    /// An item that is the type `ty`, rather than a value of it
+    #[must_use]
+    pub fn new_const(ty: Arc<Type>) -> Self {
+        Self {
+            data: None,
+            const_ty: Some(ty),
+            f: Arc::new(|_| Arc::clone(&TYPE_T)),
+            ty: OnceCell::new(),
+        }
+    }
+}
+
 impl<T: Clone + Debug> From<Arc<Type>> for LazyType<T> {
     fn from(ty: Arc<Type>) -> Self {
         Self {
             data: None,
+            const_ty: None,
             f: Arc::new(move |_| Arc::clone(&ty)),
             ty: OnceCell::new(),
         }
@@ -82,12 +119,17 @@ impl<T: Clone + Debug> From<Arc<Type>> for LazyType<T> {
 #[derive(Clone, Debug, PartialEq)]
 pub enum ValueType {
     Any,
-    Type {
-        name: Option<Ident>,
-        namespace: HashMap<SmolStr, Value>,
-        fields: HashMap<SmolStr, Arc<Type>>,
-        type_args: Vec<(SmolStr, Value)>,
-    },
+    Type(Box<ValueTypeDef>),
+}
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValueTypeDef {
+    pub name: Option<Ident>,
+    pub namespace: HashMap<SmolStr, Value>,
+    pub fields: HashMap<SmolStr, Arc<Type>>,
+    pub mutable_fields: HashSet<SmolStr>,
+    /// For structs, the fields passed to the constructor in order, and their defaults
+    pub params: Option<Vec<(SmolStr, Option<Value>)>>,
+    pub type_args: Vec<(SmolStr, Value)>,
 }
 
 impl Type {
@@ -96,7 +138,7 @@ impl Type {
         match self {
             Self::Any => Cow::Owned(HashMap::new()),
             Self::Generic { base, .. } => base.namespace(), // TODO type arg substitutions
-            Self::Type { namespace, .. } => Cow::Borrowed(namespace),
+            Self::Type(def) => Cow::Borrowed(&def.namespace),
         }
     }
     #[must_use]
@@ -104,53 +146,81 @@ impl Type {
         match self {
             Self::Any => Cow::Owned(HashMap::new()),
             Self::Generic { base, .. } => base.fields(), // TODO type arg substitutions
-            Self::Type { fields, .. } => Cow::Borrowed(fields),
+            Self::Type(def) => Cow::Borrowed(&def.fields),
+        }
+    }
+    #[must_use]
+    pub fn mutable_fields(&self) -> Cow<'_, HashSet<SmolStr>> {
+        match self {
+            Self::Any => Cow::Owned(HashSet::new()),
+            Self::Generic { base, .. } => base.mutable_fields(),
+            Self::Type(def) => Cow::Borrowed(&def.mutable_fields),
+        }
+    }
+    #[must_use]
+    pub fn params(&self) -> Option<&Vec<(SmolStr, bool)>> {
+        match self {
+            Self::Any => None,
+            Self::Generic { base, .. } => base.params(),
+            Self::Type(def) => def.params.as_ref(),
         }
     }
 
     pub fn update_name(self: &mut Arc<Self>, new_name: Ident) -> ZResult<()> {
-        let Self::Type { name, .. } = &**self else {
-            return Ok(())
+        let Self::Type(def) = &**self else {
+            return Ok(());
         };
-        if name.is_none() {
-            let Self::Type { name, .. } = Arc::get_mut(self).z()? else {
+        if def.name.is_none() {
+            let Self::Type(def) = Arc::get_mut(self).z()? else {
                 unreachable!()
             };
-            *name = Some(new_name);
+            def.name = Some(new_name);
         }
         Ok(())
     }
 }
 
 impl ValueType {
+    pub fn update_name(self: &mut Arc<Self>, new_name: Ident) -> ZResult<()> {
+        let Self::Type(def) = &**self else {
+            return Ok(());
+        };
+        if def.name.is_none() {
+            let Self::Type(def) = Arc::get_mut(self).z()? else {
+                unreachable!()
+            };
+            def.name = Some(new_name);
+        }
+        Ok(())
+    }
     #[must_use]
     pub fn to_type(self: &Arc<Self>) -> Arc<Type> {
-        if let Some((_, ty)) = PRIMS_VAL
-            .values()
-            .zip(PRIMS.values())
-            .find(|(k, _)| Arc::ptr_eq(*k, self))
-        {
-            Arc::clone(ty)
+        if let Some((name, _)) = PRIMS_VAL.iter().find(|(_, v)| Arc::ptr_eq(v, self)) {
+            Arc::clone(&PRIMS[name])
         } else {
             Arc::new(match &**self {
                 Self::Any => Type::Any,
-                Self::Type {
-                    name,
-                    namespace,
-                    fields,
-                    type_args,
-                } => Type::Type {
-                    name: name.to_owned(),
-                    namespace: namespace
+                Self::Type(def) => Type::Type(Box::new(TypeDef {
+                    name: def.name.to_owned(),
+                    namespace: def
+                        .namespace
                         .iter()
                         .map(|(k, v)| (k.to_owned(), LazyType::new_lazy(v.to_owned(), Value::ty)))
                         .collect(),
-                    fields: fields.to_owned(),
-                    type_args: type_args
+                    fields: def.fields.to_owned(),
+                    mutable_fields: def.mutable_fields.to_owned(),
+                    params: def.params.as_ref().map(|params| {
+                        params
+                            .iter()
+                            .map(|(k, v)| (k.to_owned(), v.is_some()))
+                            .collect()
+                    }),
+                    type_args: def
+                        .type_args
                         .iter()
                         .map(|(k, v)| (k.to_owned(), LazyType::new_lazy(v.to_owned(), Value::ty)))
                         .collect(),
-                },
+                })),
             })
         }
     }
@@ -158,7 +228,7 @@ impl ValueType {
     pub fn namespace(&self) -> Cow<HashMap<SmolStr, Value>> {
         match self {
             Self::Any => Cow::Owned(HashMap::new()),
-            Self::Type { namespace, .. } => Cow::Borrowed(namespace),
+            Self::Type(def) => Cow::Borrowed(&def.namespace),
         }
     }
 }
@@ -168,12 +238,13 @@ impl Debug for Type {
         write!(f, "ty {{{self} ")?;
         match self {
             Self::Any => {}
-            Self::Type {
-                name,
-                namespace,
-                fields,
-                ..
-            } => {
+            Self::Type(def) => {
+                let TypeDef {
+                    name,
+                    namespace,
+                    fields,
+                    ..
+                } = &**def;
                 if name.is_some() {
                     write!(f, ": ")?;
                 };
@@ -204,19 +275,17 @@ impl Display for Type {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Any => write!(f, "_any"),
-            Self::Type {
-                name, type_args, ..
-            } => {
-                if let Some(name) = name {
+            Self::Type(def) => {
+                if let Some(name) = &def.name {
                     write!(f, "{}", name.name)
                 } else {
                     write!(f, "(anonymous)")
                 }?;
-                if !type_args.is_empty() {
+                if !def.type_args.is_empty() {
                     write!(
                         f,
                         "[{}]",
-                        type_args
+                        def.type_args
                             .iter()
                             .map(|(k, v)| format!("{k}: {v}"))
                             .join(", ")
@@ -261,19 +330,17 @@ impl Display for ValueType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Any => write!(f, "_any"),
-            Self::Type {
-                name, type_args, ..
-            } => {
-                if let Some(name) = name {
+            Self::Type(def) => {
+                if let Some(name) = &def.name {
                     write!(f, "{}", name.name)
                 } else {
                     write!(f, "(anonymous)")
                 }?;
-                if !type_args.is_empty() {
+                if !def.type_args.is_empty() {
                     write!(
                         f,
                         "[{}]",
-                        type_args
+                        def.type_args
                             .iter()
                             .map(|(k, v)| format!("{k}: {v}"))
                             .join(", ")
@@ -294,7 +361,7 @@ pub struct BuiltinType {
 }
 impl From<BuiltinType> for Type {
     fn from(value: BuiltinType) -> Self {
-        Self::Type {
+        Self::Type(Box::new(TypeDef {
             name: value.name,
             namespace: value
                 .namespace
@@ -302,26 +369,30 @@ impl From<BuiltinType> for Type {
                 .map(|(k, v)| (k, LazyType::new_lazy(v, Value::ty)))
                 .collect(),
             fields: value.fields,
+            mutable_fields: HashSet::new(),
+            params: None,
             type_args: value
                 .type_args
                 .into_iter()
                 .map(|(k, v)| (k, v.into()))
                 .collect(),
-        }
+        }))
     }
 }
 impl From<BuiltinType> for ValueType {
     fn from(value: BuiltinType) -> Self {
-        Self::Type {
+        Self::Type(Box::new(ValueTypeDef {
             name: value.name,
             namespace: value.namespace,
             fields: value.fields,
+            mutable_fields: HashSet::new(),
+            params: None,
             type_args: value
                 .type_args
                 .into_iter()
                 .map(|(k, _)| (k, Value::Type(Arc::clone(&ANY_T_VAL))))
                 .collect(),
-        }
+        }))
     }
 }
 
@@ -336,7 +407,7 @@ impl Deref for TypeCheckType {
         match self {
             Self::Const(_) => &TYPE_T,
             Self::Type(ty) => {
-                if *ty == *TYPE_T {
+                if Arc::ptr_eq(ty, &TYPE_T) {
                     unreachable!()
                 }
                 ty
@@ -5,6 +5,7 @@ use std::{
     sync::Arc,
 };
 
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, SecondsFormat, TimeDelta};
 use enum_as_inner::EnumAsInner;
 use half::f16;
 use itertools::Itertools;
@@ -14,6 +15,7 @@ use smol_str::SmolStr;
 use crate::{
     ast::{Ast, Block, Literal},
     errors::{ZError, ZResult},
+    file_importer::ModuleEnv,
     primitives::*,
     types::{
         position::GetSpan,
@@ -22,7 +24,10 @@ use crate::{
     },
 };
 
-pub type BuiltinFunction = dyn Fn(&Vec<Value>) -> Option<Value> + Send + Sync;
+pub type BuiltinFunction = dyn Fn(&Vec<Value>) -> ZResult<Value> + Send + Sync;
+/// A builtin that calls procs it's given, which needs the symbol table to call them with
+pub type HigherOrderFunction =
+    dyn Fn(&Vec<Value>, &mut InterpretSymTable) -> ZResult<Value> + Send + Sync;
 
 #[derive(Clone)]
 pub enum Proc {
@@ -31,26 +36,38 @@ pub enum Proc {
         id: usize,
         ty: LazyGenericProc,
     },
+    HigherOrder {
+        f: Arc<HigherOrderFunction>,
+        id: usize,
+        ty: LazyGenericProc,
+    },
     Defined {
         is_fn: bool,
         content: Block,
         args: Vec<SmolStr>,
+        /// The types of `args`, which builtins that call procs they're given check the values by
+        arg_tys: Vec<Arc<ValueType>>,
+        /// The module it's defined in, if it's not in the main file
+        module: Option<ModuleEnv>,
     },
 }
 impl PartialEq for Proc {
     fn eq(&self, other: &Self) -> bool {
         match (&self, other) {
-            (Self::Builtin { id: id1, .. }, Self::Builtin { id: id2, .. }) => id1 == id2,
+            (Self::Builtin { id: id1, .. }, Self::Builtin { id: id2, .. })
+            | (Self::HigherOrder { id: id1, .. }, Self::HigherOrder { id: id2, .. }) => id1 == id2,
             (
                 Self::Defined {
                     is_fn: is_fn1,
                     content: content1,
                     args: args1,
+                    ..
                 },
                 Self::Defined {
                     is_fn: is_fn2,
                     content: content2,
                     args: args2,
+                    ..
                 },
             ) => is_fn1 == is_fn2 && content1 == content2 && args1 == args2,
             _ => false,
@@ -61,31 +78,56 @@ impl PartialEq for Proc {
 impl Proc {
     pub fn call(&self, vals: Vec<Value>, val_symt: &mut InterpretSymTable) -> ZResult<Value> {
         match self {
-            Self::Builtin { f, .. } => {
-                let Some(res) = (*f)(&vals) else {
-                    return Err(ZError::i001(&vals))
-                };
-                Ok(res)
-            }
+            Self::Builtin { f, .. } => (*f)(&vals),
+            Self::HigherOrder { f, .. } => (*f)(&vals, val_symt),
             Self::Defined {
                 content,
                 is_fn,
                 args,
+                arg_tys,
+                module,
             } => {
+                // builtins that call procs they're given can't check how many args they take, or
+                // what types they take
+                if args.len() != vals.len() {
+                    return Err(ZError::t015(args.len(), vals.len()).with_span(content));
+                }
+                for (expected, val) in arg_tys.iter().zip(&vals) {
+                    if !Self::accepts(expected, &val.value_ty()) {
+                        return Err(ZError::t004(&expected.to_type(), &val.ty()));
+                    }
+                }
+                // the module's items are visible to its procs, wherever they're called from
+                let module = module.as_ref().and_then(|module| module.get());
+                if let Some(module) = module {
+                    val_symt.add_frame(InterpretFrameType::Normal).table = module.to_owned();
+                }
                 val_symt.add_frame(if *is_fn {
                     InterpretFrameType::Function
                 } else {
                     InterpretFrameType::Normal
                 });
-                for (name, val) in args.iter().zip_eq(vals) {
+                for (name, val) in args.iter().zip(vals) {
                     val_symt.declare_val(name, val);
                 }
                 let res = content.interpret_block(val_symt, true, false);
                 val_symt.pop_frame()?;
+                if module.is_some() {
+                    val_symt.pop_frame()?;
+                }
                 res
             }
         }
     }
+    /// Whether a value of type `actual` can be passed as an argument of type `expected`
+    fn accepts(expected: &Arc<ValueType>, actual: &Arc<ValueType>) -> bool {
+        // a struct's type may be copied when its namespace is extended, so match it by name too
+        Arc::ptr_eq(expected, actual)
+            || matches!(**expected, ValueType::Any)
+            || matches!((&**expected, &**actual), (ValueType::Type(e), ValueType::Type(a))
+                if e.name.as_ref().map(|n| &n.name) == a.name.as_ref().map(|n| &n.name)
+                    && e.name.is_some())
+    }
 }
 
 #[derive(Clone, PartialEq, EnumAsInner)]
@@ -108,7 +150,17 @@ pub enum Value {
     F32(f32),
     F64(f64),
     Str(String),
+    Char(char),
     Bool(bool),
+    DateTime(DateTime<FixedOffset>),
+    Date(NaiveDate),
+    Time(NaiveTime),
+    Duration(TimeDelta),
+    Opt(Option<Box<Self>>),
+    Vec(Vec<Self>),
+    Hmap(Hmap),
+    Iter(Iter),
+    Re(Re),
     Type(Arc<ValueType>),
     Proc(Proc),
     ClassInstance {
@@ -161,7 +213,17 @@ from_to!(F16, f16, F16_T);
 from_to!(F32, f32, F32_T);
 from_to!(F64, f64, F64_T);
 from_to!(Str, String, STR_T);
+from_to!(Char, char, CHAR_T);
 from_to!(Bool, bool, BOOL_T);
+from_to!(DateTime, DateTime<FixedOffset>, DATETIME_T);
+from_to!(Date, NaiveDate, DATE_T);
+from_to!(Time, NaiveTime, TIME_T);
+from_to!(Duration, TimeDelta, DURATION_T);
+from_to!(Opt, Option<Box<Value>>, OPT_T);
+from_to!(Vec, Vec<Value>, VEC_T);
+from_to!(Hmap, Hmap, HMAP_T);
+from_to!(Iter, Iter, ITER_T);
+from_to!(Re, Re, RE_T);
 from_to!(Type, Arc<ValueType>, TYPE_T);
 from_to!(Proc, Proc, PROC_T);
 
@@ -214,9 +276,25 @@ impl Debug for Value {
                 Self::F32(v) => format!("{v}@f32"),
                 Self::F64(v) => format!("{v}@f64"),
                 Self::Str(v) => format!("\"{v}\""),
+                Self::Char(v) => format!("c\"{v}\""),
+                Self::DateTime(_) => format!("{self}@datetime"),
+                Self::Date(v) => format!("{v}@date"),
+                Self::Time(v) => format!("{v}@time"),
+                Self::Duration(v) => format!("{v}@duration"),
+                Self::Opt(Some(v)) => format!("{v:?}@opt"),
+                Self::Re(v) => format!("\"{v}\"@re"),
+                Self::Vec(v) => format!("[{}]@vec", v.iter().map(|v| format!("{v:?}")).join(", ")),
+                Self::Hmap(v) => format!(
+                    "{{{}}}@hmap",
+                    v.0.iter().map(|(k, v)| format!("{k:?}: {v:?}")).join(", ")
+                ),
                 Self::Type(v) => format!("{v:?}"),
-                Self::Bool(_) | Self::ClassInstance { .. } | Self::Proc { .. } | Self::Unit =>
-                    self.to_string(),
+                Self::Bool(_)
+                | Self::Opt(None)
+                | Self::Iter(_)
+                | Self::ClassInstance { .. }
+                | Self::Proc { .. }
+                | Self::Unit => self.to_string(),
                 Self::Return(_) => unreachable!(),
             }
         )
@@ -225,7 +303,7 @@ impl Debug for Value {
 impl Display for Proc {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Builtin { id, ty, .. } => {
+            Self::Builtin { id, ty, .. } | Self::HigherOrder { id, ty, .. } => {
                 write!(f, "builtin@{id:x}@{ty}")
             }
             Self::Defined { is_fn, content, .. } => {
@@ -264,8 +342,25 @@ impl Display for Value {
                 Self::F32(v) => Cow::Borrowed(rb.format(*v)),
                 Self::F64(v) => Cow::Borrowed(rb.format(*v)),
                 Self::Str(v) => Cow::Borrowed(&**v),
+                Self::Char(v) => Cow::Owned(v.to_string()),
                 Self::Bool(v) => Cow::Owned(v.to_string()),
-                Self::Type(v) | Self::ClassInstance { ty: v, .. } => Cow::Owned(format!("<{v}>")),
+                Self::DateTime(v) => Cow::Owned(v.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+                Self::Date(v) => Cow::Owned(v.to_string()),
+                Self::Time(v) => Cow::Owned(v.to_string()),
+                Self::Duration(v) => Cow::Owned(v.to_string()),
+                Self::Opt(Some(v)) => Cow::Owned(v.to_string()),
+                Self::Opt(None) => Cow::Borrowed("none"),
+                Self::Vec(v) => Cow::Owned(format!("[{}]", v.iter().join(", "))),
+                Self::Hmap(v) => Cow::Owned(v.to_string()),
+                Self::Iter(v) => Cow::Owned(v.to_string()),
+                Self::Re(v) => Cow::Owned(v.to_string()),
+                Self::Type(v) => Cow::Owned(format!("<{v}>")),
+                Self::ClassInstance { ty, attrs } => {
+                    let attrs = Self::ordered_attrs(ty, attrs)
+                        .map(|(k, v)| format!("{k}: {v}"))
+                        .join(", ");
+                    Cow::Owned(format!("{ty} {{{attrs}}}"))
+                }
                 Self::Unit => Cow::Borrowed("()"),
                 Self::Return(v) => Cow::Owned(v.to_string()),
                 Self::Proc(v) => Cow::Owned(v.to_string()),
@@ -319,10 +414,20 @@ impl Value {
             Self::F32(..) => Arc::clone(&F32_T),
             Self::F64(..) => Arc::clone(&F64_T),
             Self::Str(..) => Arc::clone(&STR_T),
+            Self::Char(..) => Arc::clone(&CHAR_T),
             Self::Bool(..) => Arc::clone(&BOOL_T),
+            Self::DateTime(..) => Arc::clone(&DATETIME_T),
+            Self::Date(..) => Arc::clone(&DATE_T),
+            Self::Time(..) => Arc::clone(&TIME_T),
+            Self::Duration(..) => Arc::clone(&DURATION_T),
+            Self::Opt(..) => Arc::clone(&OPT_T),
+            Self::Vec(..) => Arc::clone(&VEC_T),
+            Self::Hmap(..) => Arc::clone(&HMAP_T),
+            Self::Iter(..) => Arc::clone(&ITER_T),
+            Self::Re(..) => Arc::clone(&RE_T),
             Self::Type(..) => Arc::clone(&TYPE_T),
             Self::Proc(proc) => Arc::clone(match proc {
-                Proc::Builtin { ty, .. } => ty,
+                Proc::Builtin { ty, .. } | Proc::HigherOrder { ty, .. } => ty,
                 Proc::Defined { .. } => &PROC_T,
             }),
             Self::ClassInstance { ty, .. } => ty.to_type(),
@@ -356,7 +461,17 @@ impl Value {
             Self::F32(..) => Arc::clone(&F32_T_VAL),
             Self::F64(..) => Arc::clone(&F64_T_VAL),
             Self::Str(..) => Arc::clone(&STR_T_VAL),
+            Self::Char(..) => Arc::clone(&CHAR_T_VAL),
             Self::Bool(..) => Arc::clone(&BOOL_T_VAL),
+            Self::DateTime(..) => Arc::clone(&DATETIME_T_VAL),
+            Self::Date(..) => Arc::clone(&DATE_T_VAL),
+            Self::Time(..) => Arc::clone(&TIME_T_VAL),
+            Self::Duration(..) => Arc::clone(&DURATION_T_VAL),
+            Self::Opt(..) => Arc::clone(&OPT_T_VAL),
+            Self::Vec(..) => Arc::clone(&VEC_T_VAL),
+            Self::Hmap(..) => Arc::clone(&HMAP_T_VAL),
+            Self::Iter(..) => Arc::clone(&ITER_T_VAL),
+            Self::Re(..) => Arc::clone(&RE_T_VAL),
             Self::Type(..) => Arc::clone(&TYPE_T_VAL),
             Self::Proc(_) => Arc::clone(&PROC_T_VAL),
             Self::ClassInstance { ty, .. } => Arc::clone(ty),
@@ -364,6 +479,24 @@ impl Value {
             Self::Return(v) => v.value_ty(),
         }
     }
+    /// The attributes of a class instance in the order the constructor takes them in, if there is
+    /// one, or else by name
+    pub fn ordered_attrs<'a>(
+        ty: &ValueType,
+        attrs: &'a HashMap<String, Self>,
+    ) -> impl Iterator<Item = (String, &'a Self)> {
+        let params = match ty {
+            ValueType::Type(def) => def.params.as_ref(),
+            ValueType::Any => None,
+        };
+        let order = match params {
+            Some(params) => params.iter().map(|(k, _)| k.to_string()).collect(),
+            None => attrs.keys().sorted().cloned().collect::<Vec<_>>(),
+        };
+        order
+            .into_iter()
+            .filter_map(|k| attrs.get(&k).map(|v| (k, v)))
+    }
     #[must_use]
     pub fn as_ast(&self) -> Ast {
         Ast::Literal(Literal {
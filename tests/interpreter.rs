@@ -0,0 +1,889 @@
+use itertools::Either;
+use zyxt::{
+    errors::{ZErrors, ZResult},
+    package::Package,
+    primitives::{set_overflow_mode, OverflowMode},
+    stdlib::set_args,
+    types::sym_table::{InterpretSymTable, TypeCheckSymTable},
+};
+
+macro_rules! compile {
+    ($str:expr) => {
+        zyxt::compile(
+            &Either::Right((format!("{}:{}", file!(), line!()).into(), $str.to_owned())),
+            &mut TypeCheckSymTable::default(),
+            false,
+        )
+    };
+}
+macro_rules! run {
+    ($str:expr) => {
+        zyxt::interpret(&compile!($str).unwrap(), &mut InterpretSymTable::default()).unwrap()
+    };
+}
+macro_rules! run_err {
+    ($str:expr) => {
+        zyxt::interpret(&compile!($str).unwrap(), &mut InterpretSymTable::default())
+            .unwrap_err()
+            .code
+    };
+}
+macro_rules! compile_err {
+    ($str:expr) => {
+        compile!($str).unwrap_err().into_first().code
+    };
+}
+
+#[test]
+fn mutable_declaration() {
+    assert_eq!(run!("x .= 1;\nx = 2;\nret x"), 2);
+    assert_eq!(compile_err!("x := 1;\nx = 2"), "T018");
+}
+#[test]
+fn immutable_field() {
+    let src = "Point := struct |x: i32 .= 0, y: i32 := 0|;\n";
+    assert_eq!(compile_err!(format!("{src}p .= Point();\np.y = 5")), "T019");
+    assert_eq!(compile_err!(format!("{src}p := Point();\np.x = 5")), "T018");
+}
+#[test]
+fn nested_block_return() {
+    // only the file's own block gives its return type, not an `if`'s inside it
+    assert_eq!(run!("if true {\"s\"};\nret 1"), 1);
+}
+#[test]
+fn widen_mixed_ints() {
+    assert_eq!(run!("ret ((1@i8) + (2@i16) - (4@u8))@i32"), -1);
+    assert_eq!(run!("ret ((2@u64) * (3@i32))@i32"), 6);
+}
+#[test]
+fn widen_int_to_float() {
+    assert_eq!(run!("ret (1 + 2.5 + (0.5@f32))@i32"), 4);
+}
+#[test]
+fn widen_comparison() {
+    assert_eq!(run!("ret if (2@u8) < (3@i64) {1} else {0}"), 1);
+}
+#[test]
+fn widen_call_arg() {
+    assert_eq!(run!("f := |x: i64| x * 2;\nret (f (3@i8))@i32"), 6);
+}
+#[test]
+fn widen_result_type() {
+    assert_eq!(
+        compile_err!("f := |x: i8| x;\nf ((1@i8) + (1@i16))"),
+        "T020"
+    );
+}
+#[test]
+fn narrow_call_arg() {
+    assert_eq!(compile_err!("f := |x: i8| x;\nf 300"), "T020");
+}
+#[test]
+fn adapt_int_literal() {
+    assert_eq!(run!("x .= 1@u8;\nx += 1;\nret x @ i32"), 2);
+    assert_eq!(run!("x: i64 .= 1;\nx -= 3;\nret x @ i32"), -2);
+    assert_eq!(run!("ret if (2@u8) < 300 {1} else {0}"), 1);
+    assert_eq!(run_err!("ret ((200@u8) + 100) @ i32"), "I002");
+}
+#[test]
+fn shift_keeps_left_type() {
+    assert_eq!(run!("x: u64 := (1@u64) \\<< 40;\nret (x \\>> 38) @ i32"), 4);
+    assert_eq!(run_err!("x: u8 .= 200;\nx = x \\<< 1"), "I002");
+    assert_eq!(compile_err!("ret 1 \\<< (2@u64)"), "T020");
+}
+#[test]
+fn no_common_type() {
+    assert_eq!(compile_err!("(1@i64) + 2.5"), "T021");
+}
+
+#[test]
+fn overflow_checked() {
+    assert_eq!(run_err!("x := 2147483647;\nret x + 1"), "I002");
+    assert_eq!(
+        run_err!("x := 0 - 2147483647 - 1;\nret x / (0 - 1)"),
+        "I002"
+    );
+    assert_eq!(run_err!("ret 1 / 0"), "I003");
+    assert_eq!(run_err!("ret 1 \\<< 40"), "I002");
+    assert_eq!(run_err!("ret 1 \\>>> (0 - 1)"), "I014");
+}
+#[test]
+fn overflow_wrapping() {
+    set_overflow_mode(OverflowMode::Wrapping);
+    assert_eq!(run!("x := 2147483647;\nret x + 1"), i32::MIN);
+    assert_eq!(run!("ret ((200@u8) * (2@u8))@i32"), 144);
+    assert_eq!(run!("ret ((200@u8) \\<< 1)@i32"), 144);
+    assert_eq!(run_err!("ret 1 \\<< (0 - 1)"), "I014");
+    assert_eq!(run_err!("ret 1 % 0"), "I003");
+}
+#[test]
+fn overflow_saturating() {
+    set_overflow_mode(OverflowMode::Saturating);
+    assert_eq!(run!("x := 2147483647;\nret x + 1"), i32::MAX);
+    assert_eq!(run!("ret ((3@u8) - (5@u8))@i32"), 0);
+    assert_eq!(run!("ret ((200@u8) \\<< 1)@i32"), 255);
+    assert_eq!(run!("ret (0 - 3) \\<< 40"), i32::MIN);
+}
+#[test]
+fn overflow_methods() {
+    assert_eq!(run!("x := 2147483647;\nret x:.wrapping_add(1)"), i32::MIN);
+    assert_eq!(run!("x := 2147483647;\nret x:.saturating_mul(2)"), i32::MAX);
+    assert_eq!(run!("ret (200@u8):.wrapping_sub(201@u8) @ i32"), 255);
+    assert_eq!(run!("ret 7:.saturating_rem(3)"), 1);
+}
+#[test]
+fn overflow_checked_methods() {
+    assert_eq!(
+        run!("x := 2147483647;\nret x:.checked_sub(1) @ i32"),
+        i32::MAX - 1
+    );
+    assert_eq!(
+        run!("x := 2147483647;\nret x:.checked_add(1):.is_none() @ i32"),
+        1
+    );
+    assert_eq!(run!("ret 1:.checked_div(0):.is_some() @ i32"), 0);
+    assert_eq!(
+        run_err!("x := 2147483647;\nret x:.checked_add(1) @ i32"),
+        "I004"
+    );
+}
+#[test]
+fn bitwise_ops() {
+    assert_eq!(run!("ret (12 \\& 10) + (12 \\| 10) * 100"), 1408);
+    assert_eq!(run!("ret 12 \\^ 10"), 6);
+    assert_eq!(run!("ret \\~5"), -6);
+    assert_eq!(run!("ret (1 \\<< 4) + (0 - 16 \\>> 2)"), 12);
+    assert_eq!(run!("ret (0 - 16) \\>>> 28"), 15);
+}
+#[test]
+fn bitwise_big_ops() {
+    assert_eq!(run!("ret ((12@ubig) \\& (10@ubig)) @ i32"), 8);
+    assert_eq!(run!("ret ((12@ubig) \\| (10@ubig)) @ i32"), 14);
+    assert_eq!(run!("ret ((12@ubig) \\^ (10@ubig)) @ i32"), 6);
+    assert_eq!(run!("ret (((1@ubig) \\<< 100) \\>> 98) @ i32"), 4);
+    assert_eq!(run!("ret (\\~(5@ibig)) @ i32"), -6);
+    // a `ubig` has no fixed width to flip the bits of
+    assert_eq!(compile_err!("ret \\~(5@ubig)"), "T028");
+}
+#[test]
+fn number_literals() {
+    assert_eq!(run!("ret (0xff + 0o17 + 0b11)"), 273);
+    assert_eq!(run!("ret (1_000 + -1)"), 999);
+    assert_eq!(run!("ret (1.5e3 @ i32)"), 1500);
+    assert_eq!(run!("ret (-128i8 @ i32)"), -128);
+    assert_eq!(run!("ret if (3u64 @ type) == u64 {1} else {0}"), 1);
+    assert_eq!(run!("ret if (2.5f32 @ type) == f32 {1} else {0}"), 1);
+}
+#[test]
+fn text_literals() {
+    assert_eq!(run!(r#"ret if "a\tb\u{41}" == "a	bA" {1} else {0}"#), 1);
+    assert_eq!(run!(r#"ret if r"a\n" == "a\\n" {1} else {0}"#), 1);
+    assert_eq!(run!(r#"ret c"A" @ u32 @ i32"#), 65);
+    assert_eq!(run!(r#"ret c"8ac3" @ u32 @ i32"#), 0x8ac3);
+    assert_eq!(run!(r#"ret if (97@u8) @ char == c"a" {1} else {0}"#), 1);
+}
+#[test]
+fn fstrings() {
+    assert_eq!(
+        run!(
+            r#"x := 5; s := "zyxt"; ret if f"{s}: {x + 1}, {{x}}" == "zyxt: 6, {x}" {1} else {0}"#
+        ),
+        1
+    );
+    assert_eq!(
+        run!(r#"ret if f"{"a" ~ "b"}{true}" == "abtrue" {1} else {0}"#),
+        1
+    );
+    assert_eq!(
+        run!(
+            r#"ret if f"{255:x} {255:X} {5:b} {8:o} {-5:04} {7:3}" == "ff FF 101 10 -005   7" {1} else {0}"#
+        ),
+        1
+    );
+    assert_eq!(
+        run!(r#"ret if f"{2.5:.2} {1.5:8.3} {1500.0:.1e}" == "2.50    1.500 1.5e3" {1} else {0}"#),
+        1
+    );
+    assert_eq!(compile_err!(r#"ret f"{1.5:x}" @ i32"#), "T031");
+    assert_eq!(compile_err!(r#"ret f"{1:.2}" @ i32"#), "T031");
+    // a spec that isn't a literal can only be checked when it's used
+    assert_eq!(
+        run_err!("x := 1.5;\ns := \"x\";\nret x:._fmt(s) @ i32"),
+        "I005"
+    );
+    assert_eq!(compile_err!(r#"f"{y}""#), "T002");
+}
+#[test]
+fn str_methods() {
+    assert_eq!(
+        run!(r#"t := "  Héllo, Wörld ":.trim(); ret t:.len() * 100 + t:.byte_len()"#),
+        1214
+    );
+    assert_eq!(
+        run!(
+            r#"t := "Héllo, Wörld"; ret if t:.upper() == "HÉLLO, WÖRLD" && t:.substr(1, 4) == "éll" && t:.char_at(7) @ char == c"W" && t:.replace("l", "L") == "HéLLo, WörLd" {t:.find("W") @ i32} else {0}"#
+        ),
+        7
+    );
+    assert_eq!(
+        run!(
+            r#"p := "a,b,,c":.split(","); ret if "-":.join(p) == "a-b--c" && " x  y ":.words():.len() == 2 && "a
+b":.lines():.get(1) @ str == "b" {p:.len()} else {0}"#
+        ),
+        4
+    );
+    assert_eq!(
+        run!(
+            r#"ret if "abc":.starts_with("ab") && !"abc":.contains("d") && "abc" < "abd" {1} else {0}"#
+        ),
+        1
+    );
+    assert_eq!(run_err!(r#""a":.substr(0, 2)"#), "I007");
+    assert_eq!(run_err!(r#""1x" @ i32"#), "I010");
+}
+#[test]
+fn regexes() {
+    let r = r#"r := re::compile "(?P<y>[0-9]{4})-([0-9]{2})"; s := "on 2024-03 and 2025-11";"#;
+    assert_eq!(
+        run!(format!(
+            r#"{r} ret if r:.is_match(s) && r:.find(s) @ str == "2024-03" && r:.named(s, "y") @ str == "2024" {{r:.find_all(s):.len()}} else {{0}}"#
+        )),
+        2
+    );
+    assert_eq!(
+        run!(format!(
+            r#"{r} ret if r:.replace_all(s, "$2/${{y}}") == "on 03/2024 and 11/2025" && r:.captures("x"):.is_none() {{r:.group(s, 2) @ i32}} else {{0}}"#
+        )),
+        3
+    );
+    assert_eq!(run!(r#"ret ("\\s+" @ re):.split("a  b c"):.len()"#), 3);
+    assert_eq!(run_err!(r#"re::compile "(""#), "I011");
+}
+
+/// Runs `src` with a mutable `p` in scope, of a struct with fields `x` (mutable), `y` and `inner`,
+/// where `inner` has a mutable field `z`
+fn run_with_point(src: &str) -> ZResult<i32> {
+    let asts = compile!(format!(
+        "Inner := struct |z: i32 .= 3|;\n\
+         Point := struct |x: i32 .= 1, y: i32 := 2, inner: Inner .= Inner()|;\n\
+         p .= Point();\n{src}"
+    ))
+    .map_err(ZErrors::into_first)?;
+    zyxt::interpret(&asts, &mut InterpretSymTable::default())
+}
+#[test]
+fn field_access() {
+    assert_eq!(run_with_point("ret p.x + p.y").unwrap(), 3);
+    assert_eq!(run_with_point("ret p.inner.z").unwrap(), 3);
+    assert_eq!(run_with_point("q := p; ret q.inner.z * p.x").unwrap(), 3);
+    assert_eq!(run_with_point("ret p.w").unwrap_err().code, "T005");
+}
+#[test]
+fn field_assignment() {
+    assert_eq!(run_with_point("p.x = 5; ret p.x").unwrap(), 5);
+    assert_eq!(
+        run_with_point("p.inner.z = 7; ret p.inner.z + p.x").unwrap(),
+        8
+    );
+    assert_eq!(run_with_point("q := p; p.x = 5; ret q.x").unwrap(), 1);
+    assert_eq!(run_with_point("p.y = 5; ret 0").unwrap_err().code, "T019");
+    assert_eq!(
+        run_with_point("p.x = true; ret 0").unwrap_err().code,
+        "T011"
+    );
+    assert_eq!(
+        run_with_point("q := p; q.x = 5; ret 0").unwrap_err().code,
+        "T018"
+    );
+    // a field of a value that isn't in a variable is rejected before anything runs
+    let cell = "Cell := struct |a: i32 .= 0|;\nter.out \"BEFORE\";\n";
+    assert_eq!(compile_err!(format!("{cell}Cell().a = 3")), "T008");
+    assert_eq!(
+        compile_err!(format!("{cell}(if true {{Cell()}} else {{Cell()}}).a = 3")),
+        "T008"
+    );
+}
+#[test]
+fn struct_construction() {
+    let point = "Point := struct |a: i32, b: i32 := 10|;\n";
+    assert_eq!(run!(format!("{point}p := Point(1, 2);\nret p.a + p.b")), 3);
+    assert_eq!(
+        run!(format!("{point}p := Point(b: 3, a: 4);\nret p.a - p.b")),
+        1
+    );
+    assert_eq!(run!(format!("{point}p := Point(5);\nret p.b")), 10);
+    assert_eq!(run!(format!("{point}p := Point(5, b: 1);\nret p.b")), 1);
+    assert_eq!(
+        run!("Cell := struct |a: i32 .= 0|;\np .= Cell(1);\nq := p;\np.a = 5;\nret q.a"),
+        1
+    );
+    assert_eq!(
+        compile_err!(format!("{point}p .= Point(1);\np.a = 5")),
+        "T019"
+    );
+    assert_eq!(compile_err!(format!("{point}Point(1, 2, 3)")), "T015");
+    assert_eq!(compile_err!(format!("{point}Point(1, a: 2)")), "T022");
+    assert_eq!(compile_err!(format!("{point}Point(b: 2)")), "T023");
+    assert_eq!(compile_err!(format!("{point}Point(1, c: 2)")), "T005");
+    assert_eq!(compile_err!(format!("{point}Point(true)")), "T004");
+    // a struct's fields all come from its parameters, so there's none that a constructor can't set
+    assert_eq!(
+        compile_err!("S := struct |a: i32| { inst b: i32 .= 2; };\nret S(1).b"),
+        "T030"
+    );
+}
+#[test]
+fn struct_equality_and_display() {
+    let point = "Point := struct |a: i32, b: i32 := 10|;\n";
+    assert_eq!(
+        run!(format!(
+            "{point}ret if Point(1) == Point(a: 1, b: 10) && Point(1) != Point(2) {{1}} else {{0}}"
+        )),
+        1
+    );
+    assert_eq!(
+        run!(format!(
+            r#"{point}ret if f"{{Point(1, 2)}}" == "Point {{a: 1, b: 2}}" {{1}} else {{0}}"#
+        )),
+        1
+    );
+}
+#[test]
+fn operator_overloading() {
+    let vec2 = "Vec2 := struct |x: i32, y: i32| {
+        _add := fn |$, o: $ty|: $ty { $ty($.x + o.x, $.y + o.y) };
+        _mul := fn |$, k: i32|: $ty { $ty($.x * k, $.y * k) };
+        _rmul := fn |&$, k: i32|: $ty { $:._mul(k) };
+        _cmp := fn |$, o: $ty|: i32 { $.x - o.x };
+        _not := fn |$|: bool { $.x == 0 };
+    };\n";
+    assert_eq!(
+        run!(format!(
+            "{vec2}v := Vec2(1, 2) + Vec2(3, 4);\nret v.x * v.y"
+        )),
+        24
+    );
+    assert_eq!(run!(format!("{vec2}v := Vec2(1, 2) * 3;\nret v.y")), 6);
+    assert_eq!(run!(format!("{vec2}v := 3 * Vec2(1, 2);\nret v.y")), 6);
+    assert_eq!(
+        run!(format!(
+            "{vec2}a := Vec2(1, 2);\nb := Vec2(2, 0);\n\
+             ret if a < b && b >= a && a == Vec2(1, 5) && a != b {{1}} else {{0}}"
+        )),
+        1
+    );
+    assert_eq!(
+        run!(format!(
+            "{vec2}ret if !Vec2(0, 1) && !false {{1}} else {{0}}"
+        )),
+        1
+    );
+    assert_eq!(
+        compile_err!(format!("{vec2}Vec2(1, 2) - Vec2(1, 2)")),
+        "T028"
+    );
+    assert_eq!(compile_err!(format!("{vec2}Vec2(1, 2) + 1")), "T004");
+}
+
+/// Writes `files` into a new directory and runs `main.xt` in it
+fn run_modules(name: &str, files: &[(&str, &str)]) -> ZResult<i32> {
+    let dir = std::env::temp_dir().join(format!("zyxt-{name}-{}", std::process::id()));
+    for (file, src) in files {
+        let file = dir.join(file);
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(file, src).unwrap();
+    }
+    zyxt::interpret(
+        &zyxt::compile(
+            &Either::Left(&dir.join("main.xt")),
+            &mut TypeCheckSymTable::default(),
+            false,
+        )
+        .map_err(ZErrors::into_first)?,
+        &mut InterpretSymTable::default(),
+    )
+}
+#[test]
+fn modules() {
+    let geo = "factor := 10;
+        scale := |x: i32| x * factor;
+        pub area := |w: i32, h: i32| w * h;
+        pub scaled := |x: i32| scale x;
+        pub Point := struct |x: i32, y: i32| {
+            moved := |&$, k: i32| $ty($.x + k * factor, $.y);
+        };
+        pubp origin := Point(0, 0);";
+    let files = [
+        ("geo.xt", geo),
+        ("lib/util.xt", "pub double := |x: i32| x * 2;"),
+        (
+            "main.xt",
+            "use geo;\nuse lib::util;\n\
+             p := geo::Point(1, 2):.moved(1);\n\
+             ret geo::area(3, 4) + geo::scaled(2) + geo::origin.x + p.x + util::double(1)",
+        ),
+    ];
+    assert_eq!(run_modules("modules", &files).unwrap(), 12 + 20 + 11 + 2);
+    let files = [
+        ("lib/util.xt", "pub double := |x: i32| x * 2;"),
+        ("main.xt", "use \"lib/util.xt\";\nret util::double(3)"),
+    ];
+    assert_eq!(run_modules("modules_str", &files).unwrap(), 6);
+}
+#[test]
+fn module_errors() {
+    let private = [
+        ("m.xt", "x := 1;\npub y := 2;"),
+        ("main.xt", "use m;\nret m::x"),
+    ];
+    assert_eq!(
+        run_modules("module_private", &private).unwrap_err().code,
+        "T005"
+    );
+    let missing = [("main.xt", "use m;")];
+    assert_eq!(
+        run_modules("module_missing", &missing).unwrap_err().code,
+        "T024"
+    );
+    let cycle = [
+        ("a.xt", "use b;\npub x := 1;"),
+        ("b.xt", "use a;\npub y := 2;"),
+        ("main.xt", "use a;"),
+    ];
+    let err = run_modules("module_cycle", &cycle).unwrap_err();
+    assert_eq!(err.code, "T025");
+    assert!(err.message.contains("a.xt` -> `") && err.message.contains("b.xt` -> `"));
+}
+#[test]
+fn packages() {
+    let files = [
+        (
+            "zyxt.toml",
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\nentry = \"main.xt\"\n\
+             [dependencies]\nshapes = { path = \"shapes\" }\n",
+        ),
+        ("util.xt", "pubp inner := 5;"),
+        (
+            "shapes/zyxt.toml",
+            "[package]\nname = \"shapes\"\nversion = \"0.1.0\"\n",
+        ),
+        (
+            "shapes/src/main.xt",
+            "use extra;\npub square := |x: i32| x * x;\npubp secret := 7;\n\
+             pub tripled := |x: i32| extra::triple x;",
+        ),
+        (
+            "shapes/src/extra.xt",
+            "pubp triple := |x: i32| x * 3;\npub quad := |x: i32| x * 4;",
+        ),
+        (
+            "main.xt",
+            "use shapes;\nuse shapes::extra;\nuse util;\n\
+             ret shapes::square(3) + shapes::tripled(2) + extra::quad(2) + util::inner",
+        ),
+    ];
+    assert_eq!(run_modules("packages", &files).unwrap(), 9 + 6 + 8 + 5);
+    let mut private = files;
+    private[5].1 = "use shapes;\nshapes::secret";
+    assert_eq!(
+        run_modules("packages_private", &private).unwrap_err().code,
+        "T029"
+    );
+    private[5].1 = "use shapes::extra;\nextra::triple(1)";
+    assert_eq!(
+        run_modules("packages_private_nested", &private)
+            .unwrap_err()
+            .code,
+        "T029"
+    );
+    let mut missing = files;
+    missing[0].1 = "[package]\nname = \"app\"\nversion = \"0.1.0\"\nentry = \"main.xt\"\n\
+                    [dependencies]\nshapes = { path = \"nowhere\" }\n";
+    assert_eq!(
+        run_modules("packages_missing", &missing).unwrap_err().code,
+        "M003"
+    );
+}
+#[test]
+fn new_package() {
+    let dir = std::env::temp_dir().join(format!("zyxt-new-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let package = Package::create(&dir.join("app"), "app").unwrap();
+    let found = Package::find(&dir.join("app").join("src"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(found, package);
+    assert_eq!(found.name(), "app");
+    assert!(found.entry().is_file());
+    assert_eq!(
+        Package::create(&dir.join("app"), "app").unwrap_err().code,
+        "M004"
+    );
+    assert_eq!(
+        Package::create(&dir.join("x"), "1x").unwrap_err().code,
+        "M005"
+    );
+}
+#[test]
+fn std_math() {
+    assert_eq!(run!("use std::math;\nret math::sqrt(16.0) @ i32"), 4);
+    assert_eq!(run!("use std::math;\nret math::rt(-27.0, 3.0) @ i32"), -3);
+    assert_eq!(run!("use std::math;\nret math::log(1024.0, 2.0) @ i32"), 10);
+    assert_eq!(run!("use std::math;\nret math::gamma(6.0) @ i32"), 120);
+    assert_eq!(
+        run!("use std::math;\nret math::factorial(10) @ i32"),
+        3_628_800
+    );
+    assert_eq!(
+        run!("use std::math;\nret (math::sin(math::pi / 2.0) + math::ln math::e) @ i32"),
+        2
+    );
+    assert_eq!(
+        run!("use std::math;\np := math::to_polar(0.0, 3.0);\nret math::to_cartesian(p.r, p.theta).y @ i32"),
+        3
+    );
+    assert_eq!(run!("use std::math;\nret math::Polar(2.0, 0.0).r @ i32"), 2);
+}
+#[test]
+fn std_math_domain_errors() {
+    assert_eq!(run_err!("use std::math;\nmath::ln(-1.0)"), "I007");
+    assert_eq!(run_err!("use std::math;\nmath::log(2.0, 1.0)"), "I007");
+    assert_eq!(run_err!("use std::math;\nmath::rt(-4.0, 2.0)"), "I007");
+    assert_eq!(run_err!("use std::math;\nmath::acos(2.0)"), "I007");
+    assert_eq!(run_err!("use std::math;\nmath::gamma(-2.0)"), "I007");
+    assert_eq!(run_err!("use std::math;\nmath::factorial(-1)"), "I007");
+    assert_eq!(run!("use std::math;\nret math::sqrt(16@i64) @ i32"), 4);
+    assert_eq!(run!("use std::math;\nret math::rt(8@u128, 3@u8) @ i32"), 2);
+    assert_eq!(
+        run!("use std::math;\nret (math::sqrt(10000000000000000000000000000000000000000@ibig) / 1e19\n+ math::sqrt(2.25@f32)) @ i32"),
+        11
+    );
+    assert_eq!(compile_err!("use std::math;\nmath::sqrt(\"4\")"), "T004");
+}
+#[test]
+fn ter_namespace() {
+    assert_eq!(run!("t := ter;\nt.out 1, \"a\", 2.5;\nret 0"), 0);
+    assert_eq!(
+        run!("out := ter.out;\nout();\nter.print \"\";\nter.flush();\nret 1"),
+        1
+    );
+    assert_eq!(run!("get := fn { ter };\nget().err \"err\";\nret 2"), 2);
+    assert_eq!(run!("ter := 3;\nret ter"), 3);
+    assert!(compile!("line: str := ter.in \"prompt\";").is_ok());
+    assert_eq!(compile_err!("ter := 3;\nter.out 1"), "T005");
+    assert_eq!(compile_err!("ter.flush 1"), "T015");
+}
+#[test]
+fn exit_code() {
+    assert_eq!(run!("ret 3"), 3);
+    // a last value that isn't an `i32`, like a declaration's, leaves the exit code at 0
+    assert_eq!(run!("x: i8 := 1@i8"), 0);
+    assert_eq!(run!("\"s\""), 0);
+}
+#[test]
+fn std_sys() {
+    set_args(vec!["main.xt".into(), "a".into(), "bc".into()]);
+    assert_eq!(run!("use std::sys;\nret sys::argc()"), 3);
+    assert_eq!(
+        run!("use std::sys;\nret if (sys::arg(2) @ str) == \"bc\" {1} else {0}"),
+        1
+    );
+    assert_eq!(
+        run!("use std::sys;\nret if sys::arg(3):.is_none() {1} else {0}"),
+        1
+    );
+    assert_eq!(
+        run!("use std::sys;\nsys::set_env(\"ZYXT_TEST_VAR\", \"5\");\nret sys::env(\"ZYXT_TEST_VAR\") @ str @ i32"),
+        5
+    );
+    assert_eq!(run!("use std::sys;\nt := sys::monotonic();\nsys::sleep 0.01;\nret if sys::monotonic() > t {1} else {0}"), 1);
+    assert_eq!(
+        run!("use std::sys;\nf := fn { sys::exit 4; };\nf();\nret 1"),
+        4
+    );
+    // the defer fails, which shows that it ran
+    assert_eq!(
+        run_err!(
+            "use std::sys;\nf := fn { defer { (1@u8) - (2@u8) }; sys::exit 4; };\nf();\nret 1"
+        ),
+        "I002"
+    );
+    assert_eq!(run_err!("use std::sys;\nsys::sleep(-1.0)"), "I007");
+}
+#[test]
+fn std_fs() {
+    let dir = std::env::temp_dir().join(format!("zyxt-std_fs-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let dir = dir.display();
+    let src = format!(
+        "use std::fs;\nuse std::collections;\nd := \"{dir}\";\nfs::create_dir(fs::join(d, \"sub\"));\n\
+         p := fs::join(d, \"a.txt\");\nfs::write(p, \"ab\");\nfs::append(p, \"c\");\n\
+         fs::write_bytes(fs::join(d, \"b\"), collections::vec_of(255@u8, 0@u8));\n\
+         ok := fs::read(p) == \"abc\"\n\
+         && fs::read_bytes(fs::join(d, \"b\")) == collections::vec_of(255@u8, 0@u8)\n\
+         && fs::list_dir(d) == collections::vec_of(\"a.txt\", \"b\", \"sub\")\n\
+         && fs::is_dir(fs::join(d, \"sub\"));\n\
+         fs::remove_file p;\nret if ok && !fs::exists(p) {{fs::size(fs::join(d, \"b\")) @ i32}} else {{0}}"
+    );
+    assert_eq!(run!(src), 2);
+    assert_eq!(
+        run!("use std::fs;\nret if fs::normalize(\"a/./b/../../../c\") == \"../c\" {1} else {0}"),
+        1
+    );
+    assert_eq!(
+        run_err!(format!("use std::fs;\nfs::read \"{dir}/missing\"")),
+        "I008"
+    );
+    assert_eq!(
+        run_err!(format!(
+            "use std::fs;\nuse std::collections;\n\
+             fs::write_bytes(\"{dir}/c\", collections::vec_of(256))"
+        )),
+        "I007"
+    );
+}
+
+#[test]
+fn std_datetime() {
+    let src = "use std::datetime;\nd := datetime::from_ymd(2024, 2, 28);\n\
+               t := datetime::from_hms(23, 30, 15.5);\ndt := d:.at(t):.to_offset(19800);\n\
+               a := (d + datetime::days(2)) @ str == \"2024-03-01\" && d:.weekday() == 3\n\
+               && (t + datetime::hours(1)) @ str == \"00:30:15.500\";\n\
+               b := dt @ str == \"2024-02-29T05:00:15.500+05:30\" && dt:.offset() == 19800\n\
+               && dt:.format(\"%d/%m/%Y %H:%M\") == \"29/02/2024 05:00\";\n\
+               c := dt == datetime::parse(\"2024-02-28T23:30:15.5Z\")\n\
+               && datetime::parse(\"2024-03-01T00:00:00\") > dt;\n\
+               e := datetime::parse_fmt(\"01.03.2024\", \"%d.%m.%Y\") == datetime::unix(1709251200.0)\n\
+               && datetime::parse_date(\"2024-03-01\"):.since(d) == datetime::days(2)\n\
+               && (datetime::minutes(1.5) * 2) @ str == \"PT180S\";\n\
+               ret if a && b && c && e {dt:.to_utc():.hour()} else {0}";
+    assert_eq!(run!(src), 23);
+    assert_eq!(
+        run_err!("use std::datetime;\ndatetime::from_ymd(2023, 2, 29)"),
+        "I007"
+    );
+    assert_eq!(
+        run_err!("use std::datetime;\ndatetime::parse_time(\"25:00\")"),
+        "I010"
+    );
+    assert_eq!(
+        run_err!("use std::datetime;\ndatetime::from_ymd(2024, 1, 1):.format(\"%H\")"),
+        "I005"
+    );
+    assert_eq!(
+        run_err!("use std::datetime;\ndatetime::days(1e300)"),
+        "I007"
+    );
+}
+
+#[test]
+fn std_argparse() {
+    let parser = "use std::argparse;\n\
+                  add := argparse::parser(\"add\", \"Adds a file\"):.positional(\"file\", \"\", str);\n\
+                  p := argparse::parser(\"tool\"):.positional(\"count\", \"\", i32):.default(\"count\", 1)\n\
+                  :.flag(\"verbose\", \"v\", \"\"):.option(\"times\", \"n\", \"\", i32):.subcommand(add);\n";
+    assert_eq!(
+        run!(format!(
+            "{parser}a := p:.parse_from(\"-vn5\", \"add\", \"x.xt\");\n\
+             ok := a:.flag(\"verbose\") && a:.get(\"file\") @ str == \"x.xt\" && a:.command() @ str == \"add\";\n\
+             ret if ok {{(a:.get(\"count\") @ i32) + (a:.get(\"times\") @ i32)}} else {{0}}"
+        )),
+        6
+    );
+    assert_eq!(
+        run!(format!(
+            "{parser}p:.parse_from(\"--times\", \"x\", \"add\", \"y\");\nret 0"
+        )),
+        2
+    );
+    assert_eq!(run!(format!("{parser}p:.parse_from(\"3\");\nret 0")), 2);
+    assert_eq!(
+        run!(format!("{parser}p:.parse_from(\"--help\");\nret 1")),
+        0
+    );
+    assert_eq!(
+        run_err!(format!("{parser}p:.flag(\"verbose\", \"\", \"\")")),
+        "I007"
+    );
+    // a subcommand's values go alongside its parent's, so their names can't be shared
+    assert_eq!(
+        run_err!(format!("{parser}p:.flag(\"file\", \"\", \"\")")),
+        "I007"
+    );
+    assert_eq!(
+        run_err!(format!(
+            "{parser}p:.subcommand(argparse::parser(\"rm\"):.flag(\"verbose\", \"\", \"\"))"
+        )),
+        "I007"
+    );
+}
+
+#[test]
+fn std_collections() {
+    let src = "use std::collections;\nv := collections::vec_of(3, 1, 2);\n\
+               a := collections::map(v, |x: i32| {x * 2}) == collections::vec_of(6, 2, 4)\n\
+               && collections::filter(v, |x: i32| {x > 1}) @ str == \"[3, 2]\";\n\
+               b := collections::sort(v) == collections::vec_of(1, 2, 3)\n\
+               && collections::sort_by(v, |a: i32, b: i32| {b - a}) @ str == \"[3, 2, 1]\"\n\
+               && collections::binary_search(collections::sort(v), 3) @ i32 == 2;\n\
+               c := collections::zip(v, collections::enumerate(v)):.get(1) @ str == \"[1, [1, 1]]\"\n\
+               && collections::any(v, |x: i32| {x > 2}) && !collections::all(v, |x: i32| {x > 2});\n\
+               e := collections::count_from(1):.map(|x: i32| {x * x}):.filter(|x: i32| {x % 2 == 1})\n\
+               :.take(3):.collect() == collections::vec_of(1, 9, 25);\n\
+               ret if a && b && c && e {(collections::fold(v, 10, |a: i32, b: i32| {a + b}) @ i32)\n\
+               + (collections::max(v) @ i32)} else {0}";
+    assert_eq!(run!(src), 19);
+    assert_eq!(
+        compile_err!(
+            "use std::collections;\ncollections::filter(collections::vec_of(1), |x: i32| {x})"
+        ),
+        "T011"
+    );
+    assert_eq!(
+        compile_err!("use std::collections;\ncollections::count_from(1):.filter(|x: i32| {x})"),
+        "T011"
+    );
+    assert_eq!(
+        compile_err!(
+            "use std::collections;\ncollections::sort_by(collections::vec_of(1), |a: i32, b: i32| {a > b})"
+        ),
+        "T011"
+    );
+    assert_eq!(
+        compile_err!("use std::collections;\ncollections::map(collections::vec_of(1), 5)"),
+        "T004"
+    );
+    assert_eq!(
+        run_err!("use std::collections;\ncollections::range(0, 3):.take(0 - 1)"),
+        "I007"
+    );
+    assert_eq!(
+        run_err!(
+            "use std::collections;\ncollections::map(collections::vec_of(\"a\"), |x: i32| {x * 2})"
+        ),
+        "T004"
+    );
+    assert_eq!(
+        run_err!(
+            "use std::collections;\ncollections::sort(collections::vec_of(1.0, 0.0 / 0.0, 0.5))"
+        ),
+        "I007"
+    );
+}
+
+#[test]
+fn std_json() {
+    let src = r#"use std::json;
+use std::collections;
+m := json::parse("{\"a\": [1, 2.5, null], \"b\": {\"c\": \"x\\u00e9\"}}") @ collections::hmap;
+a := json::stringify(m, false) == "{\"a\":[1,2.5,null],\"b\":{\"c\":\"xé\"}}";
+b := json::parse(json::stringify(m, true)) @ collections::hmap == m && m:.keys() @ str == "[a, b]";
+Point := struct |y: i32, x: i32|;
+c := json::stringify(Point(1, 2), false) == "{\"y\":1,\"x\":2}";
+ret if a && b && c {(m:.get "a" @ collections::vec):.len()} else {0}"#;
+    assert_eq!(run!(src), 3);
+    assert_eq!(
+        run!("use std::json;\ns := \"[99999999999999999999999,-9223372036854775809]\";\nret if json::stringify(json::parse s, false) == s {1} else {0}"),
+        1
+    );
+    assert_eq!(
+        run_err!("use std::json;\njson::parse \"[1,\n 2,]\""),
+        "I012"
+    );
+    assert_eq!(
+        run_err!("use std::json;\njson::stringify(json::parse, false)"),
+        "I013"
+    );
+}
+
+#[test]
+fn malformed_input_errors() {
+    assert_eq!(compile_err!("x := 1; x.0"), "P026");
+    assert_eq!(compile_err!("f("), "P009");
+    assert_eq!(compile_err!("x := 1; &x"), "T026");
+    assert_eq!(
+        compile_err!(
+            "use std::collections;\ncollections::map(collections::vec_of(1), |a: i32, b: i32| {a})"
+        ),
+        "T015"
+    );
+}
+
+#[test]
+fn file_errors() {
+    let dir = std::env::temp_dir().join(format!("zyxt-file_errors-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("bad.xt"), b"ret 1\xff").unwrap();
+    let compile_file = |file: &std::path::Path| {
+        zyxt::compile(
+            &Either::Left(file),
+            &mut TypeCheckSymTable::default(),
+            false,
+        )
+        .unwrap_err()
+        .into_first()
+    };
+    let missing = compile_file(&dir.join("missing.xt"));
+    assert_eq!((missing.code, missing.exit_code()), ("F001", 3));
+    assert!(missing.pos.is_empty());
+    assert_eq!(compile_file(&dir.join("bad.xt")).code, "F003");
+    assert_eq!(compile_file(&dir).code, "F004");
+    assert_eq!(compile!("x").unwrap_err().exit_code(), 1);
+}
+
+#[test]
+fn multiple_errors() {
+    let codes = |src: &str| {
+        compile!(src)
+            .unwrap_err()
+            .0
+            .into_iter()
+            .map(|e| e.code)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(codes("a := 1 +;\nb := 2;\nc := f(;\n"), ["P006", "P009"]);
+    // the statements that parsed are still type checked, and variables declared by ones that
+    // didn't, or by failed `use`s, aren't reported as undefined
+    assert_eq!(
+        codes("w := (1;\nx := w + 1;\ny := 1 + \"a\";\nz: i32 := \"s\";\nter.out undefined;"),
+        ["P009", "T004", "T011", "T002"]
+    );
+    assert_eq!(
+        codes("use nothere;\nnothere::f();\nf := proc {a := (;};\nf();"),
+        ["T024", "P009"]
+    );
+    // uses of `b`, whose declaration failed, aren't reported again
+    assert_eq!(
+        codes("b := undefined;\nc := b + 1;\nter.out c;\nd := \"s\" - 1;\nb = 2;"),
+        ["T002", "T028"]
+    );
+    let mut ty_symt = TypeCheckSymTable::default();
+    let mut compile_with = |src: &str| {
+        zyxt::compile(
+            &Either::Right(("multiple_errors".into(), src.to_owned())),
+            &mut ty_symt,
+            false,
+        )
+        .unwrap_err()
+        .into_first()
+        .code
+    };
+    assert_eq!(compile_with("b := undefined;"), "T002");
+    assert_eq!(compile_with("b + 1"), "T027");
+    zyxt::errors::set_error_limit(1);
+    assert_eq!(codes("1 + \"a\";\n2 + \"b\";\n"), ["T004"]);
+}
+
+#[test]
+fn typed_declaration() {
+    assert_eq!(run!("x: i64 .= 1;\nx = 2@i64;\nret x @ i32"), 2);
+    assert_eq!(
+        run!("x: i64 := 1@i8;\ny: u8 := 200;\nret (x + y) @ i32"),
+        201
+    );
+    assert_eq!(compile_err!("x: i32 := 2.7"), "T020");
+    assert_eq!(compile_err!("x: u8 := 300"), "T020");
+    assert_eq!(compile_err!("y := 1@i64;\nx: i8 := y"), "T020");
+    assert_eq!(compile_err!("x: str := 1;\nret x @ i32 + 1"), "T011");
+    assert_eq!(compile_err!("q: i32 := \"s\""), "T011");
+}
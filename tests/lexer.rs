@@ -1,10 +1,9 @@
-#![feature(assert_matches)]
-use std::assert_matches::assert_matches;
+use std::assert_matches;
 
 use pretty_assertions::assert_eq;
 use proptest::prelude::*;
 use smol_str::SmolStr;
-use zyxt::types::token::TokenType;
+use zyxt::types::token::{OprType, TokenType};
 
 macro_rules! lex {
     ($str:expr) => {
@@ -68,12 +67,137 @@ fn whitespace_inner(s: String) {
     assert_eq!(re[1].whitespace, SmolStr::from(s));
 }
 #[test]
+fn word_dollar() {
+    for w in ["$", "$ty"] {
+        let re = lex!(w);
+        assert_eq!(re.len(), 1);
+        assert_eq!(re[0].value, SmolStr::from(w));
+        assert_eq!(re[0].ty, Some(TokenType::Ident));
+    }
+}
+#[test]
 fn symbol() {
     let re = lex!(":=");
     assert_eq!(re.len(), 1);
     assert_eq!(re[0].value, SmolStr::from(":="));
     assert_eq!(re[0].ty, Some(TokenType::DeclarationOpr));
 }
+#[test]
+fn symbol_bitwise() {
+    for (sy, ty) in [
+        ("\\&", TokenType::BinaryOpr(OprType::BitAnd)),
+        ("\\|", TokenType::BinaryOpr(OprType::BitOr)),
+        ("\\^", TokenType::BinaryOpr(OprType::BitXor)),
+        ("\\~", TokenType::UnaryOpr(OprType::BitCompl)),
+        ("\\<<", TokenType::BinaryOpr(OprType::Lsh)),
+        ("\\>>", TokenType::BinaryOpr(OprType::Rsh)),
+        ("\\>>>", TokenType::BinaryOpr(OprType::Zrsh)),
+        ("\\&=", TokenType::AssignmentOpr(Some(OprType::BitAnd))),
+        ("\\>>>=", TokenType::AssignmentOpr(Some(OprType::Zrsh))),
+    ] {
+        let re = lex!(sy);
+        assert_eq!(re.len(), 1);
+        assert_eq!(re[0].value, SmolStr::from(sy));
+        assert_eq!(re[0].ty, Some(ty));
+    }
+}
+#[test]
+fn symbol_mut_declaration() {
+    let re = lex!(".=");
+    assert_eq!(re.len(), 1);
+    assert_eq!(re[0].value, SmolStr::from(".="));
+    assert_eq!(re[0].ty, Some(TokenType::MutDeclarationOpr));
+}
+#[test]
+fn literal_number_forms() {
+    for n in [
+        "3u64",
+        "6i8",
+        "0xff",
+        "0xFFu8",
+        "0o17",
+        "0b1010_1010",
+        "1_000_000",
+        "1.5e-3",
+        "2E+10",
+        "2.5f32",
+        "7f16",
+        "12ibig",
+        "-6i8",
+        "-128i8",
+        "-0x10",
+    ] {
+        let re = lex!(n);
+        assert_eq!(re.len(), 1);
+        assert_eq!(re[0].value, SmolStr::from(n));
+        assert_eq!(re[0].ty, Some(TokenType::LiteralNumber));
+    }
+}
+#[test]
+fn literal_number_minus() {
+    let re = lex!("x -1");
+    assert_eq!(re.len(), 3);
+    assert_eq!(re[1].ty, Some(TokenType::BinaryOpr(OprType::Sub)));
+    let re = lex!("x - -1");
+    assert_eq!(re.len(), 3);
+    assert_eq!(re[2].value, SmolStr::from("-1"));
+}
+#[test]
+fn literal_number_errors() {
+    for (n, code) in [
+        ("256u8", "L004"),
+        ("-1u8", "L004"),
+        ("1e999", "L004"),
+        ("0b102", "L003"),
+        ("1.5i32", "L003"),
+        ("12abc", "L003"),
+        ("0x", "L003"),
+    ] {
+        let err = zyxt::lexer::lex(format!("x := {n};"), "".into()).unwrap_err();
+        assert_eq!(err.code, code);
+        assert_eq!(err.pos[0].start_pos.column, 6);
+        assert_eq!(err.pos[0].end_pos.column, 5 + n.len());
+    }
+}
+#[test]
+fn literal_text_forms() {
+    for s in [
+        r#""a\tb\"c\\d""#,
+        r#""\u{1F600}\0""#,
+        r#"r"raw\q""#,
+        r#"c"a""#,
+        r#"c"8ac3""#,
+        r#"c"\n""#,
+        r#"f"a {x} b""#,
+        r#"f"{{ {f"{x}" ~ "}"} }}""#,
+        r#"f"{x:08.2} {y:x}\n""#,
+    ] {
+        let re = lex!(s);
+        assert_eq!(re.len(), 1);
+        assert_eq!(re[0].value, SmolStr::from(s));
+        assert_eq!(re[0].ty, Some(TokenType::LiteralString));
+    }
+}
+#[test]
+fn literal_text_errors() {
+    for (s, code, column, len) in [
+        (r#""a\q""#, "L005", 8, 2),
+        (r#""\u{110000}""#, "L005", 7, 10),
+        (r#""\u12""#, "L005", 7, 2),
+        (r#"c"xyz""#, "L007", 6, 6),
+        (r#""abc"#, "L006", 6, 1),
+        (r#"r"abc"#, "L006", 6, 2),
+        (r#"f"a}""#, "L008", 9, 1),
+        (r#"f"{ }""#, "L009", 8, 1),
+        (r#"f"{x} \q""#, "L005", 12, 2),
+        (r#"f"{x"#, "L006", 6, 2),
+    ] {
+        let err = zyxt::lexer::lex(format!("x := {s}"), "".into()).unwrap_err();
+        assert_eq!(err.code, code);
+        assert_eq!(err.pos[0].start_pos.column, column);
+        assert_eq!(err.pos[0].end_pos.column, column + len - 1);
+    }
+}
 proptest! {
     #[test]
     fn word(s in "[A-Za-z_][0-9A-Za-z_]{1,}".prop_filter("", |s| !["true", "false"].contains(&&**s))) {
@@ -88,7 +212,7 @@ proptest! {
         literal_float_inner(n.abs())
     }
     #[test]
-    fn literal_string(s in any::<String>().prop_filter("", |s| !s.contains('"'))) {
+    fn literal_string(s in any::<String>().prop_filter("", |s| !s.contains(['"', '\\']))) {
         literal_string_inner(s)
     }
     #[test]
@@ -0,0 +1,172 @@
+use itertools::Either;
+use proptest::prelude::*;
+use zyxt::types::sym_table::{InterpretSymTable, TypeCheckSymTable};
+
+/// Tokens that random programs are made of. Loops, input and files are left out so that programs
+/// can't hang or touch anything outside the test.
+const TOKENS: &[&str] = &[
+    "x",
+    "y",
+    "f",
+    "i32",
+    "str",
+    "bool",
+    "_any",
+    "ter",
+    "out",
+    "std",
+    "math",
+    "collections",
+    "json",
+    "datetime",
+    "argparse",
+    "re",
+    "u8",
+    "ibig",
+    "f32",
+    "0",
+    "1",
+    "2.5",
+    "0xff",
+    "0b1010",
+    "1_000",
+    "200u8",
+    "-6i8",
+    "1.5e-3",
+    "2.5f32",
+    "\"a\"",
+    "f\"{x}\"",
+    "f\"{x:08.3} {y:x}\"",
+    "c\"a\"",
+    "r\"\\d\"",
+    "\"\\n\\u{1F600}\"",
+    "true",
+    "false",
+    "&",
+    "+",
+    "-",
+    "*",
+    "/",
+    "%",
+    "^",
+    "==",
+    "!=",
+    "<",
+    ">",
+    "<=",
+    ">=",
+    "&&",
+    "||",
+    "\\&",
+    "\\|",
+    "\\^",
+    "\\~",
+    "\\<<",
+    "\\>>",
+    "\\>>>",
+    "\\&=",
+    "!",
+    "@",
+    ":=",
+    ".=",
+    "=",
+    "+=",
+    ".",
+    ":.",
+    "::",
+    "(",
+    ")",
+    "{",
+    "}",
+    "[",
+    "]",
+    ",",
+    ";",
+    "|",
+    ":",
+    "if",
+    "else",
+    "elif",
+    "ret",
+    "defer",
+    "proc",
+    "fn",
+    "pre",
+    "class",
+    "struct",
+    "use",
+    "del",
+    "pub",
+    "inst",
+    "const",
+];
+/// Snippets that are valid on their own, so that programs made of them get past parsing more often
+const SNIPPETS: &[&str] = &[
+    "x := 1;",
+    "y .= \"a\";",
+    "f := |x: i32| {x};",
+    "f(1)",
+    "f(1, 2)",
+    "x:.y",
+    "x.y",
+    "i32::y",
+    "ter.out",
+    "use std::math;",
+    "use std::collections;",
+    "collections::vec_of(1, 2)",
+    "math::sqrt 2.5",
+    "S := struct |a: i32|;",
+    "S(1)",
+    "S(a: 1)",
+    "C := class { inst a: i32 := 0; };",
+    "C()",
+    "if true {1} else {2}",
+    "ret 1;",
+    "defer ter.out 1;",
+    "\"a\" @ i32",
+    "1 @ str",
+    "f\"{x:x} {{y}}\"",
+    "(1@u8) \\<< (3@i64)",
+    "use std::json;",
+    "json::parse \"[1, 99999999999999999999]\"",
+    "json::stringify(x, true)",
+    "use std::datetime;",
+    "datetime::parse \"2024-02-29T05:00:15Z\"",
+    "datetime::days 1.5",
+    "use std::argparse;",
+    "argparse::parser(\"t\"):.flag(\"v\", \"v\", \"\"):.parse_from(\"-v\")",
+    "re::compile \"[0-9]+\"",
+    "\"a1\" @ re",
+];
+
+/// Lexes, parses, type checks and interprets `src`, which may give an error but must not panic
+fn run_inner(src: &str) {
+    let Ok(lexed) = zyxt::lexer::lex(src.to_owned(), "".into()) else {
+        return;
+    };
+    let _ = zyxt::parser::parse_token_list(lexed);
+    let Ok(asts) = zyxt::compile(
+        &Either::Right(("no_panics".into(), src.to_owned())),
+        &mut TypeCheckSymTable::default(),
+        false,
+    ) else {
+        return;
+    };
+    let _ = zyxt::interpret(&asts, &mut InterpretSymTable::default());
+}
+
+proptest! {
+    #[test]
+    fn random_tokens(tokens in prop::collection::vec(prop::sample::select(TOKENS), 0..16)) {
+        run_inner(&tokens.join(" "))
+    }
+    #[test]
+    fn random_snippets(
+        tokens in prop::collection::vec(
+            prop_oneof![prop::sample::select(TOKENS), prop::sample::select(SNIPPETS)],
+            0..12,
+        )
+    ) {
+        run_inner(&tokens.join(" "))
+    }
+}
@@ -7,7 +7,7 @@ use zyxt::{
     ast::*,
     types::{
         position::{Position, Span},
-        token::{Flag, OprType},
+        token::{AccessType, Flag, OprType},
         value::Value,
     },
 };
@@ -102,6 +102,12 @@ fn bin_opr() {
         ("<=", OprType::Le),
         ("&&", OprType::And),
         ("||", OprType::Or),
+        ("\\&", OprType::BitAnd),
+        ("\\|", OprType::BitOr),
+        ("\\^", OprType::BitXor),
+        ("\\<<", OprType::Lsh),
+        ("\\>>", OprType::Rsh),
+        ("\\>>>", OprType::Zrsh),
     ] {
         let s = format!("x {sy} y");
         let ast = parse!(s);
@@ -124,11 +130,13 @@ fn class() {
         ast[0],
         Ast::Class(Class::Raw {
             is_struct: false,
+            kwd_span: Some(span!(1, 1, "class")),
             content: Some(Block {
-                brace_spans: None,
+                brace_spans: Some((span!(1, 7, "{"), span!(1, 9, "}"))),
                 content: vec![]
             }),
-            args: None
+            args: None,
+            name: None
         })
     )
 }
@@ -141,6 +149,7 @@ fn struct_params() {
         ast[0],
         Ast::Class(Class::Raw {
             is_struct: true,
+            kwd_span: Some(span!(1, 1, "struct")),
             content: Some(Block {
                 brace_spans: None,
                 content: vec![]
@@ -148,8 +157,10 @@ fn struct_params() {
             args: Some(vec![Argument {
                 name: ident!(notvar 1, 1, "x"),
                 ty: ident!(1, 11, "i32"),
-                default: None
-            }])
+                default: None,
+                is_mut: false
+            }]),
+            name: None
         })
     )
 }
@@ -162,12 +173,15 @@ fn struct_no_content() {
         ast[0],
         Ast::Class(Class::Raw {
             is_struct: true,
+            kwd_span: Some(span!(1, 1, "struct")),
             content: None,
             args: Some(vec![Argument {
                 name: ident!(notvar 1, 1, "x"),
                 ty: ident!(1, 11, "i32"),
-                default: None
-            }])
+                default: None,
+                is_mut: false
+            }]),
+            name: None
         })
     )
 }
@@ -180,11 +194,13 @@ fn struct_no_params() {
         ast[0],
         Ast::Class(Class::Raw {
             is_struct: true,
+            kwd_span: Some(span!(1, 1, "struct")),
             content: Some(Block {
                 brace_spans: None,
                 content: vec![]
             }),
-            args: None
+            args: None,
+            name: None
         })
     )
 }
@@ -197,8 +213,10 @@ fn struct_no_content_no_params() {
         ast[0],
         Ast::Class(Class::Raw {
             is_struct: true,
+            kwd_span: Some(span!(1, 1, "struct")),
             content: None,
-            args: None
+            args: None,
+            name: None
         })
     )
 }
@@ -213,7 +231,38 @@ fn declaration() {
             content: ident!(1, 6, "y"),
             flags: vec![],
             ty: None,
-            eq_span: None,
+            is_mut: false,
+            eq_span: Some(span!(1, 3, ":=")),
+        })
+    )
+}
+#[test]
+fn declaration_mut() {
+    let ast = parse!("x .= y");
+    assert_eq!(
+        ast[0],
+        Ast::Declare(Declare {
+            variable: ident!(1, 1, "x"),
+            content: ident!(1, 6, "y"),
+            flags: vec![],
+            ty: None,
+            is_mut: true,
+            eq_span: Some(span!(1, 3, ".=")),
+        })
+    )
+}
+#[test]
+fn declaration_typed() {
+    let ast = parse!("x: i32 := y");
+    assert_eq!(
+        ast[0],
+        Ast::Declare(Declare {
+            variable: ident!(1, 1, "x"),
+            content: ident!(1, 11, "y"),
+            flags: vec![],
+            ty: Some(ident!(1, 4, "i32")),
+            is_mut: false,
+            eq_span: Some(span!(1, 8, ":=")),
         })
     )
 }
@@ -227,7 +276,8 @@ fn declaration_flags() {
             content: ident!(1, 10, "y"),
             flags: vec![(Flag::Pub, span!(1, 1, "pub"))],
             ty: None,
-            eq_span: None,
+            is_mut: false,
+            eq_span: Some(span!(1, 7, ":=")),
         })
     )
 }
@@ -267,10 +317,10 @@ fn if_() {
         ast[0],
         Ast::If(If {
             conditions: vec![Condition {
-                kwd_span: None,
+                kwd_span: Some(span!(1, 1, "if")),
                 condition: Some(*ident!(1, 4, "x")),
                 if_true: Block {
-                    brace_spans: None,
+                    brace_spans: Some((span!(1, 6, "{"), span!(1, 8, "}"))),
                     content: vec![]
                 }
             }]
@@ -286,18 +336,18 @@ fn if_else() {
         Ast::If(If {
             conditions: vec![
                 Condition {
-                    kwd_span: None,
+                    kwd_span: Some(span!(1, 1, "if")),
                     condition: Some(*ident!(1, 4, "x")),
                     if_true: Block {
-                        brace_spans: None,
+                        brace_spans: Some((span!(1, 6, "{"), span!(1, 8, "}"))),
                         content: vec![]
                     }
                 },
                 Condition {
-                    kwd_span: None,
+                    kwd_span: Some(span!(1, 10, "else")),
                     condition: None,
                     if_true: Block {
-                        brace_spans: None,
+                        brace_spans: Some((span!(1, 15, "{"), span!(1, 17, "}"))),
                         content: vec![]
                     }
                 }
@@ -314,18 +364,18 @@ fn if_elif() {
         Ast::If(If {
             conditions: vec![
                 Condition {
-                    kwd_span: None,
+                    kwd_span: Some(span!(1, 1, "if")),
                     condition: Some(*ident!(1, 4, "x")),
                     if_true: Block {
-                        brace_spans: None,
+                        brace_spans: Some((span!(1, 6, "{"), span!(1, 8, "}"))),
                         content: vec![]
                     }
                 },
                 Condition {
-                    kwd_span: None,
+                    kwd_span: Some(span!(1, 10, "elif")),
                     condition: Some(*ident!(1, 15, "y")),
                     if_true: Block {
-                        brace_spans: None,
+                        brace_spans: Some((span!(1, 17, "{"), span!(1, 19, "}"))),
                         content: vec![]
                     }
                 }
@@ -342,26 +392,26 @@ fn if_elif_else() {
         Ast::If(If {
             conditions: vec![
                 Condition {
-                    kwd_span: None,
+                    kwd_span: Some(span!(1, 1, "if")),
                     condition: Some(*ident!(1, 4, "x")),
                     if_true: Block {
-                        brace_spans: None,
+                        brace_spans: Some((span!(1, 6, "{"), span!(1, 8, "}"))),
                         content: vec![]
                     }
                 },
                 Condition {
-                    kwd_span: None,
+                    kwd_span: Some(span!(1, 10, "elif")),
                     condition: Some(*ident!(1, 15, "y")),
                     if_true: Block {
-                        brace_spans: None,
+                        brace_spans: Some((span!(1, 17, "{"), span!(1, 19, "}"))),
                         content: vec![]
                     }
                 },
                 Condition {
-                    kwd_span: None,
+                    kwd_span: Some(span!(1, 21, "else")),
                     condition: None,
                     if_true: Block {
-                        brace_spans: None,
+                        brace_spans: Some((span!(1, 26, "{"), span!(1, 28, "}"))),
                         content: vec![]
                     }
                 }
@@ -382,7 +432,7 @@ fn block() {
     assert_eq!(
         ast[0],
         Ast::Block(Block {
-            brace_spans: None,
+            brace_spans: Some((span!(1, 1, "{"), span!(1, 3, "}"))),
             content: vec![*ident!(1, 2, "x")]
         })
     )
@@ -396,7 +446,7 @@ fn preprocess_block() {
         Ast::Preprocess(Preprocess {
             kwd_span: span!(1, 1, "pre"),
             content: Ast::Block(Block {
-                brace_spans: None,
+                brace_spans: Some((span!(1, 5, "{"), span!(1, 7, "}"))),
                 content: vec![*ident!(1, 6, "x")]
             })
             .into()
@@ -424,7 +474,7 @@ fn defer_block() {
         Ast::Defer(Defer {
             kwd_span: span!(1, 1, "defer"),
             content: Ast::Block(Block {
-                brace_spans: None,
+                brace_spans: Some((span!(1, 7, "{"), span!(1, 9, "}"))),
                 content: vec![*ident!(1, 8, "x")]
             })
             .into()
@@ -540,6 +590,19 @@ fn return_something() {
     )
 }
 
+#[test]
+fn un_opr_compl() {
+    let ast = parse!("\\~x");
+    assert_eq!(
+        ast[0],
+        Ast::UnaryOpr(UnaryOpr {
+            ty: OprType::BitCompl,
+            opr_span: Some(span!(1, 1, "\\~")),
+            operand: ident!(1, 3, "x"),
+        })
+    )
+}
+
 #[test]
 #[ignore]
 fn un_opr() {
@@ -615,11 +678,12 @@ fn dot() {
     let ast = parse!("x.y");
     assert_eq!(
         ast[0],
-        Ast::Ident(Ident {
+        Ast::Member(Member {
+            ty: AccessType::Field,
             name: "y".into(),
+            parent: ident!(1, 1, "x"),
             name_span: Some(span!(1, 3, "y")),
-            //dot_span: Some(span!(1, 2, ".")),
-            //parent: Some(ident!(1, 1, "x")) todo
+            dot_span: Some(span!(1, 2, ".")),
         })
     )
 }
@@ -658,11 +722,12 @@ fn dot_call() {
     assert_eq!(
         ast[0],
         Ast::Call(Call {
-            called: Box::new(Ast::Ident(Ident {
+            called: Box::new(Ast::Member(Member {
+                ty: AccessType::Field,
                 name: "y".into(),
+                parent: ident!(1, 1, "x"),
                 name_span: Some(span!(1, 3, "y")),
-                //dot_span: Some(span!(1, 2, ".")),
-                //parent: Some(ident!(1, 1, "x")), todo
+                dot_span: Some(span!(1, 2, ".")),
             })),
             paren_spans: Some((span!(1, 4, "("), span!(1, 5, ")"))),
             args: vec![],